@@ -4,27 +4,84 @@
 //! readability/writability polling.
 
 use std::io;
-use std::os::unix::io::RawFd;
-use std::task::Poll;
+use std::ops::BitOr;
+use std::os::unix::io::{AsRawFd, OwnedFd, RawFd};
+use std::task::{Context, Poll};
 
 use async_ffi::ContextExt;
 
 use crate::ffi;
 
+/// Which directions [`AsyncFd::ready`] waits for. Combine with `|`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interest(u8);
+
+impl Interest {
+    /// Wait for the fd to be readable.
+    pub const READ: Interest = Interest(1);
+    /// Wait for the fd to be writable.
+    pub const WRITE: Interest = Interest(2);
+}
+
+impl BitOr for Interest {
+    type Output = Interest;
+
+    fn bitor(self, rhs: Interest) -> Interest {
+        Interest(self.0 | rhs.0)
+    }
+}
+
+/// Which directions [`AsyncFd::ready`] found ready. Only directions that
+/// were asked for are ever reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Readiness(u8);
+
+impl Readiness {
+    /// Whether the fd is readable.
+    pub fn is_readable(&self) -> bool {
+        self.0 & Interest::READ.0 != 0
+    }
+
+    /// Whether the fd is writable.
+    pub fn is_writable(&self) -> bool {
+        self.0 & Interest::WRITE.0 != 0
+    }
+}
+
 /// A file descriptor registered with the tau-rt reactor for async IO.
 ///
-/// `AsyncFd` does NOT own the file descriptor — it only manages the reactor
-/// registration. The caller is responsible for closing the fd (e.g., via `OwnedFd`).
+/// Created with [`new`](Self::new), `AsyncFd` does NOT own the file
+/// descriptor — it only manages the reactor registration, and the caller must
+/// keep the fd open until the `AsyncFd` is dropped. Created with
+/// [`from_owned`](Self::from_owned), it owns the fd and closes it on drop,
+/// after deregistering.
 pub struct AsyncFd {
     handle: u64,
     fd: RawFd,
+    /// Closed when the `AsyncFd` drops — fields drop after `Drop::drop`, so
+    /// the reactor deregistration always happens first.
+    owned: Option<OwnedFd>,
 }
 
 impl AsyncFd {
     /// Register a file descriptor with the reactor.
     pub fn new(fd: RawFd) -> io::Result<Self> {
         let handle = unsafe { ffi::tau_rt_io_register(fd) };
-        Ok(AsyncFd { handle, fd })
+        Ok(AsyncFd {
+            handle,
+            fd,
+            owned: None,
+        })
+    }
+
+    /// Register a file descriptor with the reactor, taking ownership of it.
+    ///
+    /// The fd is closed when the `AsyncFd` is dropped, after it has been
+    /// removed from the reactor.
+    pub fn from_owned(fd: OwnedFd) -> io::Result<Self> {
+        let mut async_fd = Self::new(fd.as_raw_fd())?;
+        async_fd.owned = Some(fd);
+        Ok(async_fd)
     }
 
     /// Returns the raw file descriptor.
@@ -37,25 +94,98 @@ impl AsyncFd {
         self.handle
     }
 
+    /// Poll for readability from a manual `Future`/`Stream` implementation.
+    ///
+    /// Registers `cx`'s waker with the reactor when not yet readable.
+    pub fn poll_readable(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        cx.with_ffi_context(|ffi_cx| {
+            let result = unsafe { ffi::tau_rt_io_poll_readable(self.handle, ffi_cx as *mut _) };
+            match result {
+                1 => Poll::Ready(Ok(())),
+                0 => Poll::Pending,
+                2 => Poll::Ready(Err(self.last_error())),
+                _ => Poll::Ready(Err(io::Error::other(
+                    "unexpected poll_readable result",
+                ))),
+            }
+        })
+    }
+
+    /// Poll for writability from a manual `Future`/`Stream` implementation.
+    ///
+    /// Registers `cx`'s waker with the reactor when not yet writable.
+    pub fn poll_writable(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        cx.with_ffi_context(|ffi_cx| {
+            let result = unsafe { ffi::tau_rt_io_poll_writable(self.handle, ffi_cx as *mut _) };
+            match result {
+                1 => Poll::Ready(Ok(())),
+                0 => Poll::Pending,
+                2 => Poll::Ready(Err(self.last_error())),
+                _ => Poll::Ready(Err(io::Error::other(
+                    "unexpected poll_writable result",
+                ))),
+            }
+        })
+    }
+
+    /// Poll for any direction in `interest` from a manual `Future`
+    /// implementation.
+    ///
+    /// Registers `cx`'s waker for every direction in `interest` when none is
+    /// ready yet.
+    pub fn poll_ready(
+        &self,
+        cx: &mut Context<'_>,
+        interest: Interest,
+    ) -> Poll<io::Result<Readiness>> {
+        cx.with_ffi_context(|ffi_cx| {
+            let result =
+                unsafe { ffi::tau_rt_io_poll_ready(self.handle, interest.0, ffi_cx as *mut _) };
+            match result {
+                0 => Poll::Pending,
+                0x80 => Poll::Ready(Err(self.last_error())),
+                ready => Poll::Ready(Ok(Readiness(ready))),
+            }
+        })
+    }
+
+    /// Stop waiting for readability: a task parked in
+    /// [`readable`](Self::readable) is forgotten (not woken) and the reactor
+    /// keeps polling for writability only.
+    ///
+    /// For the read half of a split fd going away while the write half
+    /// stays in use.
+    pub fn clear_readable(&self) -> io::Result<()> {
+        self.clear_interest(Interest::READ)
+    }
+
+    /// Stop waiting for writability; the counterpart of
+    /// [`clear_readable`](Self::clear_readable).
+    pub fn clear_writable(&self) -> io::Result<()> {
+        self.clear_interest(Interest::WRITE)
+    }
+
+    fn clear_interest(&self, interest: Interest) -> io::Result<()> {
+        match unsafe { ffi::tau_rt_io_clear_interest(self.handle, interest.0) } {
+            0 => Ok(()),
+            errno => Err(io::Error::from_raw_os_error(-errno)),
+        }
+    }
+
+    /// The error the reactor recorded when registering interest failed.
+    fn last_error(&self) -> io::Error {
+        match unsafe { ffi::tau_rt_io_last_error(self.handle) } {
+            0 => io::Error::other("reactor failed to register fd"),
+            errno => io::Error::from_raw_os_error(errno),
+        }
+    }
+
     /// Wait until the fd is readable.
     ///
     /// After this returns `Ok(())`, you should attempt the read operation.
     /// If it returns `WouldBlock`, call `readable()` again (spurious wake).
     pub async fn readable(&self) -> io::Result<()> {
-        std::future::poll_fn(|cx| {
-            cx.with_ffi_context(|ffi_cx| {
-                let result =
-                    unsafe { ffi::tau_rt_io_poll_readable(self.handle, ffi_cx as *mut _) };
-                match result {
-                    1 => Poll::Ready(Ok(())),
-                    0 => Poll::Pending,
-                    _ => Poll::Ready(Err(io::Error::other(
-                        "unexpected poll_readable result",
-                    ))),
-                }
-            })
-        })
-        .await
+        std::future::poll_fn(|cx| self.poll_readable(cx)).await
     }
 
     /// Wait until the fd is writable.
@@ -63,20 +193,17 @@ impl AsyncFd {
     /// After this returns `Ok(())`, you should attempt the write operation.
     /// If it returns `WouldBlock`, call `writable()` again (spurious wake).
     pub async fn writable(&self) -> io::Result<()> {
-        std::future::poll_fn(|cx| {
-            cx.with_ffi_context(|ffi_cx| {
-                let result =
-                    unsafe { ffi::tau_rt_io_poll_writable(self.handle, ffi_cx as *mut _) };
-                match result {
-                    1 => Poll::Ready(Ok(())),
-                    0 => Poll::Pending,
-                    _ => Poll::Ready(Err(io::Error::other(
-                        "unexpected poll_writable result",
-                    ))),
-                }
-            })
-        })
-        .await
+        std::future::poll_fn(|cx| self.poll_writable(cx)).await
+    }
+
+    /// Wait until the fd is ready in any direction in `interest`, and
+    /// report which directions are.
+    ///
+    /// One await in place of racing `readable()` against `writable()` for a
+    /// duplex fd. Readiness can be spurious in the same way: try each
+    /// reported operation, and wait again on `WouldBlock`.
+    pub async fn ready(&self, interest: Interest) -> io::Result<Readiness> {
+        std::future::poll_fn(|cx| self.poll_ready(cx, interest)).await
     }
 }
 
@@ -85,3 +212,83 @@ impl Drop for AsyncFd {
         unsafe { ffi::tau_rt_io_deregister(self.handle) };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn from_owned_closes_fd_on_drop() {
+        use std::os::unix::io::FromRawFd;
+
+        let mut fds = [0 as libc::c_int; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        let read_end = unsafe { OwnedFd::from_raw_fd(fds[0]) };
+        let write_end = unsafe { OwnedFd::from_raw_fd(fds[1]) };
+
+        let async_fd = AsyncFd::from_owned(read_end).unwrap();
+        assert_eq!(async_fd.as_raw_fd(), fds[0]);
+        drop(async_fd);
+
+        // The read end is gone, so writing reports a broken pipe.
+        let n = unsafe {
+            libc::write(write_end.as_raw_fd(), b"x".as_ptr() as *const libc::c_void, 1)
+        };
+        assert_eq!(n, -1);
+        assert_eq!(io::Error::last_os_error().raw_os_error(), Some(libc::EPIPE));
+    }
+
+    #[test]
+    fn readable_on_closed_fd_returns_error() {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            // Far above any RLIMIT_NOFILE, so it can never be an open fd.
+            let async_fd = AsyncFd::new(1_000_000).unwrap();
+            *result_clone.lock().unwrap() = Some(async_fd.readable().await);
+        });
+
+        let err = result.lock().unwrap().take().unwrap().unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EBADF));
+    }
+
+    #[test]
+    fn ready_reports_both_directions_on_a_socketpair() {
+        use std::io::Write;
+
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let (a, mut b) = std::os::unix::net::UnixStream::pair().unwrap();
+            let async_fd = AsyncFd::new(a.as_raw_fd()).unwrap();
+            // Writable from the start, and readable once the peer has written.
+            b.write_all(b"x").unwrap();
+            let readiness = async_fd.ready(Interest::READ | Interest::WRITE).await;
+            *result_clone.lock().unwrap() = Some(readiness);
+            drop(async_fd);
+        });
+
+        let readiness = result.lock().unwrap().take().unwrap().unwrap();
+        assert!(readiness.is_readable());
+        assert!(readiness.is_writable());
+    }
+
+    #[test]
+    fn clear_readable_leaves_writable_armed() {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let (a, _b) = std::os::unix::net::UnixStream::pair().unwrap();
+            let async_fd = AsyncFd::new(a.as_raw_fd()).unwrap();
+            async_fd.clear_readable().unwrap();
+            *result_clone.lock().unwrap() = Some(async_fd.writable().await);
+            drop(async_fd);
+        });
+
+        result.lock().unwrap().take().unwrap().unwrap();
+    }
+}
@@ -3,7 +3,20 @@
 //! These mirror the exports from `libtau_rt.dylib` / `libtau_rt.so` exactly.
 //! Linked at load time via `#[link(name = "tau_rt")]`.
 
-use async_ffi::{FfiContext, FfiFuture};
+use async_ffi::{FfiContext, FfiFuture, LocalFfiFuture};
+
+/// Snapshot of the runtime's monotonic counters. Mirrors tau-rt's `TauMetrics`.
+#[repr(C)]
+#[derive(Default)]
+pub struct TauMetrics {
+    pub tasks_spawned: u64,
+    pub tasks_completed: u64,
+    pub tasks_polled: u64,
+    pub timers_fired: u64,
+    pub io_events: u64,
+    pub react_calls: u64,
+    pub watchdog_trips: u64,
+}
 
 #[link(name = "tau_rt")]
 extern "C" {
@@ -15,12 +28,46 @@ extern "C" {
     /// Deregister and remove an IO source.
     pub fn tau_rt_io_deregister(handle: u64);
 
-    /// Poll for readability. Returns 0=Pending, 1=Ready.
+    /// Poll for readability. Returns 0=Pending, 1=Ready, 2=Error.
     pub fn tau_rt_io_poll_readable(handle: u64, cx: *mut FfiContext<'_>) -> u8;
 
-    /// Poll for writability. Returns 0=Pending, 1=Ready.
+    /// Poll for writability. Returns 0=Pending, 1=Ready, 2=Error.
     pub fn tau_rt_io_poll_writable(handle: u64, cx: *mut FfiContext<'_>) -> u8;
 
+    /// Poll for readability and/or writability at once. `interest` and the
+    /// result are bitmasks: 1=Readable, 2=Writable. Returns 0=Pending, the
+    /// directions that are ready, or 0x80=Error.
+    pub fn tau_rt_io_poll_ready(handle: u64, interest: u8, cx: *mut FfiContext<'_>) -> u8;
+
+    /// Stop waiting for the directions in the `interest` bitmask (1=Readable,
+    /// 2=Writable), re-arming only the rest. Returns 0, or a negated OS error.
+    pub fn tau_rt_io_clear_interest(handle: u64, interest: u8) -> i32;
+
+    /// OS error code recorded for an IO source after a poll returned 2=Error.
+    /// Returns 0 if no error is recorded.
+    pub fn tau_rt_io_last_error(handle: u64) -> i32;
+
+    /// Check waited-on fds for having been closed every `nanos` nanoseconds,
+    /// waking their waiters with `EBADF`. 0 turns the check off (the default).
+    pub fn tau_rt_set_fd_check_interval(nanos: u64);
+
+    // ── Processes ───────────────────────────────────────────────────
+
+    /// Watch process `pid` for exit. Returns an IO handle (>= 0), or a
+    /// negated OS error code on failure. Release with `tau_rt_io_deregister`.
+    pub fn tau_rt_pidfd_create(pid: i32) -> i64;
+
+    /// Poll for process exit. Returns 0=Pending, 1=Exited, 2=Error.
+    pub fn tau_rt_pidfd_poll(handle: u64, cx: *mut FfiContext<'_>) -> u8;
+
+    // ── Signals ─────────────────────────────────────────────────────
+
+    /// Install the process-wide `SIGINT` handler (first call only) and
+    /// return a non-blocking fd that becomes readable, with one byte per
+    /// signal, each time Ctrl+C arrives. Returns a negated OS error code on
+    /// failure. The fd belongs to the runtime: never close it.
+    pub fn tau_rt_ctrl_c_fd() -> i32;
+
     // ── Timers ──────────────────────────────────────────────────────
 
     /// Create a timer. Deadline is nanoseconds from now. Returns opaque handle.
@@ -32,19 +79,76 @@ extern "C" {
     /// Poll a timer. Returns 0=Pending, 1=Ready.
     pub fn tau_rt_timer_poll(handle: u64, cx: *mut FfiContext<'_>) -> u8;
 
+    /// Monotonic nanoseconds since the runtime started, on the clock timers use.
+    pub fn tau_rt_now_nanos() -> u64;
+
+    /// Nanoseconds the runtime clock is ahead of the real monotonic clock
+    /// (negative if behind). Zero unless a virtual clock is active.
+    pub fn tau_rt_clock_offset_nanos() -> i64;
+
+    /// Set the shortest sleep a pending timer can make the reactor take.
+    pub fn tau_rt_set_min_sleep(nanos: u64);
+
     // ── Executor ────────────────────────────────────────────────────
 
     /// Spawn a future onto the shared executor.
     pub fn tau_rt_spawn(future: FfiFuture<()>);
 
+    /// Spawn a `!Send` future that only runs on the calling thread.
+    pub fn tau_rt_spawn_local(future: LocalFfiFuture<()>);
+
     /// Poll one ready task. Returns 0=no work, 1=did work.
     pub fn tau_rt_try_tick() -> u8;
 
+    /// Poll up to `max` ready tasks. Returns how many were polled.
+    pub fn tau_rt_try_tick_n(max: u64) -> u64;
+
     /// Run the reactor once (process IO + timers, wake tasks).
     /// timeout_ms: milliseconds to wait. 0 = non-blocking.
-    /// Returns 0=ok, -1=error.
+    /// Returns the number of timer/IO events handled (>= 0), or the negated OS
+    /// error code of the failing poller wait.
     pub fn tau_rt_react(timeout_ms: u64) -> i32;
 
+    /// Park in the reactor until IO, a timer or a cross-thread wakeup (a
+    /// waker or spawn from another thread) arrives, or `timeout_nanos`
+    /// passes (`u64::MAX` = no timeout). Returns like `tau_rt_react`.
+    pub fn tau_rt_park(timeout_nanos: u64) -> i32;
+
     /// Block the current thread until the future completes.
-    pub fn tau_rt_block_on(future: FfiFuture<()>);
+    /// Returns 1 once the future completes, 0 if the watchdog abandoned it,
+    /// or 2 (dropping the future unpolled) if called from inside a task the
+    /// runtime is polling.
+    pub fn tau_rt_block_on(future: FfiFuture<()>) -> u8;
+
+    /// `tau_rt_block_on`, tuning how it waits while no task is ready: up to
+    /// `max_spin_iters` non-blocking reactor polls, then blocking reactor
+    /// waits of at most `idle_timeout_nanos` each (`u64::MAX` = until an
+    /// event).
+    pub fn tau_rt_block_on_cfg(
+        future: FfiFuture<()>,
+        max_spin_iters: u32,
+        idle_timeout_nanos: u64,
+    ) -> u8;
+
+    /// Configure the `block_on` watchdog: `limit` back-to-back polls of one
+    /// task without IO, timer or outside-wake progress trip it (0, the
+    /// default, disables); `abandon` != 0 makes `block_on` give up on its
+    /// future when it trips.
+    pub fn tau_rt_set_watchdog(limit: u64, abandon: u8);
+
+    /// Stop accepting spawns and drain spawned tasks for up to `timeout_ms`.
+    /// Returns the number of tasks still pending.
+    pub fn tau_rt_shutdown(timeout_ms: u64) -> u64;
+
+    // ── Metrics ─────────────────────────────────────────────────────
+
+    /// Number of IO sources currently registered with the reactor.
+    pub fn tau_rt_io_count() -> u64;
+
+    /// Number of timers currently live (created, not yet completed or
+    /// cancelled).
+    pub fn tau_rt_timer_count() -> u64;
+
+    /// Write a snapshot of the runtime counters into `out`.
+    pub fn tau_rt_metrics(out: *mut TauMetrics);
 }
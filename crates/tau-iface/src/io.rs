@@ -0,0 +1,390 @@
+//! IO helpers built on top of the socket types.
+
+use std::io;
+use std::pin::Pin;
+
+use futures_io::{AsyncRead, AsyncWrite};
+
+use crate::sync::Notify;
+use crate::tcp::{ReadHalf, TcpStream, WriteHalf};
+use crate::util::{join_all, race, Either};
+
+/// Size of the buffer each copy direction reads into.
+const COPY_BUF: usize = 8192;
+
+/// Default [`BufWriter`] capacity.
+const DEFAULT_BUF_CAPACITY: usize = 8192;
+
+/// Read some bytes into `buf`, returning how many were read (0 at EOF).
+async fn read<R: AsyncRead + Unpin + ?Sized>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    std::future::poll_fn(|cx| Pin::new(&mut *reader).poll_read(cx, buf)).await
+}
+
+/// Close `writer`, for a socket half-close: the peer sees EOF.
+async fn close<W: AsyncWrite + Unpin + ?Sized>(writer: &mut W) -> io::Result<()> {
+    std::future::poll_fn(|cx| Pin::new(&mut *writer).poll_close(cx)).await
+}
+
+/// Write all of `buf` to `writer`, failing with `WriteZero` if it stops
+/// accepting bytes.
+async fn write_all<W>(writer: &mut W, mut buf: &[u8]) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    while !buf.is_empty() {
+        let n = std::future::poll_fn(|cx| Pin::new(&mut *writer).poll_write(cx, buf)).await?;
+        if n == 0 {
+            return Err(io::Error::from(io::ErrorKind::WriteZero));
+        }
+        buf = &buf[n..];
+    }
+    Ok(())
+}
+
+/// Batches small writes into an internal buffer, so a chatty encoder costs
+/// one syscall per flush instead of one per `write`.
+///
+/// Bytes reach the inner writer only on [`flush`](Self::flush), or
+/// automatically when a write would take the buffer past its capacity.
+/// Dropping a `BufWriter` discards anything not yet flushed — there is no
+/// async drop — so flush before letting it go.
+pub struct BufWriter<W> {
+    inner: W,
+    buf: Vec<u8>,
+    capacity: usize,
+}
+
+impl<W: AsyncWrite + Unpin> BufWriter<W> {
+    /// Wrap `inner` with an 8 KiB buffer.
+    pub fn new(inner: W) -> Self {
+        Self::with_capacity(DEFAULT_BUF_CAPACITY, inner)
+    }
+
+    /// Wrap `inner` with a buffer of `capacity` bytes.
+    pub fn with_capacity(capacity: usize, inner: W) -> Self {
+        BufWriter {
+            inner,
+            buf: Vec::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Buffer all of `buf`, first flushing if it wouldn't fit. Data at least
+    /// as large as the capacity bypasses the buffer and is written directly.
+    pub async fn write(&mut self, buf: &[u8]) -> io::Result<()> {
+        if self.buf.len() + buf.len() > self.capacity {
+            self.flush().await?;
+        }
+        if buf.len() >= self.capacity {
+            return write_all(&mut self.inner, buf).await;
+        }
+        self.buf.extend_from_slice(buf);
+        Ok(())
+    }
+
+    /// Write everything buffered to the inner writer.
+    ///
+    /// On error, the bytes not yet written stay buffered.
+    pub async fn flush(&mut self) -> io::Result<()> {
+        let mut written = 0;
+        let result = loop {
+            if written == self.buf.len() {
+                break Ok(());
+            }
+            let pending = &self.buf[written..];
+            let inner = &mut self.inner;
+            match std::future::poll_fn(|cx| Pin::new(&mut *inner).poll_write(cx, pending)).await {
+                Ok(0) => break Err(io::Error::from(io::ErrorKind::WriteZero)),
+                Ok(n) => written += n,
+                Err(err) => break Err(err),
+            }
+        };
+        self.buf.drain(..written);
+        result
+    }
+
+    /// The bytes buffered and not yet flushed.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// The buffer's capacity.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner writer. Writing to it directly
+    /// bypasses (and reorders around) the buffered bytes.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwrap the inner writer, discarding unflushed bytes.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Copy data between two streams in both directions until both sides close.
+///
+/// Each direction is a read → write loop from one stream's
+/// [`split`](TcpStream::split) read half to the other's write half. When one
+/// direction hits EOF, it closes its write half so the peer sees EOF too —
+/// a half-close. The other direction is left running, since the peer may
+/// still have data to send, and the copy resolves only once both directions
+/// are drained.
+///
+/// If one direction fails, the other still finishes writing whatever it
+/// has already read, then stops before its next read; both destinations get
+/// their write half shut down, and the error is returned (`a → b`'s if both
+/// fail).
+///
+/// Returns `(a_to_b, b_to_a)` byte counts.
+pub async fn copy_bidirectional(a: &TcpStream, b: &TcpStream) -> io::Result<(u64, u64)> {
+    let (a_read, a_write) = a.split();
+    let (b_read, b_write) = b.split();
+    let failed = Notify::new();
+    let mut results = join_all(vec![
+        copy_one_way(a_read, b_write, &failed),
+        copy_one_way(b_read, a_write, &failed),
+    ])
+    .await
+    .into_iter();
+    let a_to_b = results.next().unwrap();
+    let b_to_a = results.next().unwrap();
+    Ok((a_to_b?, b_to_a?))
+}
+
+/// Copy `from` → `to` until EOF or until `failed` is notified, then shut
+/// down `to`'s write half. Notifies `failed` on error.
+async fn copy_one_way(
+    mut from: ReadHalf<'_>,
+    mut to: WriteHalf<'_>,
+    failed: &Notify,
+) -> io::Result<u64> {
+    let result = copy_until_eof(&mut from, &mut to, failed).await;
+    if result.is_err() {
+        failed.notify();
+        // Best effort: let the peer see EOF rather than wait forever.
+        let _ = close(&mut to).await;
+    }
+    result
+}
+
+async fn copy_until_eof(
+    from: &mut ReadHalf<'_>,
+    to: &mut WriteHalf<'_>,
+    failed: &Notify,
+) -> io::Result<u64> {
+    let mut buf = [0u8; COPY_BUF];
+    let mut total = 0u64;
+    loop {
+        // Reads are cancel-safe: nothing has been consumed until one
+        // completes, so stopping here loses no data.
+        let n = match race(read(from, &mut buf), failed.notified()).await {
+            Either::Left(n) => n?,
+            Either::Right(()) => {
+                let _ = close(to).await;
+                return Ok(total);
+            }
+        };
+        if n == 0 {
+            close(to).await?;
+            return Ok(total);
+        }
+        write_all(to, &buf[..n]).await?;
+        total += n as u64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Shutdown;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+
+    use crate::tcp::pair;
+
+    /// Accepts everything, recording each `poll_write` call's bytes.
+    struct CountingWriter {
+        writes: Arc<Mutex<Vec<Vec<u8>>>>,
+    }
+
+    impl AsyncWrite for CountingWriter {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            self.writes.lock().unwrap().push(buf.to_vec());
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[test]
+    fn buf_writer_batches_small_writes_into_one() {
+        let writes = Arc::new(Mutex::new(Vec::new()));
+        let mut writer = BufWriter::new(CountingWriter {
+            writes: writes.clone(),
+        });
+
+        crate::block_on(async move {
+            let parts: [&[u8]; 4] = [b"GET ", b"/ ", b"HTTP/1.1", b"\r\n\r\n"];
+            for part in parts {
+                writer.write(part).await.unwrap();
+            }
+            assert_eq!(writer.buffer().len(), 18);
+            writer.flush().await.unwrap();
+            assert!(writer.buffer().is_empty());
+        });
+
+        assert_eq!(*writes.lock().unwrap(), vec![b"GET / HTTP/1.1\r\n\r\n".to_vec()]);
+    }
+
+    #[test]
+    fn buf_writer_flushes_when_capacity_exceeded() {
+        let writes = Arc::new(Mutex::new(Vec::new()));
+        let mut writer = BufWriter::with_capacity(
+            8,
+            CountingWriter {
+                writes: writes.clone(),
+            },
+        );
+
+        crate::block_on(async move {
+            writer.write(b"abcde").await.unwrap();
+            writer.write(b"fgh").await.unwrap(); // exactly full: still buffered
+            writer.write(b"ij").await.unwrap(); // would overflow: flush first
+            writer.write(b"0123456789").await.unwrap(); // oversized: direct
+            writer.flush().await.unwrap();
+        });
+
+        let expected: Vec<Vec<u8>> = vec![
+            b"abcdefgh".to_vec(),
+            b"ij".to_vec(),
+            b"0123456789".to_vec(),
+        ];
+        assert_eq!(*writes.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn buf_writer_over_tcp_stream() {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let (a, b) = pair().unwrap();
+            let mut writer = BufWriter::new(&a);
+            for i in 0..100u8 {
+                writer.write(&[i]).await.unwrap();
+            }
+            writer.flush().await.unwrap();
+            a.shutdown(Shutdown::Write).unwrap();
+
+            let mut received = Vec::new();
+            b.read_to_end(&mut received).await.unwrap();
+            *result_clone.lock().unwrap() = Some(received);
+        });
+
+        let expected: Vec<u8> = (0..100).collect();
+        assert_eq!(result.lock().unwrap().take(), Some(expected));
+    }
+
+    #[test]
+    fn copy_bidirectional_proxies_to_echo_server() {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            // client <-> proxy_front ... proxy_back <-> server
+            let (client, proxy_front) = pair().unwrap();
+            let (proxy_back, server) = pair().unwrap();
+
+            // Echo server: send back everything, close when the client does.
+            crate::spawn(async move {
+                let mut buf = [0u8; 64];
+                loop {
+                    let n = server.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        server.shutdown(Shutdown::Write).unwrap();
+                        break;
+                    }
+                    server.write(&buf[..n]).await.unwrap();
+                }
+            });
+
+            let (counts_tx, mut counts_rx) = crate::sync::channel(1);
+            crate::spawn(async move {
+                let counts = copy_bidirectional(&proxy_front, &proxy_back).await.unwrap();
+                let _ = counts_tx.send(counts).await;
+            });
+
+            client.write(b"hello").await.unwrap();
+            client.shutdown(Shutdown::Write).unwrap();
+            let mut echoed = Vec::new();
+            client.read_to_end(&mut echoed).await.unwrap();
+
+            // The proxy finishes right after forwarding the server's EOF.
+            let counts = counts_rx.recv().await;
+            *result_clone.lock().unwrap() = Some((echoed, counts));
+        });
+
+        let (echoed, counts) = result.lock().unwrap().take().unwrap();
+        assert_eq!(echoed, b"hello");
+        assert_eq!(counts, Some((5, 5)));
+    }
+
+    #[test]
+    fn copy_bidirectional_keeps_reverse_direction_open_after_half_close() {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let (client, proxy_front) = pair().unwrap();
+            let (proxy_back, server) = pair().unwrap();
+
+            // Server: answer only after the client's EOF arrives through
+            // the proxy, then close.
+            crate::spawn(async move {
+                let mut request = Vec::new();
+                server.read_to_end(&mut request).await.unwrap();
+                let mut reply = request;
+                reply.extend_from_slice(b" -- and goodbye");
+                write_all(&mut &server, &reply).await.unwrap();
+                server.shutdown(Shutdown::Write).unwrap();
+            });
+
+            let (counts_tx, mut counts_rx) = crate::sync::channel(1);
+            crate::spawn(async move {
+                let counts = copy_bidirectional(&proxy_front, &proxy_back).await.unwrap();
+                let _ = counts_tx.send(counts).await;
+            });
+
+            client.write(b"hello world").await.unwrap();
+            client.shutdown(Shutdown::Write).unwrap();
+            let mut reply = Vec::new();
+            client.read_to_end(&mut reply).await.unwrap();
+
+            let counts = counts_rx.recv().await;
+            *result_clone.lock().unwrap() = Some((reply, counts));
+        });
+
+        let (reply, counts) = result.lock().unwrap().take().unwrap();
+        assert_eq!(reply, b"hello world -- and goodbye");
+        assert_eq!(counts, Some((11, 26)));
+    }
+}
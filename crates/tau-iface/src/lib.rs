@@ -24,21 +24,27 @@
 pub mod ffi;
 
 pub mod async_fd;
+pub mod io;
+pub mod process;
+pub mod signal;
 pub mod timer;
+pub mod sync;
 pub mod tcp;
 pub mod udp;
+pub mod util;
 
 // Re-exports for convenience
-pub use async_fd::AsyncFd;
-pub use tcp::{TcpListener, TcpStream};
-pub use timer::Timer;
+pub use async_fd::{AsyncFd, Interest, Readiness};
+pub use sync::{CancellationToken, Notify};
+pub use tcp::{BlockingTcpStream, TcpListener, TcpStream};
+pub use timer::{DelayQueue, Interval, Timer};
 pub use udp::UdpSocket;
 
 use std::future::Future;
-use std::io;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use async_ffi::{FfiFuture, FutureExt};
+use async_ffi::{FfiFuture, FutureExt, LocalFfiFuture};
 
 /// Spawn a future onto the shared executor.
 ///
@@ -51,6 +57,52 @@ where
     unsafe { ffi::tau_rt_spawn(ffi_future) };
 }
 
+/// Spawn a `!Send` future onto the current thread.
+///
+/// Unlike `spawn()`, the future may hold `Rc`s and other thread-bound state.
+/// Local tasks never migrate: they are polled only by `try_tick()` or
+/// `block_on()` on the thread that spawned them, even when woken from another
+/// thread. A thread that never drives the runtime never runs its local tasks.
+pub fn spawn_local<F>(future: F)
+where
+    F: Future<Output = ()> + 'static,
+{
+    let ffi_future: LocalFfiFuture<()> = future.into_local_ffi();
+    unsafe { ffi::tau_rt_spawn_local(ffi_future) };
+}
+
+/// Spawn a future onto the shared executor for as long as the returned
+/// guard is held.
+///
+/// Dropping the [`TaskGuard`] aborts the task: it is woken and, on its next
+/// poll, drops the future without polling it again, releasing whatever it
+/// holds (timers, sockets). Binds background work to the lifetime of the
+/// connection or component that owns it.
+pub fn spawn_guarded<F>(future: F) -> TaskGuard
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let token = CancellationToken::new();
+    let cancelled = token.clone();
+    spawn(async move {
+        let _ = util::race(cancelled.cancelled(), future).await;
+    });
+    TaskGuard { token }
+}
+
+/// Keeps a task started by [`spawn_guarded`] running; dropping it aborts
+/// the task.
+#[must_use = "dropping the guard aborts the task"]
+pub struct TaskGuard {
+    token: CancellationToken,
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        self.token.cancel();
+    }
+}
+
 /// Sleep for the given duration.
 ///
 /// This is a convenience wrapper around `Timer::after(duration).await`.
@@ -58,16 +110,146 @@ pub async fn sleep(duration: Duration) {
     Timer::after(duration).await
 }
 
+/// The current time on the runtime's clock — the one timer deadlines are
+/// measured against.
+///
+/// Equal to `Instant::now()` on the real clock; under a virtual clock it is
+/// the virtual time.
+pub fn now() -> Instant {
+    let offset = unsafe { ffi::tau_rt_clock_offset_nanos() };
+    let real = Instant::now();
+    let shift = Duration::from_nanos(offset.unsigned_abs());
+    if offset >= 0 {
+        real + shift
+    } else {
+        real.checked_sub(shift).unwrap_or(real)
+    }
+}
+
+/// Set the timer granularity: the shortest the reactor will sleep waiting
+/// for a timer.
+///
+/// A timer due sooner than this fires up to `floor` late instead of the
+/// reactor spinning until its deadline. Explicit `react()` timeouts are
+/// unaffected. Defaults to 1ms; `Duration::ZERO` disables the floor.
+pub fn set_timer_granularity(floor: Duration) {
+    let nanos = floor.as_nanos().min(u64::MAX as u128) as u64;
+    unsafe { ffi::tau_rt_set_min_sleep(nanos) };
+}
+
+/// Periodically check fds that tasks are waiting on for having been closed.
+///
+/// An fd closed while an [`AsyncFd`] still waits on it
+/// never becomes ready. With a check every `interval`, such a wait fails
+/// with `EBADF` instead of hanging. The check bounds every reactor sleep
+/// by `interval` and costs a syscall per waited-on fd, so it's off (`None`)
+/// by default — enable it to track down a close/deregister ordering bug.
+///
+/// An fd whose number was reused by a new file before the check ran looks
+/// open; the wait only fails once it re-arms.
+pub fn set_fd_check_interval(interval: Option<Duration>) {
+    let nanos = interval.map_or(0, |i| i.as_nanos().clamp(1, u64::MAX as u128) as u64);
+    unsafe { ffi::tau_rt_set_fd_check_interval(nanos) };
+}
+
 /// Block the current thread until the future completes.
 ///
 /// Drives both the reactor (IO + timers) and executor (spawned tasks)
-/// internally until the future resolves.
+/// internally until the future resolves. While no task is ready it parks
+/// in the reactor until IO, a timer or a wakeup from another thread; see
+/// [`block_on_cfg`] to spin or wake periodically instead.
+///
+/// # Panics
+///
+/// If called from inside a task the runtime is polling — `.await` the
+/// future there instead. Also if the watchdog abandons the future (see
+/// [`set_watchdog`]).
 pub fn block_on<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    block_on_cfg(future, BlockOnConfig::default());
+}
+
+/// How [`block_on_cfg`] waits while no task is ready to run.
+///
+/// The default parks until an event arrives: no CPU while idle, at the cost
+/// of a syscall round trip per wakeup. Latency-sensitive loops can spin
+/// first; loops that must regain control regularly can cap each wait.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockOnConfig {
+    /// Non-blocking reactor polls to make, rechecking for ready tasks after
+    /// each, before blocking.
+    pub max_spin_iters: u32,
+    /// Longest a single blocking reactor wait may take; `None` waits until
+    /// IO, a timer or a cross-thread wakeup.
+    pub idle_timeout: Option<Duration>,
+}
+
+/// [`block_on`] with an explicit idle strategy.
+///
+/// # Panics
+///
+/// Same as [`block_on`].
+pub fn block_on_cfg<F>(future: F, config: BlockOnConfig)
 where
     F: Future<Output = ()> + Send + 'static,
 {
     let ffi_future: FfiFuture<()> = future.into_ffi();
-    unsafe { ffi::tau_rt_block_on(ffi_future) };
+    let idle_timeout_nanos = match config.idle_timeout {
+        Some(timeout) => timeout.as_nanos().min(u64::MAX as u128 - 1) as u64,
+        None => u64::MAX,
+    };
+    let code = unsafe {
+        ffi::tau_rt_block_on_cfg(ffi_future, config.max_spin_iters, idle_timeout_nanos)
+    };
+    match code {
+        0 => panic!("block_on() abandoned: a task kept waking itself without making progress"),
+        2 => panic!("block_on() called from within the runtime; .await the future instead"),
+        _ => {}
+    }
+}
+
+/// Drive the runtime until Ctrl+C, for programs whose work all happens in
+/// spawned tasks — a server that spawns its accept loop, then waits.
+///
+/// Like [`block_on`], parks while idle. Returns `Ok(())` once `SIGINT`
+/// arrives, with spawned tasks left as they are (see [`shutdown`]), or an
+/// error if the signal handler could not be installed. Installing it is
+/// permanent; see [`signal::ctrl_c`].
+///
+/// # Panics
+///
+/// Same as [`block_on`].
+pub fn run_until_ctrl_c() -> std::io::Result<()> {
+    block_on_output(signal::ctrl_c())
+}
+
+/// Configure the livelock watchdog in `block_on()`.
+///
+/// When one task is polled `limit` times in a row with no IO or timer event,
+/// and no wake from outside the task, in between — typically a future that
+/// wakes itself on every poll — the watchdog trips: it logs a warning (if
+/// tau-rt is built with `trace`) and bumps [`RuntimeMetrics::watchdog_trips`].
+/// With `abandon`, `block_on()` also drops its future and panics. Off by
+/// default; a `limit` of 0 disables the watchdog.
+pub fn set_watchdog(limit: u64, abandon: bool) {
+    unsafe { ffi::tau_rt_set_watchdog(limit, u8::from(abandon)) };
+}
+
+/// `block_on()` for a future with an output, handing the output back.
+pub(crate) fn block_on_output<T, F>(future: F) -> T
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
+    let slot = Arc::new(Mutex::new(None));
+    let out = slot.clone();
+    block_on(async move {
+        *out.lock().unwrap() = Some(future.await);
+    });
+    let output = slot.lock().unwrap().take();
+    output.expect("block_on() returned before its future completed")
 }
 
 /// Poll one ready task from the executor queue.
@@ -77,11 +259,83 @@ pub fn try_tick() -> bool {
     unsafe { ffi::tau_rt_try_tick() != 0 }
 }
 
+/// Poll up to `max` ready tasks from the executor queue.
+///
+/// Returns how many were polled; fewer than `max` means the queue is empty.
+/// Bounds the work done per iteration of a loop that embeds the runtime:
+/// run at most N tasks, then render, then `react()`.
+pub fn try_tick_n(max: usize) -> usize {
+    unsafe { ffi::tau_rt_try_tick_n(max as u64) as usize }
+}
+
+/// Shut the runtime down gracefully.
+///
+/// New `spawn()` calls are dropped from this point on. Already-spawned tasks
+/// keep being driven (executor and reactor) until they all complete or
+/// `timeout` elapses. Returns how many tasks were still pending; those are
+/// abandoned. Call it once, after `block_on()` returns and before exit.
+pub fn shutdown(timeout: Duration) -> usize {
+    let timeout_ms = timeout.as_millis().min(u64::MAX as u128) as u64;
+    let pending = unsafe { ffi::tau_rt_shutdown(timeout_ms) };
+    pending as usize
+}
+
+/// Snapshot of the runtime's monotonic counters, for dashboards and debugging.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuntimeMetrics {
+    /// Tasks passed to `spawn()`.
+    pub tasks_spawned: u64,
+    /// Spawned tasks that ran to completion.
+    pub tasks_completed: u64,
+    /// Task polls performed by `try_tick()` (including inside `block_on()`).
+    pub tasks_polled: u64,
+    /// Timers whose deadline expired.
+    pub timers_fired: u64,
+    /// IO readiness events delivered by the OS poller.
+    pub io_events: u64,
+    /// Calls to `react()`.
+    pub react_calls: u64,
+    /// Times the `block_on()` watchdog tripped (see [`set_watchdog`]).
+    pub watchdog_trips: u64,
+}
+
+/// Read the runtime's counters. Cheap — a handful of relaxed atomic loads.
+pub fn metrics() -> RuntimeMetrics {
+    let mut raw = ffi::TauMetrics::default();
+    unsafe { ffi::tau_rt_metrics(&mut raw) };
+    RuntimeMetrics {
+        tasks_spawned: raw.tasks_spawned,
+        tasks_completed: raw.tasks_completed,
+        tasks_polled: raw.tasks_polled,
+        timers_fired: raw.timers_fired,
+        io_events: raw.io_events,
+        react_calls: raw.react_calls,
+        watchdog_trips: raw.watchdog_trips,
+    }
+}
+
+/// Number of IO sources (sockets, [`AsyncFd`]s, process handles) currently
+/// registered with the reactor. A count that keeps growing points at
+/// handles that are never dropped.
+pub fn io_source_count() -> usize {
+    unsafe { ffi::tau_rt_io_count() as usize }
+}
+
+/// Number of live timers: created by a [`Timer`], `sleep()` and the like,
+/// and not yet completed or dropped. Like [`io_source_count`], useful for
+/// spotting leaks.
+pub fn timer_count() -> usize {
+    unsafe { ffi::tau_rt_timer_count() as usize }
+}
+
 /// Run the reactor once: process expired timers, poll OS for IO events.
 ///
 /// - `Some(duration)` — wait up to `duration` for events
 /// - `None` — wait indefinitely until an event occurs
-pub fn react(timeout: Option<Duration>) -> io::Result<()> {
+///
+/// Returns the number of timer and IO events handled, or the OS error from
+/// the poller (e.g. `EBADF`, `ENOMEM`).
+pub fn react(timeout: Option<Duration>) -> std::io::Result<usize> {
     let timeout_ms = match timeout {
         Some(d) => {
             let ms = d.as_millis();
@@ -93,10 +347,350 @@ pub fn react(timeout: Option<Duration>) -> io::Result<()> {
         }
         None => u64::MAX, // effectively infinite
     };
-    let result = unsafe { ffi::tau_rt_react(timeout_ms) };
-    if result < 0 {
-        Err(io::Error::other("reactor error"))
+    react_result(unsafe { ffi::tau_rt_react(timeout_ms) })
+}
+
+/// Wait until the runtime has something to do: IO readiness, a timer
+/// deadline, or a wakeup from another thread — a waker firing or a task
+/// spawned there — or until `timeout` passes. `None` waits indefinitely.
+///
+/// This is the canonical "wait for IO/timer" call for loops that drive the
+/// executor themselves: park, then [`run_until_idle`] (or [`try_tick`]
+/// until empty), and repeat. Parking handles fired timers and IO events the
+/// same way [`react`] does, at nanosecond rather than millisecond precision.
+/// Returns the number of timer and IO events handled; a cross-thread wakeup
+/// alone returns `Ok(0)`.
+pub fn parked(timeout: Option<Duration>) -> std::io::Result<usize> {
+    let timeout_nanos = match timeout {
+        Some(d) => d.as_nanos().min(u64::MAX as u128 - 1) as u64,
+        None => u64::MAX,
+    };
+    react_result(unsafe { ffi::tau_rt_park(timeout_nanos) })
+}
+
+/// Decode `tau_rt_react`'s return: an event count, or a negated errno.
+fn react_result(code: i32) -> std::io::Result<usize> {
+    if code < 0 {
+        Err(std::io::Error::from_raw_os_error(-code))
     } else {
-        Ok(())
+        Ok(code as usize)
+    }
+}
+
+/// Drain all work that is ready right now, without blocking.
+///
+/// Alternates between polling ready tasks (`try_tick()` until the queue is
+/// empty) and a non-blocking [`parked`] until neither makes progress.
+/// Returns the total number of task polls and reactor events handled.
+///
+/// This is the integration point for driving tau from a foreign event loop:
+/// call it whenever the host loop wakes up.
+///
+/// # Panics
+///
+/// Panics if polling the reactor fails; call [`parked`] directly to handle
+/// that error instead.
+pub fn run_until_idle() -> usize {
+    let mut work = 0;
+    loop {
+        while try_tick() {
+            work += 1;
+        }
+        match parked(Some(Duration::ZERO)) {
+            Ok(0) => return work,
+            Ok(events) => work += events,
+            Err(err) => panic!("run_until_idle(): polling the reactor failed: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn dropping_task_guard_aborts_sleeping_task() {
+        /// Records when the task's future is dropped.
+        struct SetOnDrop(Arc<AtomicUsize>);
+        impl Drop for SetOnDrop {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let finished = Arc::new(AtomicUsize::new(0));
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let (finished_clone, dropped_clone) = (finished.clone(), dropped.clone());
+        let start = Instant::now();
+
+        block_on(async move {
+            let on_drop = SetOnDrop(dropped_clone.clone());
+            let guard = spawn_guarded(async move {
+                let _on_drop = on_drop;
+                sleep(Duration::from_secs(10)).await;
+                finished_clone.fetch_add(1, Ordering::SeqCst);
+            });
+            sleep(Duration::from_millis(10)).await;
+            assert_eq!(dropped_clone.load(Ordering::SeqCst), 0, "held guard keeps it alive");
+
+            drop(guard);
+            sleep(Duration::from_millis(10)).await;
+        });
+
+        assert_eq!(dropped.load(Ordering::SeqCst), 1);
+        assert_eq!(finished.load(Ordering::SeqCst), 0);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn run_until_idle_completes_ready_tasks_and_fired_timers() {
+        let done = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..2 {
+            let done = done.clone();
+            spawn(async move {
+                done.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+
+        // Create the timer up front so its deadline has passed by the time
+        // the task first polls it.
+        let timer = Timer::after(Duration::from_millis(5));
+        std::thread::sleep(Duration::from_millis(10));
+        let done_timer = done.clone();
+        spawn(async move {
+            timer.await;
+            done_timer.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let work = run_until_idle();
+        assert_eq!(done.load(Ordering::SeqCst), 3);
+        assert!(work >= 3, "expected at least three task polls, got {}", work);
+    }
+
+    #[test]
+    fn spawn_local_task_mutates_rc_state() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+        use std::sync::atomic::AtomicBool;
+
+        let state = Rc::new(RefCell::new(Vec::new()));
+        let done = Arc::new(AtomicBool::new(false));
+        let origin = std::thread::current().id();
+
+        for i in 0..2 {
+            let state = state.clone();
+            let done = done.clone();
+            spawn_local(async move {
+                sleep(Duration::from_millis(1)).await;
+                assert_eq!(std::thread::current().id(), origin);
+                state.borrow_mut().push(i);
+                if state.borrow().len() == 2 {
+                    done.store(true, Ordering::SeqCst);
+                }
+            });
+        }
+
+        block_on(async move {
+            while !done.load(Ordering::SeqCst) {
+                sleep(Duration::from_millis(1)).await;
+            }
+        });
+        let mut pushed = state.borrow().clone();
+        pushed.sort();
+        assert_eq!(pushed, vec![0, 1]);
+    }
+
+    #[test]
+    fn block_on_cfg_completes_sleep_when_spinning_or_capped() {
+        for config in [
+            BlockOnConfig {
+                max_spin_iters: 100,
+                idle_timeout: None,
+            },
+            BlockOnConfig {
+                max_spin_iters: 0,
+                idle_timeout: Some(Duration::from_millis(1)),
+            },
+        ] {
+            let start = Instant::now();
+            block_on_cfg(sleep(Duration::from_millis(20)), config);
+            assert!(start.elapsed() >= Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn parked_returns_once_a_timer_fires() {
+        let fired = Arc::new(AtomicUsize::new(0));
+        let fired_task = fired.clone();
+        // Local, so no other test thread polls it first.
+        spawn_local(async move {
+            sleep(Duration::from_millis(20)).await;
+            fired_task.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let start = Instant::now();
+        while fired.load(Ordering::SeqCst) == 0 {
+            run_until_idle();
+            if fired.load(Ordering::SeqCst) == 0 {
+                parked(None).unwrap();
+            }
+        }
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn parked_wakes_for_a_spawn_from_another_thread() {
+        let ran = Arc::new(AtomicUsize::new(0));
+        let ran_task = ran.clone();
+        let spawner = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            spawn(async move {
+                ran_task.fetch_add(1, Ordering::SeqCst);
+            });
+        });
+
+        while ran.load(Ordering::SeqCst) == 0 {
+            parked(None).unwrap();
+            run_until_idle();
+        }
+        spawner.join().unwrap();
+    }
+
+    #[test]
+    fn now_advances_across_sleep() {
+        let before = now();
+        std::thread::sleep(Duration::from_millis(5));
+        let after = now();
+        assert!(after - before >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn react_result_preserves_os_error() {
+        assert_eq!(react_result(3).unwrap(), 3);
+        let err = react_result(-libc::EBADF).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EBADF));
+        let err = react_result(-libc::ENOMEM).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::OutOfMemory);
+    }
+
+    /// Set in the environment of the re-executed test binary that runs
+    /// `shutdown_drains_quick_tasks_child`.
+    const SHUTDOWN_CHILD_ENV: &str = "TAU_IFACE_SHUTDOWN_CHILD";
+
+    /// Shutdown is permanent for the process, so the real test runs in a
+    /// child process to keep other tests able to spawn.
+    #[test]
+    fn shutdown_drains_quick_tasks() {
+        let status = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "tests::shutdown_drains_quick_tasks_child",
+                "--test-threads=1",
+            ])
+            .env(SHUTDOWN_CHILD_ENV, "1")
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn shutdown_drains_quick_tasks_child() {
+        if std::env::var_os(SHUTDOWN_CHILD_ENV).is_none() {
+            return;
+        }
+
+        let done = Arc::new(AtomicUsize::new(0));
+        for delay in [0, 5] {
+            let done = done.clone();
+            spawn(async move {
+                sleep(Duration::from_millis(delay)).await;
+                done.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        let slow_done = Arc::new(AtomicUsize::new(0));
+        let slow_clone = slow_done.clone();
+        spawn(async move {
+            sleep(Duration::from_secs(10)).await;
+            slow_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let pending = shutdown(Duration::from_millis(100));
+        assert_eq!(done.load(Ordering::SeqCst), 2);
+        assert_eq!(slow_done.load(Ordering::SeqCst), 0);
+        assert_eq!(pending, 1);
+
+        // Spawns after shutdown are dropped, never polled.
+        let late = done.clone();
+        spawn(async move {
+            late.fetch_add(1, Ordering::SeqCst);
+        });
+        while try_tick() {}
+        assert_eq!(done.load(Ordering::SeqCst), 2);
+    }
+
+    /// Set in the environment of the re-executed test binary that runs
+    /// `run_until_ctrl_c_stops_on_sigint_child`.
+    const CTRL_C_CHILD_ENV: &str = "TAU_IFACE_CTRL_C_CHILD";
+
+    /// The `SIGINT` handler is permanent for the process, so the real test
+    /// runs in a child process.
+    #[test]
+    fn run_until_ctrl_c_stops_on_sigint() {
+        let status = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "tests::run_until_ctrl_c_stops_on_sigint_child",
+                "--test-threads=1",
+            ])
+            .env(CTRL_C_CHILD_ENV, "1")
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn run_until_ctrl_c_stops_on_sigint_child() {
+        if std::env::var_os(CTRL_C_CHILD_ENV).is_none() {
+            return;
+        }
+        // Fail instead of hanging if the signal never stops the runtime.
+        std::thread::spawn(|| {
+            std::thread::sleep(Duration::from_secs(5));
+            std::process::exit(2);
+        });
+
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let ticks_clone = ticks.clone();
+        spawn(async move {
+            loop {
+                sleep(Duration::from_millis(1)).await;
+                ticks_clone.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        // A second waiter: each signal completes one of the two, so two
+        // signals complete both, whichever sees the first.
+        let other_done = Arc::new(AtomicBool::new(false));
+        let other_done_clone = other_done.clone();
+        spawn(async move {
+            signal::ctrl_c().await.unwrap();
+            other_done_clone.store(true, Ordering::SeqCst);
+        });
+        spawn(async {
+            for _ in 0..2 {
+                sleep(Duration::from_millis(20)).await;
+                unsafe { libc::kill(libc::getpid(), libc::SIGINT) };
+            }
+        });
+
+        run_until_ctrl_c().unwrap();
+        assert!(ticks.load(Ordering::SeqCst) > 0);
+        block_on(async move {
+            while !other_done.load(Ordering::SeqCst) {
+                sleep(Duration::from_millis(1)).await;
+            }
+        });
     }
 }
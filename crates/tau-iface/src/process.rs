@@ -0,0 +1,142 @@
+//! Async child-process exit.
+//!
+//! Uses a pidfd on Linux and a kqueue process filter on macOS/BSD, so waiting
+//! doesn't busy-poll `waitid`.
+
+use std::io;
+use std::task::Poll;
+
+use async_ffi::ContextExt;
+
+use crate::ffi;
+
+/// Wait for child process `pid` to exit and reap it.
+///
+/// Returns the exit code, or `128 + signal` if the child was killed by a
+/// signal (the shell convention). `pid` must be a child of this process.
+pub async fn wait_pid(pid: i32) -> io::Result<i32> {
+    let watch = ProcessWatch::new(pid)?;
+    loop {
+        if let Some(status) = try_reap(pid)? {
+            return Ok(status);
+        }
+        watch.exited().await?;
+    }
+}
+
+/// Reactor registration for a process-exit watch. Deregisters on drop.
+struct ProcessWatch {
+    handle: u64,
+}
+
+impl ProcessWatch {
+    fn new(pid: i32) -> io::Result<Self> {
+        let handle = unsafe { ffi::tau_rt_pidfd_create(pid) };
+        if handle < 0 {
+            return Err(io::Error::from_raw_os_error(-handle as i32));
+        }
+        Ok(ProcessWatch {
+            handle: handle as u64,
+        })
+    }
+
+    /// Wait until the reactor reports the process has exited.
+    async fn exited(&self) -> io::Result<()> {
+        std::future::poll_fn(|cx| {
+            cx.with_ffi_context(|ffi_cx| {
+                let result = unsafe { ffi::tau_rt_pidfd_poll(self.handle, ffi_cx as *mut _) };
+                match result {
+                    1 => Poll::Ready(Ok(())),
+                    0 => Poll::Pending,
+                    2 => {
+                        let errno = unsafe { ffi::tau_rt_io_last_error(self.handle) };
+                        Poll::Ready(Err(io::Error::from_raw_os_error(errno)))
+                    }
+                    _ => Poll::Ready(Err(io::Error::other("unexpected pidfd_poll result"))),
+                }
+            })
+        })
+        .await
+    }
+}
+
+impl Drop for ProcessWatch {
+    fn drop(&mut self) {
+        unsafe { ffi::tau_rt_io_deregister(self.handle) };
+    }
+}
+
+/// Non-blocking `waitid`. Returns `None` while the child is still running.
+fn try_reap(pid: i32) -> io::Result<Option<i32>> {
+    loop {
+        // With WNOHANG and no exited child, waitid succeeds without filling
+        // in `info`, so a zeroed si_pid means "still running".
+        let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+        let result = unsafe {
+            libc::waitid(
+                libc::P_PID,
+                pid as libc::id_t,
+                &mut info,
+                libc::WEXITED | libc::WNOHANG,
+            )
+        };
+        if result < 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(err);
+        }
+        if unsafe { info.si_pid() } == 0 {
+            return Ok(None);
+        }
+        let status = unsafe { info.si_status() };
+        return Ok(Some(match info.si_code {
+            libc::CLD_EXITED => status,
+            // Killed or dumped core: `status` is the signal.
+            _ => 128 + status,
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn wait_pid_returns_exit_status() {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        let mut ok = std::process::Command::new("/bin/true").spawn().unwrap();
+        let mut fail = std::process::Command::new("/bin/false").spawn().unwrap();
+        let (ok_pid, fail_pid) = (ok.id() as i32, fail.id() as i32);
+        crate::block_on(async move {
+            let ok = wait_pid(ok_pid).await.unwrap();
+            let fail = wait_pid(fail_pid).await.unwrap();
+            *result_clone.lock().unwrap() = Some((ok, fail));
+        });
+
+        assert_eq!(result.lock().unwrap().take(), Some((0, 1)));
+        // wait_pid reaped both, so there is nothing left for `wait` to collect.
+        assert!(ok.wait().is_err());
+        assert!(fail.wait().is_err());
+    }
+
+    #[test]
+    fn wait_pid_reports_killing_signal() {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        let mut child = std::process::Command::new("/bin/sleep").arg("10").spawn().unwrap();
+        let pid = child.id() as i32;
+        unsafe { libc::kill(pid, libc::SIGKILL) };
+        crate::block_on(async move {
+            *result_clone.lock().unwrap() = Some(wait_pid(pid).await.unwrap());
+        });
+
+        assert_eq!(result.lock().unwrap().take(), Some(128 + libc::SIGKILL));
+        assert!(child.wait().is_err());
+    }
+}
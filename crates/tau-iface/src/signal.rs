@@ -0,0 +1,39 @@
+//! Async Ctrl+C.
+//!
+//! The runtime owns the process-wide `SIGINT` handler, which writes to a
+//! pipe; waiting reads it through the reactor, so no thread blocks on it.
+
+use std::io;
+
+use crate::async_fd::AsyncFd;
+use crate::ffi;
+
+/// Wait for Ctrl+C (`SIGINT`).
+///
+/// The first call replaces the default `SIGINT` action, which kills the
+/// process, for good: from then on Ctrl+C only completes `ctrl_c()` futures.
+/// Any number of calls can wait at once. Each signal completes one waiting
+/// (or the next) call, so with several waiters only one sees a given Ctrl+C.
+pub async fn ctrl_c() -> io::Result<()> {
+    let fd = unsafe { ffi::tau_rt_ctrl_c_fd() };
+    if fd < 0 {
+        return Err(io::Error::from_raw_os_error(-fd));
+    }
+    let async_fd = AsyncFd::new(fd)?;
+    loop {
+        let mut byte = 0u8;
+        let n = unsafe { libc::read(fd, &mut byte as *mut u8 as *mut libc::c_void, 1) };
+        if n > 0 {
+            return Ok(());
+        }
+        if n == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let err = io::Error::last_os_error();
+        match err.kind() {
+            io::ErrorKind::WouldBlock => async_fd.readable().await?,
+            io::ErrorKind::Interrupted => {}
+            _ => return Err(err),
+        }
+    }
+}
@@ -0,0 +1,541 @@
+//! Task synchronization primitives.
+//!
+//! These are plain waker-based structures — no runtime state lives here.
+//! Waking a task from another thread goes through the executor's schedule
+//! path, which interrupts a reactor parked in `react()`.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll, Waker};
+
+/// Wake a task from any thread.
+///
+/// `notify()` stores a single permit; `notified().await` consumes it, waiting
+/// if none is available. Permits don't accumulate — several `notify()` calls
+/// before anyone waits release only one `notified()`. Share via `Arc<Notify>`.
+pub struct Notify {
+    state: Mutex<NotifyState>,
+}
+
+struct NotifyState {
+    permit: bool,
+    wakers: Vec<Waker>,
+}
+
+impl Notify {
+    pub fn new() -> Self {
+        Notify {
+            state: Mutex::new(NotifyState {
+                permit: false,
+                wakers: Vec::new(),
+            }),
+        }
+    }
+
+    /// Release a permit and wake any waiting tasks. Callable from any thread.
+    pub fn notify(&self) {
+        let wakers = {
+            let mut state = self.state.lock().unwrap();
+            state.permit = true;
+            std::mem::take(&mut state.wakers)
+        };
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Wait until a permit is available, then consume it.
+    pub async fn notified(&self) {
+        std::future::poll_fn(|cx| {
+            let mut state = self.state.lock().unwrap();
+            if state.permit {
+                state.permit = false;
+                return Poll::Ready(());
+            }
+            if !state.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                state.wakers.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl Default for Notify {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A cooperative cancellation signal: any number of tasks await
+/// [`cancelled()`](Self::cancelled), and whoever holds a clone calls
+/// [`cancel()`](Self::cancel) to wake them all at once.
+///
+/// Unlike [`Notify`], cancellation is sticky and broadcast: every waiter,
+/// present or future, sees it. Clones share one signal; a
+/// [`child_token()`](Self::child_token) is cancelled along with its parent
+/// but can also be cancelled on its own. Pair `cancelled()` with
+/// [`race`](crate::util::race) to stop an operation early.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Mutex<TokenState>>,
+}
+
+#[derive(Default)]
+struct TokenState {
+    cancelled: bool,
+    /// Waiting `cancelled()` calls, by the key each was registered under.
+    wakers: Vec<(u64, Waker)>,
+    next_key: u64,
+    children: Vec<Weak<Mutex<TokenState>>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel this token and its children, waking every task awaiting
+    /// `cancelled()`. Callable from any thread; later calls do nothing.
+    pub fn cancel(&self) {
+        cancel_state(&self.inner);
+    }
+
+    /// Whether `cancel()` has been called on this token or an ancestor.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.lock().unwrap().cancelled
+    }
+
+    /// Wait until the token is cancelled. Resolves immediately if it
+    /// already is.
+    pub async fn cancelled(&self) {
+        let mut waiter = CancelledWaiter {
+            state: &self.inner,
+            key: None,
+        };
+        std::future::poll_fn(|cx| waiter.poll(cx)).await
+    }
+
+    /// A new token that is cancelled when this one is. Cancelling the child
+    /// leaves the parent alone.
+    pub fn child_token(&self) -> CancellationToken {
+        let child = CancellationToken::new();
+        let mut state = self.inner.lock().unwrap();
+        if state.cancelled {
+            child.inner.lock().unwrap().cancelled = true;
+        } else {
+            state.children.retain(|child| child.strong_count() > 0);
+            state.children.push(Arc::downgrade(&child.inner));
+        }
+        child
+    }
+}
+
+/// One `cancelled()` call's slot in `TokenState::wakers`, removed when the
+/// call is dropped — so waits abandoned before cancellation don't pile up.
+struct CancelledWaiter<'a> {
+    state: &'a Mutex<TokenState>,
+    key: Option<u64>,
+}
+
+impl CancelledWaiter<'_> {
+    fn poll(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.cancelled {
+            return Poll::Ready(());
+        }
+        let slot = self
+            .key
+            .and_then(|key| state.wakers.iter_mut().find(|(k, _)| *k == key));
+        match slot {
+            Some((_, waker)) => {
+                if !waker.will_wake(cx.waker()) {
+                    *waker = cx.waker().clone();
+                }
+            }
+            None => {
+                let key = state.next_key;
+                state.next_key += 1;
+                state.wakers.push((key, cx.waker().clone()));
+                self.key = Some(key);
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for CancelledWaiter<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key {
+            self.state.lock().unwrap().wakers.retain(|(k, _)| *k != key);
+        }
+    }
+}
+
+/// Mark `state` cancelled, then wake its waiters and cancel its children
+/// with no lock held.
+fn cancel_state(state: &Mutex<TokenState>) {
+    let (wakers, children) = {
+        let mut state = state.lock().unwrap();
+        if state.cancelled {
+            return;
+        }
+        state.cancelled = true;
+        (
+            std::mem::take(&mut state.wakers),
+            std::mem::take(&mut state.children),
+        )
+    };
+    for (_, waker) in wakers {
+        waker.wake();
+    }
+    for child in children.iter().filter_map(Weak::upgrade) {
+        cancel_state(&child);
+    }
+}
+
+/// Create a bounded channel holding at most `capacity` values in flight.
+///
+/// [`Sender::send`] waits while the channel is full and [`Receiver::recv`]
+/// while it is empty, so a fast producer is held back to the consumer's
+/// pace. Senders can be cloned and moved to other threads; there is one
+/// receiver. Panics if `capacity` is 0.
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "channel capacity must be at least 1");
+    let chan = Arc::new(Mutex::new(ChanState {
+        queue: VecDeque::with_capacity(capacity),
+        capacity,
+        senders: 1,
+        receiver_alive: true,
+        recv_waker: None,
+        send_wakers: Vec::new(),
+    }));
+    (Sender { chan: chan.clone() }, Receiver { chan })
+}
+
+/// The sending half of a [`channel`]. The channel closes for the receiver
+/// once every clone is dropped.
+pub struct Sender<T> {
+    chan: Arc<Mutex<ChanState<T>>>,
+}
+
+/// The receiving half of a [`channel`].
+pub struct Receiver<T> {
+    chan: Arc<Mutex<ChanState<T>>>,
+}
+
+struct ChanState<T> {
+    queue: VecDeque<T>,
+    capacity: usize,
+    senders: usize,
+    receiver_alive: bool,
+    recv_waker: Option<Waker>,
+    send_wakers: Vec<Waker>,
+}
+
+/// Returned by [`Sender::send`] when the receiver is gone, handing back the
+/// value that couldn't be sent.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("channel receiver dropped")
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for SendError<T> {}
+
+impl<T> Sender<T> {
+    /// Send `value`, waiting for room if the channel is full. Fails only if
+    /// the receiver has been dropped.
+    pub async fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let mut value = Some(value);
+        std::future::poll_fn(|cx| {
+            let mut state = self.chan.lock().unwrap();
+            if !state.receiver_alive {
+                return Poll::Ready(Err(SendError(value.take().unwrap())));
+            }
+            if state.queue.len() < state.capacity {
+                state.queue.push_back(value.take().unwrap());
+                let waker = state.recv_waker.take();
+                drop(state);
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+                return Poll::Ready(Ok(()));
+            }
+            if !state.send_wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                state.send_wakers.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.chan.lock().unwrap().senders += 1;
+        Sender {
+            chan: self.chan.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut state = self.chan.lock().unwrap();
+            state.senders -= 1;
+            if state.senders > 0 {
+                return;
+            }
+            state.recv_waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Receive the next value, waiting if the channel is empty. Returns
+    /// `None` once every sender is dropped and the buffered values are
+    /// drained.
+    pub async fn recv(&mut self) -> Option<T> {
+        std::future::poll_fn(|cx| {
+            let mut state = self.chan.lock().unwrap();
+            if let Some(value) = state.queue.pop_front() {
+                // Every blocked sender retries; all but one find the channel
+                // full again and go back to waiting.
+                let wakers = std::mem::take(&mut state.send_wakers);
+                drop(state);
+                for waker in wakers {
+                    waker.wake();
+                }
+                return Poll::Ready(Some(value));
+            }
+            if state.senders == 0 {
+                return Poll::Ready(None);
+            }
+            state.recv_waker = Some(cx.waker().clone());
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let wakers = {
+            let mut state = self.chan.lock().unwrap();
+            state.receiver_alive = false;
+            std::mem::take(&mut state.send_wakers)
+        };
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn notify_from_another_thread_wakes_waiter() {
+        let notify = Arc::new(Notify::new());
+        let notify_clone = notify.clone();
+
+        let notifier = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            notify_clone.notify();
+        });
+
+        let start = Instant::now();
+        crate::block_on(async move {
+            notify.notified().await;
+        });
+        notifier.join().unwrap();
+
+        assert!(
+            start.elapsed() < Duration::from_secs(1),
+            "notified() should resolve promptly: {:?}",
+            start.elapsed()
+        );
+    }
+
+    #[test]
+    fn notify_before_wait_stores_permit() {
+        let notify = Arc::new(Notify::new());
+        notify.notify();
+        notify.notify(); // Permits don't accumulate.
+
+        let second_ready = Arc::new(Mutex::new(None));
+        let second_ready_clone = second_ready.clone();
+        crate::block_on(async move {
+            notify.notified().await;
+            let second =
+                crate::util::race(notify.notified(), crate::sleep(Duration::from_millis(20)))
+                    .await;
+            *second_ready_clone.lock().unwrap() = Some(second);
+        });
+
+        assert_eq!(
+            second_ready.lock().unwrap().take(),
+            Some(crate::util::Either::Right(()))
+        );
+    }
+
+    #[test]
+    fn cancel_wakes_every_waiter() {
+        let token = CancellationToken::new();
+        let woken = Arc::new(Mutex::new(0));
+
+        let (token_clone, woken_clone) = (token.clone(), woken.clone());
+        crate::block_on(async move {
+            for _ in 0..2 {
+                let (token, woken) = (token_clone.clone(), woken_clone.clone());
+                crate::spawn(async move {
+                    token.cancelled().await;
+                    *woken.lock().unwrap() += 1;
+                });
+            }
+            // Let both waiters park before cancelling.
+            crate::sleep(Duration::from_millis(10)).await;
+            assert_eq!(*woken_clone.lock().unwrap(), 0);
+            token_clone.cancel();
+            while *woken_clone.lock().unwrap() < 2 {
+                crate::sleep(Duration::from_millis(1)).await;
+            }
+        });
+
+        assert!(token.is_cancelled());
+        assert_eq!(*woken.lock().unwrap(), 2);
+    }
+
+    #[test]
+    fn dropped_waits_leave_no_wakers_behind() {
+        let token = CancellationToken::new();
+        let done = Arc::new(Mutex::new(0));
+
+        let (token_clone, done_clone) = (token.clone(), done.clone());
+        crate::block_on(async move {
+            for _ in 0..20 {
+                let (token, done) = (token_clone.clone(), done_clone.clone());
+                crate::spawn(async move {
+                    let timeout = crate::sleep(Duration::from_millis(1));
+                    crate::util::race(token.cancelled(), timeout).await;
+                    *done.lock().unwrap() += 1;
+                });
+            }
+            while *done_clone.lock().unwrap() < 20 {
+                crate::sleep(Duration::from_millis(1)).await;
+            }
+        });
+
+        assert!(!token.is_cancelled());
+        assert!(token.inner.lock().unwrap().wakers.is_empty());
+    }
+
+    #[test]
+    fn child_token_follows_parent_but_not_vice_versa() {
+        let parent = CancellationToken::new();
+        let child = parent.child_token();
+        let grandchild = child.child_token();
+        let sibling = parent.child_token();
+
+        sibling.cancel();
+        assert!(sibling.is_cancelled());
+        assert!(!parent.is_cancelled());
+        assert!(!child.is_cancelled());
+
+        let grandchild_clone = grandchild.clone();
+        let parent_clone = parent.clone();
+        crate::block_on(async move {
+            crate::spawn(async move {
+                crate::sleep(Duration::from_millis(10)).await;
+                parent_clone.cancel();
+            });
+            grandchild_clone.cancelled().await;
+        });
+        assert!(child.is_cancelled());
+        assert!(grandchild.is_cancelled());
+        // Children of a cancelled token start out cancelled.
+        assert!(parent.child_token().is_cancelled());
+    }
+
+    #[test]
+    fn channel_round_trips_values_in_order() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+
+        crate::block_on(async move {
+            let (tx, mut rx) = channel(2);
+            let tx2 = tx.clone();
+            crate::spawn(async move {
+                for i in 0..5 {
+                    tx.send(i).await.unwrap();
+                }
+                drop(tx2);
+            });
+            while let Some(value) = rx.recv().await {
+                received_clone.lock().unwrap().push(value);
+            }
+        });
+
+        assert_eq!(*received.lock().unwrap(), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn full_channel_send_waits_for_recv() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let sent = Arc::new(AtomicBool::new(false));
+        let sent_clone = sent.clone();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+
+        crate::block_on(async move {
+            let (tx, mut rx) = channel(1);
+            tx.send("first").await.unwrap();
+            crate::spawn(async move {
+                tx.send("second").await.unwrap();
+                sent_clone.store(true, Ordering::SeqCst);
+            });
+
+            crate::sleep(Duration::from_millis(20)).await;
+            assert!(!sent.load(Ordering::SeqCst), "send should wait while full");
+
+            let first = rx.recv().await.unwrap();
+            received_clone.lock().unwrap().push(first);
+            while !sent.load(Ordering::SeqCst) {
+                crate::sleep(Duration::from_millis(1)).await;
+            }
+            let second = rx.recv().await.unwrap();
+            received_clone.lock().unwrap().push(second);
+            // The sender is gone with its task.
+            assert_eq!(rx.recv().await, None);
+        });
+
+        assert_eq!(*received.lock().unwrap(), ["first", "second"]);
+    }
+
+    #[test]
+    fn send_fails_once_receiver_is_dropped() {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let (tx, rx) = channel(1);
+            drop(rx);
+            *result_clone.lock().unwrap() = Some(tx.send(7).await);
+        });
+
+        assert_eq!(result.lock().unwrap().take(), Some(Err(SendError(7))));
+    }
+}
@@ -2,11 +2,25 @@
 //!
 //! Built on `AsyncFd` for non-blocking IO through the tau-rt reactor.
 
+use std::fs::File;
 use std::io;
-use std::net::SocketAddr;
+use std::net::{Shutdown, SocketAddr};
 use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_io::{AsyncRead, AsyncWrite};
 
 use crate::async_fd::AsyncFd;
+use crate::timer::Timer;
+
+/// Size of the stack buffer `read_to_end` reads into per iteration.
+const READ_CHUNK: usize = 8192;
+
+/// How long `accept` pauses after running out of file descriptors.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(50);
 
 // ── Socket helpers ──────────────────────────────────────────────────
 
@@ -87,6 +101,32 @@ pub(crate) fn raw_to_socket_addr(
     }
 }
 
+/// Issue a syscall, re-issuing it for as long as it fails with `EINTR`.
+///
+/// A signal landing mid-call is not an error the caller should see; any other
+/// failure (including `WouldBlock`) is returned as-is.
+pub(crate) fn retry_interrupted<T, F>(mut syscall: F) -> io::Result<T>
+where
+    T: Copy + PartialOrd + From<i8>,
+    F: FnMut() -> T,
+{
+    loop {
+        let result = syscall();
+        if result >= T::from(0) {
+            return Ok(result);
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+}
+
+/// Whether an error means the process or system ran out of file descriptors.
+fn is_fd_exhaustion(err: &io::Error) -> bool {
+    matches!(err.raw_os_error(), Some(libc::EMFILE) | Some(libc::ENFILE))
+}
+
 /// Set a file descriptor to non-blocking mode.
 pub(crate) fn set_nonblocking(fd: RawFd) -> io::Result<()> {
     let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
@@ -118,6 +158,117 @@ pub(crate) fn set_reuseaddr(fd: RawFd) -> io::Result<()> {
     Ok(())
 }
 
+/// Set a socket buffer size option (`SO_RCVBUF` / `SO_SNDBUF`). Sizes past
+/// `c_int::MAX` are clamped.
+pub(crate) fn set_buffer_size(fd: RawFd, opt: libc::c_int, size: usize) -> io::Result<()> {
+    let optval = size.min(libc::c_int::MAX as usize) as libc::c_int;
+    let result = unsafe {
+        libc::setsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            opt,
+            &optval as *const _ as *const libc::c_void,
+            std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Read a socket buffer size option (`SO_RCVBUF` / `SO_SNDBUF`).
+pub(crate) fn buffer_size(fd: RawFd, opt: libc::c_int) -> io::Result<usize> {
+    let mut optval: libc::c_int = 0;
+    let mut len: libc::socklen_t =
+        std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+    let result = unsafe {
+        libc::getsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            opt,
+            &mut optval as *mut _ as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(optval as usize)
+}
+
+/// `SO_LINGER` measures in seconds under this name; on Apple platforms plain
+/// `SO_LINGER` counts clock ticks.
+#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+const SO_LINGER_SECONDS: libc::c_int = libc::SO_LINGER;
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+const SO_LINGER_SECONDS: libc::c_int = libc::SO_LINGER_SEC;
+
+/// Set `SO_LINGER`: `None` turns lingering off, `Some(timeout)` turns it on
+/// with the timeout rounded up to whole seconds.
+pub(crate) fn set_linger(fd: RawFd, linger: Option<Duration>) -> io::Result<()> {
+    let optval = libc::linger {
+        l_onoff: linger.is_some() as libc::c_int,
+        l_linger: linger.map_or(0, |timeout| {
+            let secs = timeout.as_secs() + u64::from(timeout.subsec_nanos() > 0);
+            secs.min(libc::c_int::MAX as u64) as libc::c_int
+        }),
+    };
+    let result = unsafe {
+        libc::setsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            SO_LINGER_SECONDS,
+            &optval as *const _ as *const libc::c_void,
+            std::mem::size_of::<libc::linger>() as libc::socklen_t,
+        )
+    };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Read `SO_LINGER`; `None` when lingering is off.
+pub(crate) fn linger(fd: RawFd) -> io::Result<Option<Duration>> {
+    let mut optval = libc::linger {
+        l_onoff: 0,
+        l_linger: 0,
+    };
+    let mut len: libc::socklen_t = std::mem::size_of::<libc::linger>() as libc::socklen_t;
+    let result = unsafe {
+        libc::getsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            SO_LINGER_SECONDS,
+            &mut optval as *mut _ as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok((optval.l_onoff != 0).then(|| Duration::from_secs(optval.l_linger.max(0) as u64)))
+}
+
+/// Query the local address a socket is bound to (`getsockname`).
+pub(crate) fn local_addr(fd: RawFd) -> io::Result<SocketAddr> {
+    let mut storage: libc::sockaddr_storage = unsafe { std::mem::zeroed() };
+    let mut addr_len: libc::socklen_t =
+        std::mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    let result = unsafe {
+        libc::getsockname(
+            fd,
+            &mut storage as *mut _ as *mut libc::sockaddr,
+            &mut addr_len,
+        )
+    };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    raw_to_socket_addr(&storage)
+}
+
 /// Check the pending socket error (used after non-blocking connect).
 fn get_socket_error(fd: RawFd) -> io::Result<()> {
     let mut error: libc::c_int = 0;
@@ -177,6 +328,43 @@ impl TcpStream {
     /// completion via the reactor.
     pub async fn connect(addr: SocketAddr) -> io::Result<Self> {
         let owned_fd = create_socket(addr_family(&addr), libc::SOCK_STREAM)?;
+        Self::connect_socket(owned_fd, addr).await
+    }
+
+    /// Connect to `remote` from a specific local address.
+    ///
+    /// Binds the socket to `local` (with `SO_REUSEADDR`) before connecting,
+    /// for hosts that must originate from a particular source IP or port.
+    /// Port 0 lets the OS pick the port. Both addresses must be of the same
+    /// family, otherwise `InvalidInput` is returned.
+    pub async fn connect_from(local: SocketAddr, remote: SocketAddr) -> io::Result<Self> {
+        if local.is_ipv4() != remote.is_ipv4() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "local and remote address families differ",
+            ));
+        }
+        let owned_fd = create_socket(addr_family(&remote), libc::SOCK_STREAM)?;
+        let raw = owned_fd.as_raw_fd();
+        set_reuseaddr(raw)?;
+
+        let (raw_addr, addr_len) = socket_addr_to_raw(&local);
+        let result = unsafe {
+            libc::bind(
+                raw,
+                &raw_addr as *const _ as *const libc::sockaddr,
+                addr_len,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Self::connect_socket(owned_fd, remote).await
+    }
+
+    /// Non-blocking connect of a fresh socket, awaiting completion.
+    async fn connect_socket(owned_fd: OwnedFd, addr: SocketAddr) -> io::Result<Self> {
         let raw = owned_fd.as_raw_fd();
 
         // Initiate non-blocking connect
@@ -190,8 +378,11 @@ impl TcpStream {
         };
 
         if result < 0 {
+            // An interrupted non-blocking connect keeps going in the
+            // background, exactly like EINPROGRESS — re-issuing it would fail
+            // with EALREADY. Either way, completion is signalled by writability.
             let err = io::Error::last_os_error();
-            if err.raw_os_error() != Some(libc::EINPROGRESS) {
+            if !matches!(err.raw_os_error(), Some(libc::EINPROGRESS) | Some(libc::EINTR)) {
                 return Err(err);
             }
         }
@@ -212,6 +403,15 @@ impl TcpStream {
         })
     }
 
+    /// Wrap a stream connected by `std` or inherited from another process.
+    /// Switches it to non-blocking mode and registers it with the reactor.
+    pub fn from_std(stream: std::net::TcpStream) -> io::Result<Self> {
+        stream.set_nonblocking(true)?;
+        let fd = OwnedFd::from(stream);
+        let async_fd = AsyncFd::new(fd.as_raw_fd())?;
+        Ok(TcpStream { async_fd, fd })
+    }
+
     /// Create a `TcpStream` from a raw fd that is already connected and non-blocking.
     ///
     /// # Safety
@@ -230,24 +430,33 @@ impl TcpStream {
     ///
     /// Returns the number of bytes read, or 0 for EOF.
     pub async fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.recv_with_flags(buf, 0).await
+    }
+
+    /// Read data without removing it from the socket's receive buffer; the
+    /// next `read` (or `peek`) returns the same bytes.
+    ///
+    /// Returns the number of bytes copied, or 0 for EOF.
+    pub async fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.recv_with_flags(buf, libc::MSG_PEEK).await
+    }
+
+    async fn recv_with_flags(&self, buf: &mut [u8], flags: libc::c_int) -> io::Result<usize> {
         loop {
             self.async_fd.readable().await?;
-            let n = unsafe {
+            let n = retry_interrupted(|| unsafe {
                 libc::recv(
                     self.fd.as_raw_fd(),
                     buf.as_mut_ptr() as *mut libc::c_void,
                     buf.len(),
-                    0,
+                    flags,
                 )
-            };
-            if n >= 0 {
-                return Ok(n as usize);
+            });
+            match n {
+                Ok(n) => return Ok(n as usize),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => continue, // spurious wake
+                Err(err) => return Err(err),
             }
-            let err = io::Error::last_os_error();
-            if err.kind() == io::ErrorKind::WouldBlock {
-                continue; // spurious wake
-            }
-            return Err(err);
         }
     }
 
@@ -257,29 +466,488 @@ impl TcpStream {
     pub async fn write(&self, buf: &[u8]) -> io::Result<usize> {
         loop {
             self.async_fd.writable().await?;
-            let n = unsafe {
+            let n = retry_interrupted(|| unsafe {
                 libc::send(
                     self.fd.as_raw_fd(),
                     buf.as_ptr() as *const libc::c_void,
                     buf.len(),
                     0,
                 )
+            });
+            match n {
+                Ok(n) => return Ok(n as usize),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => continue, // spurious wake
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Wait until the stream is readable.
+    ///
+    /// Readiness can be spurious: a following [`try_read`](Self::try_read)
+    /// may still return `WouldBlock`.
+    pub async fn readable(&self) -> io::Result<()> {
+        self.async_fd.readable().await
+    }
+
+    /// Wait until the stream is writable.
+    ///
+    /// Readiness can be spurious: a following [`try_write`](Self::try_write)
+    /// may still return `WouldBlock`.
+    pub async fn writable(&self) -> io::Result<()> {
+        self.async_fd.writable().await
+    }
+
+    /// Read whatever is already buffered, without waiting.
+    ///
+    /// Issues a single `recv` and returns `WouldBlock` if no data is
+    /// available; the reactor is not involved. Returns 0 for EOF.
+    pub fn try_read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = retry_interrupted(|| unsafe {
+            libc::recv(
+                self.fd.as_raw_fd(),
+                buf.as_mut_ptr() as *mut libc::c_void,
+                buf.len(),
+                0,
+            )
+        })?;
+        Ok(n as usize)
+    }
+
+    /// Write as much of `buf` as the socket takes right now, without waiting.
+    ///
+    /// Issues a single `send` and returns `WouldBlock` if the send buffer is
+    /// full; the reactor is not involved.
+    pub fn try_write(&self, buf: &[u8]) -> io::Result<usize> {
+        let n = retry_interrupted(|| unsafe {
+            libc::send(
+                self.fd.as_raw_fd(),
+                buf.as_ptr() as *const libc::c_void,
+                buf.len(),
+                0,
+            )
+        })?;
+        Ok(n as usize)
+    }
+
+    /// Send `count` bytes of `file`, starting at `offset`, without copying
+    /// them through user space.
+    ///
+    /// Uses `sendfile(2)` on Linux and macOS; elsewhere it falls back to
+    /// reading into a buffer and writing that. The file's own position is not
+    /// used or moved. Returns the number of bytes sent, which is less than
+    /// `count` only if the file ends first.
+    pub async fn send_file(&self, file: &File, offset: u64, count: usize) -> io::Result<usize> {
+        let mut sent = 0;
+        while sent < count {
+            self.async_fd.writable().await?;
+            match self.send_file_chunk(file, offset + sent as u64, count - sent) {
+                Ok(0) => break, // end of file
+                Ok(n) => sent += n,
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(sent)
+    }
+
+    /// One non-blocking `sendfile` call. `Ok(0)` means end of file.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn send_file_chunk(&self, file: &File, offset: u64, count: usize) -> io::Result<usize> {
+        let mut offset = offset as libc::off_t;
+        let n = retry_interrupted(|| unsafe {
+            libc::sendfile(self.fd.as_raw_fd(), file.as_raw_fd(), &mut offset, count)
+        })?;
+        Ok(n as usize)
+    }
+
+    /// One non-blocking `sendfile` call. `Ok(0)` means end of file.
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    fn send_file_chunk(&self, file: &File, offset: u64, count: usize) -> io::Result<usize> {
+        loop {
+            // In: bytes to send (0 would mean "until EOF"). Out: bytes sent,
+            // which can be non-zero even when the call fails with EAGAIN.
+            let mut len = count as libc::off_t;
+            let result = unsafe {
+                libc::sendfile(
+                    file.as_raw_fd(),
+                    self.fd.as_raw_fd(),
+                    offset as libc::off_t,
+                    &mut len,
+                    std::ptr::null_mut(),
+                    0,
+                )
             };
-            if n >= 0 {
-                return Ok(n as usize);
+            if result == 0 || len > 0 {
+                return Ok(len as usize);
             }
             let err = io::Error::last_os_error();
-            if err.kind() == io::ErrorKind::WouldBlock {
-                continue; // spurious wake
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
             }
-            return Err(err);
         }
     }
 
+    /// Buffered fallback: read a chunk at `offset`, send what the socket takes.
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios"
+    )))]
+    fn send_file_chunk(&self, file: &File, offset: u64, count: usize) -> io::Result<usize> {
+        use std::os::unix::fs::FileExt;
+
+        let mut chunk = [0u8; READ_CHUNK];
+        let len = count.min(READ_CHUNK);
+        let n = file.read_at(&mut chunk[..len], offset)?;
+        if n == 0 {
+            return Ok(0);
+        }
+        let sent = retry_interrupted(|| unsafe {
+            libc::send(
+                self.fd.as_raw_fd(),
+                chunk.as_ptr() as *const libc::c_void,
+                n,
+                0,
+            )
+        })?;
+        Ok(sent as usize)
+    }
+
+    /// Poll-based `read`, for manual `Future` implementations and
+    /// [`AsyncRead`](futures_io::AsyncRead).
+    pub fn poll_read(&self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        loop {
+            match self.async_fd.poll_readable(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+            match self.try_read(buf) {
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => continue, // spurious wake
+                result => return Poll::Ready(result),
+            }
+        }
+    }
+
+    /// Poll-based `write`, for manual `Future` implementations and
+    /// [`AsyncWrite`](futures_io::AsyncWrite).
+    pub fn poll_write(&self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        loop {
+            match self.async_fd.poll_writable(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+            let n = retry_interrupted(|| unsafe {
+                libc::send(
+                    self.fd.as_raw_fd(),
+                    buf.as_ptr() as *const libc::c_void,
+                    buf.len(),
+                    0,
+                )
+            });
+            match n {
+                Ok(n) => return Poll::Ready(Ok(n as usize)),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => continue, // spurious wake
+                Err(err) => return Poll::Ready(Err(err)),
+            }
+        }
+    }
+
+    /// Split into a read half and a write half that can be driven
+    /// concurrently, such as the two directions of a proxy.
+    pub fn split(&self) -> (ReadHalf<'_>, WriteHalf<'_>) {
+        (ReadHalf { stream: self }, WriteHalf { stream: self })
+    }
+
+    /// Read until EOF, appending everything to `buf`.
+    ///
+    /// Returns the number of bytes appended. The buffer grows without bound —
+    /// a peer that never closes its write side can exhaust memory, so cap the
+    /// input yourself when reading from untrusted peers.
+    pub async fn read_to_end(&self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        let mut chunk = [0u8; READ_CHUNK];
+        let mut total = 0;
+        loop {
+            let n = self.read(&mut chunk).await?;
+            if n == 0 {
+                return Ok(total);
+            }
+            buf.extend_from_slice(&chunk[..n]);
+            total += n;
+        }
+    }
+
+    /// Read until EOF, appending everything to `buf` as UTF-8.
+    ///
+    /// Returns `InvalidData` (leaving `buf` untouched) if the data isn't valid
+    /// UTF-8. Same unbounded-memory caveat as [`read_to_end`](Self::read_to_end).
+    pub async fn read_to_string(&self, buf: &mut String) -> io::Result<usize> {
+        let mut bytes = Vec::new();
+        let n = self.read_to_end(&mut bytes).await?;
+        let s = String::from_utf8(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        buf.push_str(&s);
+        Ok(n)
+    }
+
+    /// Shut down the read, write, or both halves of the connection.
+    ///
+    /// `Shutdown::Write` sends EOF to the peer while keeping the read side open.
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        let how = match how {
+            Shutdown::Read => libc::SHUT_RD,
+            Shutdown::Write => libc::SHUT_WR,
+            Shutdown::Both => libc::SHUT_RDWR,
+        };
+        let result = unsafe { libc::shutdown(self.fd.as_raw_fd(), how) };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Set the kernel receive buffer size (`SO_RCVBUF`).
+    ///
+    /// The kernel treats this as a request: Linux doubles the value (to
+    /// leave room for bookkeeping) and caps it at `net.core.rmem_max`, so
+    /// [`recv_buffer_size`](Self::recv_buffer_size) may not read back what
+    /// was set.
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(self.fd.as_raw_fd(), libc::SO_RCVBUF, size)
+    }
+
+    /// The kernel receive buffer size (`SO_RCVBUF`).
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(self.fd.as_raw_fd(), libc::SO_RCVBUF)
+    }
+
+    /// Set the kernel send buffer size (`SO_SNDBUF`). Same caveats as
+    /// [`set_recv_buffer_size`](Self::set_recv_buffer_size), with the cap at
+    /// `net.core.wmem_max`.
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(self.fd.as_raw_fd(), libc::SO_SNDBUF, size)
+    }
+
+    /// The kernel send buffer size (`SO_SNDBUF`).
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(self.fd.as_raw_fd(), libc::SO_SNDBUF)
+    }
+
+    /// Control what closing the socket does with unsent data (`SO_LINGER`).
+    ///
+    /// The socket is closed when the stream is dropped. With `None` (the OS
+    /// default) the close returns at once and the kernel keeps sending in
+    /// the background. `Some(Duration::ZERO)` aborts the connection instead:
+    /// unsent data is discarded and the peer gets a RST. Any other timeout
+    /// (rounded up to whole seconds) makes the close wait for the data to be
+    /// acknowledged — on Linux the drop blocks the dropping thread, and with
+    /// it the runtime, for up to that long, so keep it short or call
+    /// [`shutdown`](Self::shutdown) and wait for EOF before dropping.
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        set_linger(self.fd.as_raw_fd(), linger)
+    }
+
+    /// The `SO_LINGER` setting; `None` when lingering is off.
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        linger(self.fd.as_raw_fd())
+    }
+
     /// Returns the raw file descriptor.
     pub fn as_raw_fd(&self) -> RawFd {
         self.fd.as_raw_fd()
     }
+
+    /// Wrap the stream in a blocking [`std::io::Read`] / [`std::io::Write`]
+    /// adapter, for synchronous code that expects one.
+    ///
+    /// Each call tries the socket directly and, if it would block, drives the
+    /// runtime with `block_on()` until the stream is ready. So the adapter
+    /// must only be used outside the runtime — from inside a spawned task or
+    /// a `block_on()` future, a call that has to wait panics.
+    pub fn into_blocking_io(self) -> BlockingTcpStream {
+        BlockingTcpStream {
+            stream: Arc::new(self),
+        }
+    }
+}
+
+/// Blocking `Read` / `Write` over a [`TcpStream`], from
+/// [`TcpStream::into_blocking_io`].
+pub struct BlockingTcpStream {
+    /// Shared with the future of the call in progress; unique between calls.
+    stream: Arc<TcpStream>,
+}
+
+impl BlockingTcpStream {
+    /// Unwrap the underlying async stream.
+    pub fn into_inner(self) -> TcpStream {
+        Arc::try_unwrap(self.stream)
+            .ok()
+            .expect("no call in progress holds the stream")
+    }
+}
+
+impl io::Read for BlockingTcpStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Only the readiness wait goes through the runtime; the data lands
+        // straight in `buf`.
+        loop {
+            match self.stream.try_read(buf) {
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                    let stream = self.stream.clone();
+                    crate::block_on_output(async move { stream.readable().await })?;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl io::Write for BlockingTcpStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        loop {
+            match self.stream.try_write(buf) {
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                    let stream = self.stream.clone();
+                    crate::block_on_output(async move { stream.writable().await })?;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Reads go through [`TcpStream::poll_read`].
+impl AsyncRead for TcpStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        TcpStream::poll_read(&self, cx, buf)
+    }
+}
+
+impl AsyncRead for &TcpStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        TcpStream::poll_read(&self, cx, buf)
+    }
+}
+
+/// Writes go through [`TcpStream::poll_write`]; `close` shuts down the write
+/// half. Sockets have no userspace buffer, so `flush` does nothing.
+impl AsyncWrite for TcpStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        TcpStream::poll_write(&self, cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.shutdown(Shutdown::Write))
+    }
+}
+
+impl AsyncWrite for &TcpStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        TcpStream::poll_write(&self, cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.shutdown(Shutdown::Write))
+    }
+}
+
+/// The read half of a [`TcpStream`], from [`TcpStream::split`].
+pub struct ReadHalf<'a> {
+    stream: &'a TcpStream,
+}
+
+/// The write half of a [`TcpStream`], from [`TcpStream::split`]. Closing it
+/// shuts down the stream's write direction; the read half stays usable.
+pub struct WriteHalf<'a> {
+    stream: &'a TcpStream,
+}
+
+impl AsyncRead for ReadHalf<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.stream.poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for WriteHalf<'_> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.stream.poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.stream.shutdown(Shutdown::Write))
+    }
+}
+
+/// Create a pair of connected, non-blocking streams.
+///
+/// Built on `socketpair(AF_UNIX, SOCK_STREAM)`, so no port is bound. Both ends
+/// are registered with the reactor — useful for deterministic duplex tests of
+/// protocol code.
+pub fn pair() -> io::Result<(TcpStream, TcpStream)> {
+    let mut fds = [0 as libc::c_int; 2];
+    let result =
+        unsafe { libc::socketpair(libc::AF_UNIX, libc::SOCK_STREAM, 0, fds.as_mut_ptr()) };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // Take ownership immediately so both fds are closed on any error below.
+    let (a, b) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+    set_nonblocking(a.as_raw_fd())?;
+    set_nonblocking(b.as_raw_fd())?;
+
+    let a = TcpStream {
+        async_fd: AsyncFd::new(a.as_raw_fd())?,
+        fd: a,
+    };
+    let b = TcpStream {
+        async_fd: AsyncFd::new(b.as_raw_fd())?,
+        fd: b,
+    };
+    Ok((a, b))
 }
 
 // ── TcpListener ─────────────────────────────────────────────────────
@@ -326,8 +994,36 @@ impl TcpListener {
         })
     }
 
+    /// Wrap a listener bound by `std`, or handed down by a parent process
+    /// or systemd socket activation. Switches it to non-blocking mode and
+    /// registers it with the reactor.
+    pub fn from_std(listener: std::net::TcpListener) -> io::Result<Self> {
+        Self::from_owned_fd(OwnedFd::from(listener))
+    }
+
+    /// Take ownership of a listening socket by fd, such as one inherited at
+    /// startup. See [`from_std`](Self::from_std).
+    ///
+    /// # Safety
+    /// `fd` must be an open, listening TCP socket owned by nothing else. It
+    /// is closed when the listener is dropped.
+    pub unsafe fn from_raw_fd(fd: RawFd) -> io::Result<Self> {
+        Self::from_owned_fd(OwnedFd::from_raw_fd(fd))
+    }
+
+    fn from_owned_fd(fd: OwnedFd) -> io::Result<Self> {
+        set_nonblocking(fd.as_raw_fd())?;
+        let async_fd = AsyncFd::new(fd.as_raw_fd())?;
+        Ok(TcpListener { async_fd, fd })
+    }
+
     /// Accept a new incoming connection.
     ///
+    /// When the process or system is out of file descriptors (`EMFILE` /
+    /// `ENFILE`), the pending connection stays queued and the listener stays
+    /// readable. The error is returned only after a short pause, so an accept
+    /// loop that retries on error doesn't hot-spin until descriptors free up.
+    ///
     /// Returns the connected stream and the peer's address.
     pub async fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
         loop {
@@ -337,26 +1033,28 @@ impl TcpListener {
             let mut addr_len: libc::socklen_t =
                 std::mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
 
-            let fd = unsafe {
+            let fd = retry_interrupted(|| unsafe {
                 libc::accept(
                     self.fd.as_raw_fd(),
                     &mut storage as *mut _ as *mut libc::sockaddr,
                     &mut addr_len,
                 )
-            };
-
-            if fd >= 0 {
-                set_nonblocking(fd)?;
-                let addr = raw_to_socket_addr(&storage)?;
-                let stream = unsafe { TcpStream::from_raw_fd(fd)? };
-                return Ok((stream, addr));
-            }
+            });
 
-            let err = io::Error::last_os_error();
-            if err.kind() == io::ErrorKind::WouldBlock {
-                continue; // spurious wake
+            match fd {
+                Ok(fd) => {
+                    set_nonblocking(fd)?;
+                    let addr = raw_to_socket_addr(&storage)?;
+                    let stream = unsafe { TcpStream::from_raw_fd(fd)? };
+                    return Ok((stream, addr));
+                }
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => continue, // spurious wake
+                Err(err) if is_fd_exhaustion(&err) => {
+                    Timer::after(ACCEPT_BACKOFF).await;
+                    return Err(err);
+                }
+                Err(err) => return Err(err),
             }
-            return Err(err);
         }
     }
 
@@ -370,6 +1068,30 @@ impl TcpListener {
 mod tests {
     use super::*;
 
+    #[test]
+    fn buffer_sizes_read_back_at_least_requested() {
+        let (a, _b) = pair().unwrap();
+        a.set_recv_buffer_size(100_000).unwrap();
+        a.set_send_buffer_size(100_000).unwrap();
+        assert!(a.recv_buffer_size().unwrap() >= 100_000);
+        assert!(a.send_buffer_size().unwrap() >= 100_000);
+    }
+
+    #[test]
+    fn linger_reads_back() {
+        let (a, _b) = pair().unwrap();
+        assert_eq!(a.linger().unwrap(), None);
+        a.set_linger(Some(Duration::from_secs(2))).unwrap();
+        assert_eq!(a.linger().unwrap(), Some(Duration::from_secs(2)));
+        a.set_linger(Some(Duration::from_millis(1500))).unwrap();
+        assert_eq!(a.linger().unwrap(), Some(Duration::from_secs(2)));
+        a.set_linger(None).unwrap();
+        assert_eq!(a.linger().unwrap(), None);
+        // Leave it set to abort on drop, which returns at once.
+        a.set_linger(Some(Duration::ZERO)).unwrap();
+        assert_eq!(a.linger().unwrap(), Some(Duration::ZERO));
+    }
+
     #[test]
     fn socket_addr_v4_roundtrip() {
         let addr: SocketAddr = "127.0.0.1:8080".parse().unwrap();
@@ -406,4 +1128,487 @@ mod tests {
         let result = raw_to_socket_addr(&storage);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn pair_write_then_read() {
+        use std::sync::{Arc, Mutex};
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+
+        crate::block_on(async move {
+            let (a, b) = pair().unwrap();
+            let n = a.write(b"ping").await.unwrap();
+            assert_eq!(n, 4);
+
+            let mut buf = [0u8; 16];
+            let n = b.read(&mut buf).await.unwrap();
+            received_clone.lock().unwrap().extend_from_slice(&buf[..n]);
+        });
+
+        assert_eq!(&*received.lock().unwrap(), b"ping");
+    }
+
+    #[test]
+    fn connect_from_binds_the_local_address() {
+        use std::sync::{Arc, Mutex};
+
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let listener = TcpListener::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+            let addr = local_addr(listener.as_raw_fd()).unwrap();
+            let local = "127.0.0.1:0".parse().unwrap();
+            let client = TcpStream::connect_from(local, addr).await.unwrap();
+            let (_server, peer) = listener.accept().await.unwrap();
+            let client_local = local_addr(client.as_raw_fd()).unwrap();
+            *result_clone.lock().unwrap() = Some((peer, client_local));
+        });
+
+        // Port 0 lets the kernel pick; the accepted peer is whatever it chose.
+        let (peer, client_local) = result.lock().unwrap().take().unwrap();
+        assert_ne!(client_local.port(), 0);
+        assert_eq!(client_local.ip(), std::net::Ipv4Addr::LOCALHOST);
+        assert_eq!(peer, client_local);
+    }
+
+    #[test]
+    fn connect_from_rejects_mismatched_families() {
+        use std::sync::{Arc, Mutex};
+
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let local: SocketAddr = "[::1]:0".parse().unwrap();
+            let remote: SocketAddr = "127.0.0.1:9".parse().unwrap();
+            let err = TcpStream::connect_from(local, remote).await.err().unwrap();
+            *result_clone.lock().unwrap() = Some(err.kind());
+        });
+
+        assert_eq!(result.lock().unwrap().take(), Some(io::ErrorKind::InvalidInput));
+    }
+
+    #[test]
+    fn blocking_io_round_trip_outside_runtime() {
+        use std::io::{Read, Write};
+
+        let (a, b) = pair().unwrap();
+        let mut a = a.into_blocking_io();
+        let mut b = b.into_blocking_io();
+
+        a.write_all(b"ping over blocking io").unwrap();
+        a.flush().unwrap();
+        let mut received = [0u8; 21];
+        b.read_exact(&mut received).unwrap();
+        assert_eq!(&received, b"ping over blocking io");
+
+        b.write_all(b"pong").unwrap();
+        let a = a.into_inner();
+        a.shutdown(Shutdown::Both).unwrap();
+        let mut rest = Vec::new();
+        b.read_to_end(&mut rest).unwrap();
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn blocking_read_waits_for_data() {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (accepted, _) = listener.accept().unwrap();
+        let mut server = TcpStream::from_std(accepted).unwrap().into_blocking_io();
+
+        let writer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            client.write_all(b"late").unwrap();
+        });
+        let mut received = [0u8; 4];
+        server.read_exact(&mut received).unwrap();
+        assert_eq!(&received, b"late");
+        writer.join().unwrap();
+    }
+
+    /// Set in the environment of the re-executed test binary that runs
+    /// `blocking_io_panics_inside_runtime_child`.
+    const BLOCKING_IO_CHILD_ENV: &str = "TAU_IFACE_BLOCKING_IO_CHILD";
+
+    /// A panic inside a polled task can't unwind across the runtime's C ABI,
+    /// so the guard is observed from a child process.
+    #[test]
+    fn blocking_io_panics_inside_runtime() {
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "tcp::tests::blocking_io_panics_inside_runtime_child",
+                "--test-threads=1",
+                "--nocapture",
+            ])
+            .env(BLOCKING_IO_CHILD_ENV, "1")
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("called from within the runtime"), "{}", stderr);
+    }
+
+    #[test]
+    fn blocking_io_panics_inside_runtime_child() {
+        use std::io::Read;
+
+        if std::env::var_os(BLOCKING_IO_CHILD_ENV).is_none() {
+            return;
+        }
+        let (a, _b) = pair().unwrap();
+        let mut a = a.into_blocking_io();
+        crate::block_on(async move {
+            // Nothing to read, so the call has to wait.
+            let _ = a.read(&mut [0u8; 1]);
+        });
+    }
+
+    #[test]
+    fn peek_leaves_data_for_read() {
+        use std::sync::{Arc, Mutex};
+
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let listener = TcpListener::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+            let addr = local_addr(listener.as_raw_fd()).unwrap();
+            let client = TcpStream::connect(addr).await.unwrap();
+            let (server, _) = listener.accept().await.unwrap();
+            client.write(b"\x16\x03\x01hello").await.unwrap();
+
+            let mut peeked = [0u8; 3];
+            let n = server.peek(&mut peeked).await.unwrap();
+            assert_eq!(&peeked[..n], b"\x16\x03\x01");
+
+            let mut read = [0u8; 16];
+            let n = server.read(&mut read).await.unwrap();
+            *result_clone.lock().unwrap() = Some(read[..n].to_vec());
+        });
+
+        assert_eq!(result.lock().unwrap().take().unwrap(), b"\x16\x03\x01hello");
+    }
+
+    #[test]
+    fn try_read_does_not_wait_for_data() {
+        use std::sync::{Arc, Mutex};
+
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let (a, b) = pair().unwrap();
+            let mut buf = [0u8; 16];
+            let empty = b.try_read(&mut buf).err().map(|err| err.kind());
+
+            assert_eq!(a.try_write(b"drain me").unwrap(), 8);
+            b.readable().await.unwrap();
+            let n = b.try_read(&mut buf).unwrap();
+            *result_clone.lock().unwrap() = Some((empty, buf[..n].to_vec()));
+        });
+
+        let (empty, data) = result.lock().unwrap().take().unwrap();
+        assert_eq!(empty, Some(io::ErrorKind::WouldBlock));
+        assert_eq!(data, b"drain me");
+    }
+
+    #[test]
+    fn from_std_listener_accepts_async_connection() {
+        use std::io::Write;
+        use std::sync::{Arc, Mutex};
+
+        let std_listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = std_listener.local_addr().unwrap();
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let listener = TcpListener::from_std(std_listener).unwrap();
+            let mut client = std::net::TcpStream::connect(addr).unwrap();
+            client.write_all(b"hello").unwrap();
+            let (server, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 16];
+            let n = server.read(&mut buf).await.unwrap();
+            *result_clone.lock().unwrap() = Some(buf[..n].to_vec());
+        });
+
+        assert_eq!(result.lock().unwrap().take().unwrap(), b"hello");
+    }
+
+    #[test]
+    fn from_std_stream_reads_and_writes() {
+        use std::io::Read;
+        use std::sync::{Arc, Mutex};
+
+        let std_listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = std::net::TcpStream::connect(std_listener.local_addr().unwrap()).unwrap();
+        let (mut server, _) = std_listener.accept().unwrap();
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let client = TcpStream::from_std(client).unwrap();
+            client.write(b"ping").await.unwrap();
+            let mut buf = [0u8; 4];
+            // Nothing to read yet: the wrapped stream no longer blocks.
+            let empty = client.try_read(&mut buf).err().map(|err| err.kind());
+            *result_clone.lock().unwrap() = Some(empty);
+        });
+
+        let mut buf = [0u8; 4];
+        server.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"ping");
+        assert_eq!(
+            result.lock().unwrap().take().unwrap(),
+            Some(io::ErrorKind::WouldBlock)
+        );
+    }
+
+    #[test]
+    fn send_file_transfers_file_range() {
+        use std::io::Write;
+        use std::sync::{Arc, Mutex};
+
+        let contents: Vec<u8> = (0..200_000u32).map(|i| (i % 253) as u8).collect();
+        let path = std::env::temp_dir()
+            .join(format!("tau-iface-send-file-{}", std::process::id()));
+        File::create(&path).unwrap().write_all(&contents).unwrap();
+        let file = File::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let listener = TcpListener::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+            let addr = local_addr(listener.as_raw_fd()).unwrap();
+            let client = TcpStream::connect(addr).await.unwrap();
+            let (server, _) = listener.accept().await.unwrap();
+
+            crate::spawn(async move {
+                // Skip the first 10 bytes and ask for more than is left.
+                let sent = server.send_file(&file, 10, 500_000).await.unwrap();
+                assert_eq!(sent, 200_000 - 10);
+                server.shutdown(Shutdown::Write).unwrap();
+            });
+
+            let mut received = Vec::new();
+            client.read_to_end(&mut received).await.unwrap();
+            *result_clone.lock().unwrap() = Some(received);
+        });
+
+        let received = result.lock().unwrap().take().unwrap();
+        assert_eq!(received.len(), contents.len() - 10);
+        assert!(received == contents[10..]);
+    }
+
+    #[test]
+    fn read_to_end_slurps_until_shutdown() {
+        use std::sync::{Arc, Mutex};
+
+        let payload: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let expected = payload.clone();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+
+        crate::block_on(async move {
+            let (writer, reader) = pair().unwrap();
+            crate::spawn(async move {
+                let mut sent = 0;
+                while sent < payload.len() {
+                    sent += writer.write(&payload[sent..]).await.unwrap();
+                }
+                writer.shutdown(Shutdown::Write).unwrap();
+            });
+
+            let mut buf = Vec::new();
+            let n = reader.read_to_end(&mut buf).await.unwrap();
+            assert_eq!(n, buf.len());
+            *received_clone.lock().unwrap() = buf;
+        });
+
+        assert_eq!(*received.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn read_to_string_rejects_invalid_utf8() {
+        use std::sync::{Arc, Mutex};
+
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let (writer, reader) = pair().unwrap();
+            writer.write(&[0xff, 0xfe]).await.unwrap();
+            writer.shutdown(Shutdown::Write).unwrap();
+
+            let mut buf = String::new();
+            let err = reader.read_to_string(&mut buf).await.unwrap_err();
+            *result_clone.lock().unwrap() = Some((err.kind(), buf));
+        });
+
+        let (kind, buf) = result.lock().unwrap().take().unwrap();
+        assert_eq!(kind, io::ErrorKind::InvalidData);
+        assert!(buf.is_empty());
+    }
+
+    /// Set in the environment of the re-executed test binary that runs
+    /// `accept_survives_signal_delivery_child`.
+    #[cfg(target_os = "linux")]
+    const SIGNAL_CHILD_ENV: &str = "TAU_IFACE_SIGNAL_CHILD";
+
+    /// Installing a signal handler affects the whole process, so the real
+    /// test runs in a child process.
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn accept_survives_signal_delivery() {
+        let status = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "tcp::tests::accept_survives_signal_delivery_child",
+                "--test-threads=1",
+            ])
+            .env(SIGNAL_CHILD_ENV, "1")
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn accept_survives_signal_delivery_child() {
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+        use std::sync::{Arc, Mutex};
+        use std::time::Duration;
+
+        if std::env::var_os(SIGNAL_CHILD_ENV).is_none() {
+            return;
+        }
+
+        static DELIVERED: AtomicUsize = AtomicUsize::new(0);
+        extern "C" fn count(_: libc::c_int) {
+            DELIVERED.fetch_add(1, Ordering::SeqCst);
+        }
+
+        // No SA_RESTART: every syscall the signal lands in fails with EINTR.
+        unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = count as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            libc::sigemptyset(&mut action.sa_mask);
+            assert_eq!(libc::sigaction(libc::SIGUSR1, &action, std::ptr::null_mut()), 0);
+        }
+
+        /// Whether thread `tid` of this process is asleep in a syscall.
+        fn is_sleeping(tid: libc::pid_t) -> bool {
+            let stat = std::fs::read_to_string(format!("/proc/self/task/{}/stat", tid)).unwrap();
+            let state = stat.rsplit(')').next().unwrap().trim_start();
+            state.starts_with('S')
+        }
+
+        let listener = TcpListener::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let addr = local_addr(listener.as_raw_fd()).unwrap();
+        let target = unsafe { libc::pthread_self() };
+        let tid = unsafe { libc::syscall(libc::SYS_gettid) } as libc::pid_t;
+        let accepting = Arc::new(AtomicBool::new(false));
+
+        let accepting_clone = accepting.clone();
+        let client = std::thread::spawn(move || {
+            while !accepting_clone.load(Ordering::SeqCst) {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            for _ in 0..3 {
+                // Only signal once the accept wait has parked the thread.
+                while !is_sleeping(tid) {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                unsafe { libc::pthread_kill(target, libc::SIGUSR1) };
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            std::net::TcpStream::connect(addr).unwrap()
+        });
+
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+        crate::block_on(async move {
+            accepting.store(true, Ordering::SeqCst);
+            let accepted = listener.accept().await.map(|(_, peer)| peer);
+            *result_clone.lock().unwrap() = Some(accepted);
+        });
+
+        let client = client.join().unwrap();
+        assert_eq!(DELIVERED.load(Ordering::SeqCst), 3);
+        let peer = result.lock().unwrap().take().unwrap().expect("accept failed");
+        assert_eq!(peer, client.local_addr().unwrap());
+    }
+
+    /// Set in the environment of the re-executed test binary that runs
+    /// `accept_backs_off_on_emfile_child`.
+    const EMFILE_CHILD_ENV: &str = "TAU_IFACE_EMFILE_CHILD";
+
+    /// Exhausting descriptors affects the whole process, so the real test runs
+    /// in a child process with its own fd limit.
+    #[test]
+    fn accept_backs_off_on_emfile() {
+        let status = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "tcp::tests::accept_backs_off_on_emfile_child",
+                "--test-threads=1",
+            ])
+            .env(EMFILE_CHILD_ENV, "1")
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn accept_backs_off_on_emfile_child() {
+        use std::sync::{Arc, Mutex};
+        use std::time::Instant;
+
+        if std::env::var_os(EMFILE_CHILD_ENV).is_none() {
+            return;
+        }
+
+        let listener = TcpListener::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let addr = local_addr(listener.as_raw_fd()).unwrap();
+        // A queued connection keeps the listener readable throughout.
+        let _client = std::net::TcpStream::connect(addr).unwrap();
+
+        // Lower the limit and fill every remaining slot.
+        let limit = libc::rlimit {
+            rlim_cur: 64,
+            rlim_max: 64,
+        };
+        assert_eq!(unsafe { libc::setrlimit(libc::RLIMIT_NOFILE, &limit) }, 0);
+        let mut hoard = Vec::new();
+        loop {
+            let fd = unsafe { libc::dup(0) };
+            if fd < 0 {
+                break;
+            }
+            hoard.push(unsafe { OwnedFd::from_raw_fd(fd) });
+        }
+
+        let attempts = Arc::new(Mutex::new(0));
+        let attempts_clone = attempts.clone();
+        crate::block_on(async move {
+            let start = Instant::now();
+            while start.elapsed() < Duration::from_millis(300) {
+                let err = listener.accept().await.map(|_| ()).unwrap_err();
+                assert_eq!(err.raw_os_error(), Some(libc::EMFILE));
+                *attempts_clone.lock().unwrap() += 1;
+            }
+        });
+        drop(hoard);
+
+        let attempts = *attempts.lock().unwrap();
+        assert!(attempts <= 10, "accept spun {} times in 300ms", attempts);
+    }
 }
@@ -1,14 +1,17 @@
-//! Async timer.
+//! Async timers.
 //!
-//! Creates a one-shot timer via the tau-rt reactor and provides a `Future`
-//! implementation that resolves when the deadline expires.
+//! Creates one-shot timers via the tau-rt reactor and provides a `Future`
+//! implementation that resolves when the deadline expires, plus a periodic
+//! [`Interval`] and a keyed [`DelayQueue`] built on top of them.
 
+use std::collections::{BTreeMap, HashMap};
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_ffi::ContextExt;
+use futures_core::Stream;
 
 use crate::ffi;
 
@@ -65,3 +68,269 @@ impl Drop for Timer {
         }
     }
 }
+
+/// A periodic timer ticking every `period`, starting immediately.
+///
+/// Await ticks with [`tick`](Self::tick) or use it as a never-ending
+/// `Stream` of tick deadlines. Ticks are scheduled relative to the previous
+/// deadline, so the schedule doesn't drift; if a consumer falls more than a
+/// period behind, missed ticks are skipped and the schedule restarts from now.
+pub struct Interval {
+    period: Duration,
+    /// Deadline of the next tick.
+    deadline: Instant,
+    /// Timer for `deadline`, created on first poll after each tick.
+    timer: Option<Timer>,
+}
+
+impl Interval {
+    /// Create an interval whose first tick completes immediately.
+    /// Panics if `period` is zero.
+    pub fn new(period: Duration) -> Self {
+        assert!(!period.is_zero(), "Interval period must be non-zero");
+        Interval {
+            period,
+            deadline: crate::now(),
+            timer: None,
+        }
+    }
+
+    /// The time between ticks.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Wait for the next tick. Returns the tick's scheduled deadline.
+    pub async fn tick(&mut self) -> Instant {
+        std::future::poll_fn(|cx| self.poll_tick(cx)).await
+    }
+
+    /// Poll for the next tick. Returns the tick's scheduled deadline.
+    pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Instant> {
+        let deadline = self.deadline;
+        let timer = self.timer.get_or_insert_with(|| {
+            Timer::after(deadline.saturating_duration_since(crate::now()))
+        });
+        if Pin::new(timer).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        self.timer = None;
+
+        let now = crate::now();
+        self.deadline = if now > deadline + self.period {
+            now + self.period
+        } else {
+            deadline + self.period
+        };
+        Poll::Ready(deadline)
+    }
+}
+
+impl Stream for Interval {
+    type Item = Instant;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Instant>> {
+        self.get_mut().poll_tick(cx).map(Some)
+    }
+}
+
+/// Handle to an entry in a [`DelayQueue`], for `reset` and `remove`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key(u64);
+
+/// A queue of values that each become available at their own deadline.
+///
+/// Values are inserted with a delay and popped by [`next`](Self::next) in
+/// deadline order once due. A single [`Timer`] is kept armed for the
+/// earliest deadline, however many entries are queued.
+pub struct DelayQueue<T> {
+    /// Entries ordered by (deadline, key id).
+    entries: BTreeMap<(Instant, u64), T>,
+    /// Reverse lookup: key id → deadline.
+    deadlines: HashMap<u64, Instant>,
+    next_id: u64,
+    /// The armed timer and the deadline it was armed for.
+    timer: Option<(Instant, Timer)>,
+}
+
+impl<T> DelayQueue<T> {
+    /// Create an empty queue.
+    pub fn new() -> Self {
+        DelayQueue {
+            entries: BTreeMap::new(),
+            deadlines: HashMap::new(),
+            next_id: 0,
+            timer: None,
+        }
+    }
+
+    /// Queue `value` to become available after `delay`.
+    pub fn insert(&mut self, value: T, delay: Duration) -> Key {
+        let id = self.next_id;
+        self.next_id += 1;
+        let deadline = crate::now() + delay;
+        self.entries.insert((deadline, id), value);
+        self.deadlines.insert(id, deadline);
+        Key(id)
+    }
+
+    /// Move an entry's deadline to `delay` from now. Returns false if the
+    /// entry was already popped or removed.
+    pub fn reset(&mut self, key: Key, delay: Duration) -> bool {
+        let Some(value) = self.take(key) else {
+            return false;
+        };
+        let deadline = crate::now() + delay;
+        self.entries.insert((deadline, key.0), value);
+        self.deadlines.insert(key.0, deadline);
+        true
+    }
+
+    /// Remove an entry before it expires, returning its value.
+    pub fn remove(&mut self, key: Key) -> Option<T> {
+        self.take(key)
+    }
+
+    /// Number of queued entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no entries are queued.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Wait for the next entry to expire and return its value. Returns `None`
+    /// right away if the queue is empty.
+    pub async fn next(&mut self) -> Option<T> {
+        std::future::poll_fn(|cx| self.poll_expired(cx)).await
+    }
+
+    /// Poll for the next expired entry. `Ready(None)` if the queue is empty.
+    pub fn poll_expired(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        loop {
+            let Some(&(deadline, id)) = self.entries.keys().next() else {
+                self.timer = None;
+                return Poll::Ready(None);
+            };
+            let now = crate::now();
+            if deadline <= now {
+                self.deadlines.remove(&id);
+                return Poll::Ready(self.entries.remove(&(deadline, id)));
+            }
+
+            // (Re-)arm for the earliest deadline if it changed.
+            let armed_for = self.timer.as_ref().map(|(at, _)| *at);
+            if armed_for != Some(deadline) {
+                self.timer = Some((deadline, Timer::after(deadline - now)));
+            }
+            let (_, timer) = self.timer.as_mut().unwrap();
+            if Pin::new(timer).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            self.timer = None;
+        }
+    }
+
+    fn take(&mut self, key: Key) -> Option<T> {
+        let deadline = self.deadlines.remove(&key.0)?;
+        self.entries.remove(&(deadline, key.0))
+    }
+}
+
+impl<T> Default for DelayQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn interval_stream_yields_ticks_one_period_apart() {
+        const PERIOD: Duration = Duration::from_millis(30);
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let start = Instant::now();
+            let mut interval = Interval::new(PERIOD);
+            let mut ticks = Vec::new();
+            for _ in 0..3 {
+                let tick = std::future::poll_fn(|cx| Pin::new(&mut interval).poll_next(cx));
+                ticks.push(tick.await.unwrap());
+            }
+            *result_clone.lock().unwrap() = Some((ticks, start.elapsed()));
+        });
+
+        let (ticks, elapsed) = result.lock().unwrap().take().unwrap();
+        // First tick is immediate, so three ticks span two periods.
+        assert!(elapsed >= 2 * PERIOD, "too fast: {:?}", elapsed);
+        assert!(elapsed < 2 * PERIOD + Duration::from_millis(200), "too slow: {:?}", elapsed);
+        assert_eq!(ticks[1] - ticks[0], PERIOD);
+        assert_eq!(ticks[2] - ticks[1], PERIOD);
+    }
+
+    #[test]
+    fn tick_returns_scheduled_deadlines() {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let mut interval = Interval::new(Duration::from_millis(5));
+            let first = interval.tick().await;
+            let second = interval.tick().await;
+            *result_clone.lock().unwrap() = Some(second - first);
+        });
+
+        assert_eq!(result.lock().unwrap().take(), Some(Duration::from_millis(5)));
+    }
+
+    #[test]
+    fn delay_queue_pops_in_deadline_order() {
+        let popped = Arc::new(Mutex::new(Vec::new()));
+        let popped_clone = popped.clone();
+
+        crate::block_on(async move {
+            let mut queue = DelayQueue::new();
+            queue.insert("slow", Duration::from_millis(30));
+            queue.insert("fast", Duration::from_millis(10));
+            queue.insert("medium", Duration::from_millis(20));
+            while let Some(value) = queue.next().await {
+                popped_clone.lock().unwrap().push(value);
+            }
+        });
+
+        assert_eq!(*popped.lock().unwrap(), vec!["fast", "medium", "slow"]);
+    }
+
+    #[test]
+    fn delay_queue_reset_and_remove() {
+        let popped = Arc::new(Mutex::new(Vec::new()));
+        let popped_clone = popped.clone();
+
+        crate::block_on(async move {
+            let mut queue = DelayQueue::new();
+            let a = queue.insert("a", Duration::from_millis(5));
+            let b = queue.insert("b", Duration::from_millis(10));
+            let c = queue.insert("c", Duration::from_millis(15));
+
+            assert!(queue.reset(a, Duration::from_millis(25)));
+            assert_eq!(queue.remove(b), Some("b"));
+            assert_eq!(queue.remove(b), None);
+            assert_eq!(queue.len(), 2);
+
+            while let Some(value) = queue.next().await {
+                popped_clone.lock().unwrap().push(value);
+            }
+            assert!(!queue.reset(c, Duration::from_millis(1)));
+            assert!(queue.is_empty());
+        });
+
+        assert_eq!(*popped.lock().unwrap(), vec!["c", "a"]);
+    }
+}
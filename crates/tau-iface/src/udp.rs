@@ -5,16 +5,33 @@
 use std::io;
 use std::net::SocketAddr;
 use std::os::unix::io::{AsRawFd, OwnedFd, RawFd};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
 
 use crate::async_fd::AsyncFd;
-use crate::tcp::{addr_family, create_socket, raw_to_socket_addr, socket_addr_to_raw};
+use crate::tcp::{
+    addr_family, buffer_size, create_socket, local_addr, raw_to_socket_addr, retry_interrupted,
+    set_buffer_size, socket_addr_to_raw,
+};
+
+/// Buffer size used by [`UdpSocket::recv_stream`] — the largest possible
+/// UDP payload, so datagrams are never truncated.
+const DEFAULT_RECV_CAPACITY: usize = 65_536;
 
 /// An async UDP socket.
 ///
 /// Supports both unconnected (send_to/recv_from) and connected (send/recv) modes.
+///
+/// On a connected socket, an ICMP "port unreachable" from the peer surfaces as
+/// `ConnectionRefused` from the next send or receive call. Protocols that want
+/// to keep going can opt out with
+/// [`set_ignore_connrefused`](Self::set_ignore_connrefused).
 pub struct UdpSocket {
     async_fd: AsyncFd,
     fd: OwnedFd,
+    ignore_connrefused: bool,
 }
 
 impl UdpSocket {
@@ -39,6 +56,7 @@ impl UdpSocket {
         Ok(UdpSocket {
             async_fd,
             fd: owned_fd,
+            ignore_connrefused: false,
         })
     }
 
@@ -48,16 +66,13 @@ impl UdpSocket {
     /// instead of `send_to`/`recv_from`.
     pub fn connect(&self, addr: SocketAddr) -> io::Result<()> {
         let (raw_addr, addr_len) = socket_addr_to_raw(&addr);
-        let result = unsafe {
+        retry_interrupted(|| unsafe {
             libc::connect(
                 self.fd.as_raw_fd(),
                 &raw_addr as *const _ as *const libc::sockaddr,
                 addr_len,
             )
-        };
-        if result < 0 {
-            return Err(io::Error::last_os_error());
-        }
+        })?;
         Ok(())
     }
 
@@ -66,7 +81,7 @@ impl UdpSocket {
         let (raw_addr, addr_len) = socket_addr_to_raw(&addr);
         loop {
             self.async_fd.writable().await?;
-            let n = unsafe {
+            let n = retry_interrupted(|| unsafe {
                 libc::sendto(
                     self.fd.as_raw_fd(),
                     buf.as_ptr() as *const libc::c_void,
@@ -75,15 +90,12 @@ impl UdpSocket {
                     &raw_addr as *const _ as *const libc::sockaddr,
                     addr_len,
                 )
-            };
-            if n >= 0 {
-                return Ok(n as usize);
-            }
-            let err = io::Error::last_os_error();
-            if err.kind() == io::ErrorKind::WouldBlock {
-                continue;
+            });
+            match n {
+                Ok(n) => return Ok(n as usize),
+                Err(err) if self.is_retryable(&err) => continue,
+                Err(err) => return Err(err),
             }
-            return Err(err);
         }
     }
 
@@ -91,82 +103,316 @@ impl UdpSocket {
     pub async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
         loop {
             self.async_fd.readable().await?;
+            match self.recv_from_nonblocking(buf) {
+                Err(err) if self.is_retryable(&err) => continue,
+                result => return result,
+            }
+        }
+    }
 
-            let mut storage: libc::sockaddr_storage = unsafe { std::mem::zeroed() };
-            let mut addr_len: libc::socklen_t =
-                std::mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
-
-            let n = unsafe {
-                libc::recvfrom(
-                    self.fd.as_raw_fd(),
-                    buf.as_mut_ptr() as *mut libc::c_void,
-                    buf.len(),
-                    0,
-                    &mut storage as *mut _ as *mut libc::sockaddr,
-                    &mut addr_len,
-                )
-            };
+    /// A stream of incoming datagrams and their senders.
+    ///
+    /// Each item owns a freshly allocated buffer sized to fit any datagram.
+    /// Use [`recv_stream_with_capacity`](Self::recv_stream_with_capacity) to
+    /// bound the allocation.
+    pub fn recv_stream(&self) -> RecvStream<'_> {
+        self.recv_stream_with_capacity(DEFAULT_RECV_CAPACITY)
+    }
 
-            if n >= 0 {
-                let addr = raw_to_socket_addr(&storage)?;
-                return Ok((n as usize, addr));
-            }
-            let err = io::Error::last_os_error();
-            if err.kind() == io::ErrorKind::WouldBlock {
-                continue;
-            }
-            return Err(err);
+    /// A stream of incoming datagrams, each read into a buffer of `capacity`
+    /// bytes. Longer datagrams are truncated, as with `recv_from`.
+    pub fn recv_stream_with_capacity(&self, capacity: usize) -> RecvStream<'_> {
+        RecvStream {
+            socket: self,
+            capacity,
         }
     }
 
+    /// One `recvfrom` call without waiting for readiness.
+    fn recv_from_nonblocking(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        let mut storage: libc::sockaddr_storage = unsafe { std::mem::zeroed() };
+        let mut addr_len: libc::socklen_t =
+            std::mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+
+        let n = retry_interrupted(|| unsafe {
+            libc::recvfrom(
+                self.fd.as_raw_fd(),
+                buf.as_mut_ptr() as *mut libc::c_void,
+                buf.len(),
+                0,
+                &mut storage as *mut _ as *mut libc::sockaddr,
+                &mut addr_len,
+            )
+        })?;
+
+        let addr = raw_to_socket_addr(&storage)?;
+        Ok((n as usize, addr))
+    }
+
     /// Send data on a connected socket.
     pub async fn send(&self, buf: &[u8]) -> io::Result<usize> {
         loop {
             self.async_fd.writable().await?;
-            let n = unsafe {
+            let n = retry_interrupted(|| unsafe {
                 libc::send(
                     self.fd.as_raw_fd(),
                     buf.as_ptr() as *const libc::c_void,
                     buf.len(),
                     0,
                 )
-            };
-            if n >= 0 {
-                return Ok(n as usize);
-            }
-            let err = io::Error::last_os_error();
-            if err.kind() == io::ErrorKind::WouldBlock {
-                continue;
+            });
+            match n {
+                Ok(n) => return Ok(n as usize),
+                Err(err) if self.is_retryable(&err) => continue,
+                Err(err) => return Err(err),
             }
-            return Err(err);
         }
     }
 
+    /// Send a whole datagram on a connected socket.
+    ///
+    /// Unlike a stream write, a datagram send is all-or-nothing at the
+    /// protocol level: there is no "rest" to send later. If the kernel reports
+    /// fewer than `buf.len()` bytes, this returns an error (`"datagram
+    /// truncated"`) instead of silently dropping the tail.
+    pub async fn send_all(&self, buf: &[u8]) -> io::Result<()> {
+        let n = self.send(buf).await?;
+        if n < buf.len() {
+            return Err(io::Error::other("datagram truncated"));
+        }
+        Ok(())
+    }
+
     /// Receive data on a connected socket.
     pub async fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
         loop {
             self.async_fd.readable().await?;
-            let n = unsafe {
+            let n = retry_interrupted(|| unsafe {
                 libc::recv(
                     self.fd.as_raw_fd(),
                     buf.as_mut_ptr() as *mut libc::c_void,
                     buf.len(),
                     0,
                 )
-            };
-            if n >= 0 {
-                return Ok(n as usize);
-            }
-            let err = io::Error::last_os_error();
-            if err.kind() == io::ErrorKind::WouldBlock {
-                continue;
+            });
+            match n {
+                Ok(n) => return Ok(n as usize),
+                Err(err) if self.is_retryable(&err) => continue,
+                Err(err) => return Err(err),
             }
-            return Err(err);
         }
     }
 
+    /// Retry transparently instead of returning `ConnectionRefused` when the
+    /// connected peer's port is closed. Off by default.
+    pub fn set_ignore_connrefused(&mut self, ignore: bool) {
+        self.ignore_connrefused = ignore;
+    }
+
+    /// Whether a syscall error means "wait for readiness and try again".
+    fn is_retryable(&self, err: &io::Error) -> bool {
+        match err.kind() {
+            io::ErrorKind::WouldBlock => true,
+            io::ErrorKind::ConnectionRefused => self.ignore_connrefused,
+            _ => false,
+        }
+    }
+
+    /// Returns the local address this socket is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        local_addr(self.fd.as_raw_fd())
+    }
+
+    /// Set the kernel receive buffer size (`SO_RCVBUF`). Raise it for bursty
+    /// high-rate traffic: datagrams arriving while the buffer is full are
+    /// dropped.
+    ///
+    /// The kernel treats this as a request: Linux doubles the value (to
+    /// leave room for bookkeeping) and caps it at `net.core.rmem_max`, so
+    /// [`recv_buffer_size`](Self::recv_buffer_size) may not read back what
+    /// was set.
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(self.fd.as_raw_fd(), libc::SO_RCVBUF, size)
+    }
+
+    /// The kernel receive buffer size (`SO_RCVBUF`).
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(self.fd.as_raw_fd(), libc::SO_RCVBUF)
+    }
+
+    /// Set the kernel send buffer size (`SO_SNDBUF`). Same caveats as
+    /// [`set_recv_buffer_size`](Self::set_recv_buffer_size), with the cap at
+    /// `net.core.wmem_max`.
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(self.fd.as_raw_fd(), libc::SO_SNDBUF, size)
+    }
+
+    /// The kernel send buffer size (`SO_SNDBUF`).
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(self.fd.as_raw_fd(), libc::SO_SNDBUF)
+    }
+
     /// Returns the raw file descriptor.
     pub fn as_raw_fd(&self) -> RawFd {
         self.fd.as_raw_fd()
     }
 }
+
+/// Stream of datagrams returned by [`UdpSocket::recv_stream`].
+///
+/// Never ends on its own; errors other than `WouldBlock` (and
+/// `ConnectionRefused`, if ignored) are yielded as `Err` items and the stream
+/// can keep being polled.
+pub struct RecvStream<'a> {
+    socket: &'a UdpSocket,
+    capacity: usize,
+}
+
+impl Stream for RecvStream<'_> {
+    type Item = io::Result<(Vec<u8>, SocketAddr)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match self.socket.async_fd.poll_readable(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(err)) => return Poll::Ready(Some(Err(err))),
+                Poll::Pending => return Poll::Pending,
+            }
+
+            let mut buf = vec![0u8; self.capacity];
+            match self.socket.recv_from_nonblocking(&mut buf) {
+                Ok((n, addr)) => {
+                    buf.truncate(n);
+                    return Poll::Ready(Some(Ok((buf, addr))));
+                }
+                Err(err) if self.socket.is_retryable(&err) => continue,
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn buffer_sizes_read_back_at_least_requested() {
+        let socket = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        socket.set_recv_buffer_size(100_000).unwrap();
+        socket.set_send_buffer_size(100_000).unwrap();
+        assert!(socket.recv_buffer_size().unwrap() >= 100_000);
+        assert!(socket.send_buffer_size().unwrap() >= 100_000);
+    }
+
+    #[test]
+    fn recv_stream_yields_datagrams() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+
+        crate::block_on(async move {
+            let receiver = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+            let sender = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+            let target = receiver.local_addr().unwrap();
+            sender.send_to(b"one", target).await.unwrap();
+            sender.send_to(b"two", target).await.unwrap();
+
+            let mut stream = receiver.recv_stream();
+            for _ in 0..2 {
+                let item = std::future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await;
+                let (data, from) = item.unwrap().unwrap();
+                assert_eq!(from, sender.local_addr().unwrap());
+                received_clone.lock().unwrap().push(data);
+            }
+        });
+
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![b"one".to_vec(), b"two".to_vec()]
+        );
+    }
+
+    #[test]
+    fn send_all_delivers_whole_datagram() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        let payload: Vec<u8> = (0..1200u32).map(|i| i as u8).collect();
+        let expected = payload.clone();
+
+        crate::block_on(async move {
+            let receiver = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+            let sender = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+            sender.connect(receiver.local_addr().unwrap()).unwrap();
+            sender.send_all(&payload).await.unwrap();
+
+            let mut buf = [0u8; 2048];
+            let (n, _) = receiver.recv_from(&mut buf).await.unwrap();
+            received_clone.lock().unwrap().extend_from_slice(&buf[..n]);
+        });
+
+        assert_eq!(*received.lock().unwrap(), expected);
+    }
+
+    /// A local address with nothing listening on it.
+    fn dead_port() -> SocketAddr {
+        let probe = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        probe.local_addr().unwrap()
+    }
+
+    #[test]
+    fn connrefused_surfaces_by_default() {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let socket = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+            socket.connect(dead_port()).unwrap();
+            socket.send(b"ping").await.unwrap();
+
+            let mut buf = [0u8; 16];
+            let outcome = crate::util::race(
+                socket.recv(&mut buf),
+                crate::sleep(std::time::Duration::from_millis(500)),
+            )
+            .await;
+            *result_clone.lock().unwrap() = Some(match outcome {
+                crate::util::Either::Left(res) => res.map_err(|e| e.kind()),
+                crate::util::Either::Right(()) => Ok(usize::MAX),
+            });
+        });
+
+        assert_eq!(
+            result.lock().unwrap().take(),
+            Some(Err(io::ErrorKind::ConnectionRefused))
+        );
+    }
+
+    #[test]
+    fn connrefused_ignored_when_configured() {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let mut socket = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+            socket.set_ignore_connrefused(true);
+            socket.connect(dead_port()).unwrap();
+            socket.send(b"ping").await.unwrap();
+
+            // The refusal is swallowed: recv keeps waiting and the timer wins.
+            let mut buf = [0u8; 16];
+            let outcome = crate::util::race(
+                socket.recv(&mut buf),
+                crate::sleep(std::time::Duration::from_millis(100)),
+            )
+            .await;
+            let timed_out = matches!(outcome, crate::util::Either::Right(()));
+
+            // Sending again is fine too.
+            let sent = socket.send(b"ping").await.is_ok();
+            *result_clone.lock().unwrap() = Some((timed_out, sent));
+        });
+
+        assert_eq!(result.lock().unwrap().take(), Some((true, true)));
+    }
+}
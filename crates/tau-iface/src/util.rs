@@ -0,0 +1,224 @@
+//! Future combinators.
+//!
+//! Small, self-contained helpers for waiting on groups of futures without
+//! pulling in the `futures` crate.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Poll;
+
+/// Wait for every future to complete, returning their outputs in order.
+///
+/// All futures are polled concurrently within the calling task.
+pub async fn join_all<F: Future>(futures: Vec<F>) -> Vec<F::Output> {
+    let mut pending: Vec<Option<Pin<Box<F>>>> =
+        futures.into_iter().map(|f| Some(Box::pin(f))).collect();
+    let mut outputs: Vec<Option<F::Output>> = pending.iter().map(|_| None).collect();
+
+    std::future::poll_fn(|cx| {
+        let mut all_done = true;
+        for (slot, output) in pending.iter_mut().zip(outputs.iter_mut()) {
+            if let Some(future) = slot {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(value) => {
+                        *output = Some(value);
+                        *slot = None;
+                    }
+                    Poll::Pending => all_done = false,
+                }
+            }
+        }
+        if all_done {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await;
+
+    outputs.into_iter().map(|o| o.unwrap()).collect()
+}
+
+/// Wait for every future to complete successfully, returning their outputs
+/// in order.
+///
+/// Short-circuits on the first `Err`: the remaining futures are dropped and
+/// the error is returned.
+pub async fn try_join_all<T, E, F>(futures: Vec<F>) -> Result<Vec<T>, E>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    let mut pending: Vec<Option<Pin<Box<F>>>> =
+        futures.into_iter().map(|f| Some(Box::pin(f))).collect();
+    let mut outputs: Vec<Option<T>> = pending.iter().map(|_| None).collect();
+
+    std::future::poll_fn(|cx| {
+        let mut all_done = true;
+        for (slot, output) in pending.iter_mut().zip(outputs.iter_mut()) {
+            if let Some(future) = slot {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(Ok(value)) => {
+                        *output = Some(value);
+                        *slot = None;
+                    }
+                    Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                    Poll::Pending => all_done = false,
+                }
+            }
+        }
+        if all_done {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    })
+    .await?;
+
+    Ok(outputs.into_iter().map(|o| o.unwrap()).collect())
+}
+
+/// The output of [`race`]: which of the two futures finished first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Either<A, B> {
+    Left(A),
+    Right(B),
+}
+
+/// Poll two futures concurrently and return the output of whichever completes
+/// first. The other future is dropped.
+///
+/// If both are ready on the same poll, `a` wins.
+pub async fn race<A: Future, B: Future>(a: A, b: B) -> Either<A::Output, B::Output> {
+    let mut a = Box::pin(a);
+    let mut b = Box::pin(b);
+    std::future::poll_fn(|cx| {
+        if let Poll::Ready(value) = a.as_mut().poll(cx) {
+            return Poll::Ready(Either::Left(value));
+        }
+        if let Poll::Ready(value) = b.as_mut().poll(cx) {
+            return Poll::Ready(Either::Right(value));
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    use crate::timer::Timer;
+
+    #[test]
+    fn join_all_resolves_after_longest() {
+        let elapsed = Arc::new(Mutex::new(None));
+        let elapsed_clone = elapsed.clone();
+
+        crate::block_on(async move {
+            let start = Instant::now();
+            let outputs = join_all(vec![
+                Timer::after(Duration::from_millis(10)),
+                Timer::after(Duration::from_millis(50)),
+                Timer::after(Duration::from_millis(30)),
+            ])
+            .await;
+            assert_eq!(outputs.len(), 3);
+            *elapsed_clone.lock().unwrap() = Some(start.elapsed());
+        });
+
+        let elapsed = elapsed.lock().unwrap().expect("join_all should complete");
+        assert!(
+            elapsed >= Duration::from_millis(50),
+            "resolved before the longest timer: {:?}",
+            elapsed
+        );
+    }
+
+    #[test]
+    fn join_all_empty() {
+        let done = Arc::new(Mutex::new(false));
+        let done_clone = done.clone();
+        crate::block_on(async move {
+            let outputs: Vec<()> = join_all(Vec::<Timer>::new()).await;
+            assert!(outputs.is_empty());
+            *done_clone.lock().unwrap() = true;
+        });
+        assert!(*done.lock().unwrap());
+    }
+
+    async fn after(ms: u64, result: Result<u64, &'static str>) -> Result<u64, &'static str> {
+        Timer::after(Duration::from_millis(ms)).await;
+        result
+    }
+
+    #[test]
+    fn try_join_all_collects_in_order() {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let joined =
+                try_join_all(vec![after(20, Ok(1)), after(5, Ok(2)), after(10, Ok(3))]).await;
+            *result_clone.lock().unwrap() = Some(joined);
+        });
+
+        assert_eq!(result.lock().unwrap().take(), Some(Ok(vec![1, 2, 3])));
+    }
+
+    #[test]
+    fn try_join_all_short_circuits_on_error() {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let start = Instant::now();
+            let joined =
+                try_join_all(vec![after(500, Ok(1)), after(10, Err("boom")), after(500, Ok(3))])
+                    .await;
+            *result_clone.lock().unwrap() = Some((joined, start.elapsed()));
+        });
+
+        let (joined, elapsed) = result.lock().unwrap().take().unwrap();
+        assert_eq!(joined, Err("boom"));
+        assert!(
+            elapsed < Duration::from_millis(500),
+            "should not wait for the slow futures: {:?}",
+            elapsed
+        );
+    }
+
+    #[test]
+    fn race_shorter_sleep_wins() {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let start = Instant::now();
+            let winner = race(
+                crate::sleep(Duration::from_millis(10)),
+                crate::sleep(Duration::from_millis(100)),
+            )
+            .await;
+            *result_clone.lock().unwrap() = Some((winner, start.elapsed()));
+        });
+
+        let (winner, elapsed) = result.lock().unwrap().take().unwrap();
+        assert_eq!(winner, Either::Left(()));
+        assert!(elapsed < Duration::from_millis(100), "loser was awaited: {:?}", elapsed);
+    }
+
+    #[test]
+    fn race_right_wins_with_its_output() {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+
+        crate::block_on(async move {
+            let winner = race(crate::sleep(Duration::from_millis(100)), async { 7 }).await;
+            *result_clone.lock().unwrap() = Some(winner);
+        });
+
+        assert_eq!(result.lock().unwrap().take(), Some(Either::Right(7)));
+    }
+}
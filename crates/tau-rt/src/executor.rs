@@ -1,16 +1,62 @@
-use std::sync::OnceLock;
-use std::time::Duration;
+use std::cell::Cell;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
 
-use async_ffi::FfiFuture;
+use async_ffi::{FfiFuture, LocalFfiFuture};
 use async_task::Runnable;
 use concurrent_queue::ConcurrentQueue;
 
 use crate::reactor;
 
+/// Monotonic executor counters. `Relaxed` everywhere — they're statistics,
+/// not synchronization.
+#[derive(Default)]
+pub(crate) struct ExecutorMetrics {
+    pub(crate) tasks_spawned: AtomicU64,
+    pub(crate) tasks_completed: AtomicU64,
+    pub(crate) tasks_polled: AtomicU64,
+    pub(crate) watchdog_trips: AtomicU64,
+}
+
+/// Default for `Executor::set_watchdog()`'s limit: off.
+const DEFAULT_WATCHDOG_LIMIT: u64 = 0;
+
+/// How `block_on()` waits for the reactor once no task is ready to run.
+///
+/// The default parks in the reactor until IO, a timer or a cross-thread
+/// wakeup arrives, which costs no CPU but pays a syscall round trip on every
+/// wakeup. Spinning first trades CPU for latency.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct BlockOnConfig {
+    /// Non-blocking reactor polls to make, each followed by another pass
+    /// over the ready queue, before blocking.
+    pub(crate) max_spin_iters: u32,
+    /// Longest a single blocking reactor wait may take; `None` waits for an
+    /// event.
+    pub(crate) idle_timeout: Option<Duration>,
+}
+
 /// The global single-threaded task executor.
 pub(crate) struct Executor {
     /// Ready queue: tasks whose wakers have fired.
     queue: ConcurrentQueue<Runnable>,
+    /// Set by `shutdown()`; once set, `spawn()` drops new futures.
+    shutting_down: AtomicBool,
+    /// Spawned tasks whose future has not been dropped yet — whether it ran
+    /// to completion or async-task destroyed it unfinished.
+    live_tasks: AtomicU64,
+    /// Wakes of a task from anywhere but its own poll — the reactor, another
+    /// task or another thread. Each one is progress, so it resets the
+    /// watchdog's streak.
+    foreign_wakes: AtomicU64,
+    /// Consecutive polls of one task, with no IO, timer or foreign wake in
+    /// between, that trip the `block_on()` watchdog. 0 disables it.
+    watchdog_limit: AtomicU64,
+    /// Whether a tripped watchdog makes `block_on()` give up.
+    watchdog_abandon: AtomicBool,
+    pub(crate) metrics: ExecutorMetrics,
 }
 
 static EXECUTOR: OnceLock<Executor> = OnceLock::new();
@@ -18,21 +64,143 @@ static EXECUTOR: OnceLock<Executor> = OnceLock::new();
 pub(crate) fn get() -> &'static Executor {
     EXECUTOR.get_or_init(|| Executor {
         queue: ConcurrentQueue::unbounded(),
+        shutting_down: AtomicBool::new(false),
+        live_tasks: AtomicU64::new(0),
+        foreign_wakes: AtomicU64::new(0),
+        watchdog_limit: AtomicU64::new(DEFAULT_WATCHDOG_LIMIT),
+        watchdog_abandon: AtomicBool::new(false),
+        metrics: ExecutorMetrics::default(),
     })
 }
 
+/// Spots a task being polled over and over without the runtime making any
+/// other progress — the signature of a future that wakes itself every poll.
+struct Watchdog {
+    limit: u64,
+    last_task: Option<NonNull<()>>,
+    streak: u64,
+    /// `Executor::foreign_wakes` as of the last observed poll.
+    foreign_wakes: u64,
+}
+
+impl Watchdog {
+    fn new(limit: u64) -> Self {
+        Watchdog {
+            limit,
+            last_task: None,
+            streak: 0,
+            foreign_wakes: 0,
+        }
+    }
+
+    /// Record a poll of `task`, given the executor's foreign wake count.
+    /// Returns true each time the streak of back-to-back polls of the same
+    /// task, with no foreign wake in between, reaches the limit.
+    fn observe(&mut self, task: NonNull<()>, foreign_wakes: u64) -> bool {
+        if self.last_task != Some(task) || self.foreign_wakes != foreign_wakes {
+            self.last_task = Some(task);
+            self.foreign_wakes = foreign_wakes;
+            self.streak = 0;
+        }
+        self.streak += 1;
+        if self.limit == 0 || self.streak < self.limit {
+            return false;
+        }
+        self.streak = 0;
+        true
+    }
+
+    /// IO or a timer made progress; whatever was spinning may be waiting on it.
+    fn reset(&mut self) {
+        self.last_task = None;
+        self.streak = 0;
+    }
+}
+
+/// Counts a spawned task as live from spawn until its future is dropped.
+/// Moved into the wrapped future, so it goes away however the task ends:
+/// completion, or async-task destroying a task whose last waker was dropped.
+struct LiveTask;
+
+impl LiveTask {
+    fn new() -> Self {
+        get().live_tasks.fetch_add(1, Ordering::AcqRel);
+        LiveTask
+    }
+}
+
+impl Drop for LiveTask {
+    fn drop(&mut self) {
+        get().live_tasks.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+thread_local! {
+    /// Ready queue for tasks spawned with `spawn_local()` on this thread.
+    /// Each task's schedule function holds a clone, so a waker firing on any
+    /// thread requeues the task here — and only this thread pops from it.
+    static LOCAL_QUEUE: Arc<ConcurrentQueue<Runnable>> =
+        Arc::new(ConcurrentQueue::unbounded());
+
+    /// How many tasks this thread is polling right now (nested `try_tick()`
+    /// calls from inside a task count again).
+    static POLL_DEPTH: Cell<usize> = const { Cell::new(0) };
+
+    /// The task this thread is polling right now, if any.
+    static CURRENT_TASK: Cell<Option<NonNull<()>>> = const { Cell::new(None) };
+}
+
+/// Whether the calling thread is currently polling a task — i.e. running
+/// inside an async context driven by this runtime.
+pub(crate) fn in_task() -> bool {
+    POLL_DEPTH.with(|depth| depth.get() > 0)
+}
+
+/// Count a wake of `runnable` unless it comes from the task's own poll.
+fn count_foreign_wake(runnable: Runnable) -> Runnable {
+    let task = runnable.into_raw();
+    if CURRENT_TASK.with(Cell::get) != Some(task) {
+        get().foreign_wakes.fetch_add(1, Ordering::Relaxed);
+    }
+    unsafe { Runnable::from_raw(task) }
+}
+
 /// Schedule function for async-task: pushes a runnable into the global queue.
 /// This is `Fn(Runnable) + Send + Sync + 'static` — safe to call from wakers
-/// on any thread.
+/// on any thread. Wakes the reactor in case the driving thread is parked in
+/// `react()` waiting for IO that will never come.
 fn schedule(runnable: Runnable) {
+    let runnable = count_foreign_wake(runnable);
     get().queue.push(runnable).unwrap();
+    reactor::get().wake_up();
 }
 
 impl Executor {
     /// Spawn a future onto the executor. The future is polled by whoever
     /// calls `try_tick()` or `block_on()`.
+    ///
+    /// After `shutdown()` the future is dropped without being polled.
     pub(crate) fn spawn(&self, future: FfiFuture<()>) {
-        // FfiFuture<()> is Send + 'static, so we use async_task::spawn
+        if self.shutting_down.load(Ordering::Acquire) {
+            rt_warn!("spawn after shutdown: future dropped");
+            return;
+        }
+        self.metrics.tasks_spawned.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "trace")]
+        let id = {
+            static TASK_ID: AtomicU64 = AtomicU64::new(0);
+            let id = TASK_ID.fetch_add(1, Ordering::Relaxed);
+            rt_trace!(task = id, "task spawned");
+            id
+        };
+        let live = LiveTask::new();
+        let future = async move {
+            let _live = live;
+            future.await;
+            get().metrics.tasks_completed.fetch_add(1, Ordering::Relaxed);
+            rt_trace!(task = id, "task completed");
+        };
+        // The wrapped future is Send + 'static, so we use async_task::spawn
         // (not spawn_local). This avoids thread-affinity panics — important
         // because wakers may fire from any thread and the executor runs on
         // whichever thread drives the loop.
@@ -41,48 +209,172 @@ impl Executor {
         runnable.schedule(); // Push to queue for first poll.
     }
 
+    /// Spawn a `!Send` future that only ever runs on the calling thread: it
+    /// is polled by this thread's `try_tick()` / `block_on()` and never by
+    /// another thread's, wherever its waker fires.
+    ///
+    /// After `shutdown()` the future is dropped without being polled.
+    pub(crate) fn spawn_local(&self, future: LocalFfiFuture<()>) {
+        if self.shutting_down.load(Ordering::Acquire) {
+            rt_warn!("spawn_local after shutdown: future dropped");
+            return;
+        }
+        self.metrics.tasks_spawned.fetch_add(1, Ordering::Relaxed);
+        rt_trace!("local task spawned");
+        let live = LiveTask::new();
+        let future = async move {
+            let _live = live;
+            future.await;
+            get().metrics.tasks_completed.fetch_add(1, Ordering::Relaxed);
+            rt_trace!("local task completed");
+        };
+        let queue = LOCAL_QUEUE.with(Arc::clone);
+        let schedule = move |runnable| {
+            queue.push(count_foreign_wake(runnable)).unwrap();
+            reactor::get().wake_up();
+        };
+        // spawn_local: the future is !Send, and async-task panics if the
+        // runnable is ever run off this thread — which LOCAL_QUEUE prevents.
+        let (runnable, task) = async_task::spawn_local(future, schedule);
+        task.detach();
+        runnable.schedule();
+    }
+
+    /// Configure the `block_on()` watchdog: warn (and count a trip in the
+    /// metrics) whenever one task is polled `limit` times in a row with no IO
+    /// or timer event, and no wake from outside the task, in between. With
+    /// `abandon`, `block_on()` also gives up on its future. A `limit` of 0,
+    /// the default, disables the watchdog.
+    pub(crate) fn set_watchdog(&self, limit: u64, abandon: bool) {
+        self.watchdog_limit.store(limit, Ordering::Relaxed);
+        self.watchdog_abandon.store(abandon, Ordering::Relaxed);
+    }
+
     /// Pop one ready task and run it. Returns true if a task was polled.
+    ///
+    /// This thread's local tasks go first, then the shared queue.
     pub(crate) fn try_tick(&self) -> bool {
-        match self.queue.pop() {
-            Ok(runnable) => {
-                runnable.run();
-                true
-            }
-            Err(_) => false,
+        self.tick().is_some()
+    }
+
+    /// Run ready tasks until `max` have been polled or none are left.
+    /// Returns how many were polled.
+    pub(crate) fn try_tick_n(&self, max: u64) -> u64 {
+        let mut polled = 0;
+        while polled < max && self.try_tick() {
+            polled += 1;
         }
+        polled
+    }
+
+    /// `try_tick()`, returning the identity of the task that was polled.
+    fn tick(&self) -> Option<NonNull<()>> {
+        let runnable = LOCAL_QUEUE
+            .with(|local| local.pop())
+            .or_else(|_| self.queue.pop())
+            .ok()?;
+        self.metrics.tasks_polled.fetch_add(1, Ordering::Relaxed);
+        rt_trace!("task polled");
+        // The raw pointer is the task's allocation, stable for its lifetime.
+        let task = runnable.into_raw();
+        let runnable: Runnable = unsafe { Runnable::from_raw(task) };
+        POLL_DEPTH.with(|depth| depth.set(depth.get() + 1));
+        let outer = CURRENT_TASK.with(|current| current.replace(Some(task)));
+        runnable.run();
+        CURRENT_TASK.with(|current| current.set(outer));
+        POLL_DEPTH.with(|depth| depth.set(depth.get() - 1));
+        Some(task)
+    }
+
+    /// Drive the executor and reactor until the given future completes,
+    /// parking in the reactor whenever no task is ready.
+    ///
+    /// Returns false if the watchdog (see `set_watchdog()`) tripped with
+    /// `abandon` set: the future is dropped unfinished.
+    pub(crate) fn block_on(&self, future: FfiFuture<()>) -> bool {
+        self.block_on_with(future, BlockOnConfig::default())
     }
 
-    /// Drive the executor and reactor until the given future completes.
-    pub(crate) fn block_on(&self, future: FfiFuture<()>) {
+    /// `block_on()`, waiting for the reactor as `config` says when idle.
+    pub(crate) fn block_on_with(&self, future: FfiFuture<()>, config: BlockOnConfig) -> bool {
         let (runnable, task) = async_task::spawn(future, schedule);
         runnable.schedule();
 
         let reactor = reactor::get();
+        let mut watchdog = Watchdog::new(self.watchdog_limit.load(Ordering::Relaxed));
+        let mut spins = 0;
 
         loop {
             if task.is_finished() {
-                break;
+                return true;
             }
 
             // Drive executor: poll all ready tasks.
             let mut did_work = false;
-            while self.try_tick() {
+            while let Some(polled) = self.tick() {
                 did_work = true;
                 // Check after each tick — the future might be done.
                 if task.is_finished() {
-                    return;
+                    return true;
+                }
+                if watchdog.observe(polled, self.foreign_wakes.load(Ordering::Relaxed)) {
+                    self.metrics.watchdog_trips.fetch_add(1, Ordering::Relaxed);
+                    rt_warn!(
+                        polls = watchdog.limit,
+                        trips = self.metrics.watchdog_trips.load(Ordering::Relaxed),
+                        "task polled repeatedly without IO or timer progress; \
+                         is a future waking itself on every poll?"
+                    );
+                    if self.watchdog_abandon.load(Ordering::Relaxed) {
+                        return false;
+                    }
                 }
             }
 
-            // Drive reactor: wait for IO/timers.
+            // Drive reactor: wait for IO/timers, draining every ready event.
             // Non-blocking if we just did work (there might be more tasks
-            // after wakers fire), short sleep otherwise.
+            // after wakers fire) or are still spinning; otherwise block
+            // until something happens. Wakers and spawns from other threads
+            // interrupt the wait via `wake_up()`.
             let timeout = if did_work {
+                spins = 0;
+                Some(Duration::ZERO)
+            } else if spins < config.max_spin_iters {
+                spins += 1;
                 Some(Duration::ZERO)
             } else {
-                Some(Duration::from_millis(10))
+                config.idle_timeout
             };
-            let _ = reactor.react(timeout);
+            if matches!(reactor.react_all(timeout), Ok(events) if events > 0) {
+                watchdog.reset();
+            }
+        }
+    }
+
+    /// Spawned tasks that are still alive: not completed, and not destroyed
+    /// by async-task after their last waker went away.
+    fn pending(&self) -> u64 {
+        self.live_tasks.load(Ordering::Acquire)
+    }
+
+    /// Stop accepting spawns and drive the executor and reactor until every
+    /// spawned task has completed or `timeout` elapses. Returns the number of
+    /// tasks still pending. Shutdown is permanent for the process.
+    pub(crate) fn shutdown(&self, timeout: Duration) -> u64 {
+        self.shutting_down.store(true, Ordering::Release);
+        let deadline = Instant::now() + timeout;
+        let reactor = reactor::get();
+
+        loop {
+            while self.try_tick() {}
+            if self.pending() == 0 {
+                return 0;
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return self.pending();
+            }
+            let _ = reactor.react(Some(remaining.min(Duration::from_millis(10))));
         }
     }
 }
@@ -92,7 +384,7 @@ mod tests {
     use super::*;
     use std::future::Future;
     use std::pin::Pin;
-    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::atomic::AtomicBool;
     use std::sync::{Arc, Mutex};
     use std::task::{Context, Poll};
     use std::time::Instant;
@@ -209,6 +501,69 @@ mod tests {
         assert_eq!(counter.load(Ordering::SeqCst), 1);
     }
 
+    #[test]
+    fn metrics_count_spawned_and_completed_tasks() {
+        let _g = test_guard();
+        let executor = get();
+        let spawned_before = executor.metrics.tasks_spawned.load(Ordering::Relaxed);
+        let completed_before = executor.metrics.tasks_completed.load(Ordering::Relaxed);
+
+        const N: u64 = 5;
+        for _ in 0..N {
+            executor.spawn(FfiFuture::new(async {}));
+        }
+        while executor.try_tick() {}
+
+        let spawned = executor.metrics.tasks_spawned.load(Ordering::Relaxed) - spawned_before;
+        let completed =
+            executor.metrics.tasks_completed.load(Ordering::Relaxed) - completed_before;
+        assert_eq!(spawned, N);
+        assert_eq!(completed, N);
+    }
+
+    #[test]
+    fn pending_drops_tasks_destroyed_without_completing() {
+        let _g = test_guard();
+        let executor = get();
+        let before = executor.pending();
+
+        // Parks forever, holding the only waker in a slot the test controls.
+        let slot: Arc<Mutex<Option<std::task::Waker>>> = Arc::new(Mutex::new(None));
+        let slot_clone = slot.clone();
+        executor.spawn(FfiFuture::new(std::future::poll_fn(move |cx| {
+            *slot_clone.lock().unwrap() = Some(cx.waker().clone());
+            Poll::<()>::Pending
+        })));
+        assert_eq!(executor.pending(), before + 1);
+        while executor.try_tick() {}
+        assert_eq!(executor.pending(), before + 1);
+
+        // Dropping the last waker closes the task; async-task schedules it
+        // once more so the executor drops the unfinished future.
+        slot.lock().unwrap().take();
+        while executor.try_tick() {}
+        assert_eq!(executor.pending(), before);
+    }
+
+    #[test]
+    fn try_tick_n_stops_at_max() {
+        let _g = test_guard();
+        let executor = get();
+        let counter = Arc::new(AtomicU64::new(0));
+        for _ in 0..10 {
+            let counter = counter.clone();
+            executor.spawn(FfiFuture::new(async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }));
+        }
+
+        assert_eq!(executor.try_tick_n(4), 4);
+        assert_eq!(counter.load(Ordering::SeqCst), 4);
+        assert_eq!(executor.try_tick_n(100), 6);
+        assert_eq!(counter.load(Ordering::SeqCst), 10);
+        assert_eq!(executor.try_tick_n(4), 0);
+    }
+
     #[test]
     fn try_tick_empty_queue() {
         let _g = test_guard();
@@ -257,4 +612,159 @@ mod tests {
             elapsed
         );
     }
+
+    // ── Idle strategy ───────────────────────────────────────────────
+
+    /// Pending on its first poll; a thread wakes it `delay` later, and the
+    /// next poll completes. Nothing else wakes the reactor meanwhile.
+    struct WakeLater {
+        delay: Duration,
+        started: bool,
+    }
+
+    impl Future for WakeLater {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.started {
+                return Poll::Ready(());
+            }
+            self.started = true;
+            let (waker, delay) = (cx.waker().clone(), self.delay);
+            std::thread::spawn(move || {
+                std::thread::sleep(delay);
+                waker.wake();
+            });
+            Poll::Pending
+        }
+    }
+
+    /// `react()` calls one `block_on_with(config)` makes while its future
+    /// waits 100ms for a wakeup from another thread.
+    fn react_calls_while_waiting(config: BlockOnConfig) -> u64 {
+        let calls = || reactor::get().metrics.react_calls.load(Ordering::Relaxed);
+        let before = calls();
+        let future = WakeLater {
+            delay: Duration::from_millis(100),
+            started: false,
+        };
+        assert!(get().block_on_with(FfiFuture::new(future), config));
+        calls() - before
+    }
+
+    #[test]
+    fn block_on_parks_until_woken_by_default() {
+        let _g = test_guard();
+        let start = Instant::now();
+        let calls = react_calls_while_waiting(BlockOnConfig::default());
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        // One non-blocking pass after the first poll, then a single park.
+        assert!(calls <= 4, "parked block_on reacted {calls} times");
+    }
+
+    #[test]
+    fn idle_timeout_caps_each_reactor_wait() {
+        let _g = test_guard();
+        let calls = react_calls_while_waiting(BlockOnConfig {
+            max_spin_iters: 0,
+            idle_timeout: Some(Duration::from_millis(10)),
+        });
+        // ~10 waits of 10ms each; a longer cap would need fewer.
+        assert!((6..=20).contains(&calls), "reacted {calls} times");
+    }
+
+    #[test]
+    fn max_spin_iters_bounds_the_busy_phase() {
+        let _g = test_guard();
+        let calls = react_calls_while_waiting(BlockOnConfig {
+            max_spin_iters: 50,
+            idle_timeout: None,
+        });
+        // 50 spins, then park for the rest of the 100ms instead of spinning.
+        assert!((51..=55).contains(&calls), "reacted {calls} times");
+    }
+
+    // ── Watchdog ────────────────────────────────────────────────────
+
+    /// Wakes itself on every poll; completes after `remaining` polls, or
+    /// never if `None`.
+    struct SelfWaking {
+        remaining: Option<usize>,
+    }
+
+    impl Future for SelfWaking {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            match &mut self.remaining {
+                Some(0) => return Poll::Ready(()),
+                Some(n) => *n -= 1,
+                None => {}
+            }
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn watchdog_counts_self_waking_streaks() {
+        let _g = test_guard();
+        let executor = get();
+        let trips_before = executor.metrics.watchdog_trips.load(Ordering::Relaxed);
+
+        executor.set_watchdog(100, false);
+        let completed = executor.block_on(FfiFuture::new(SelfWaking {
+            remaining: Some(250),
+        }));
+        executor.set_watchdog(DEFAULT_WATCHDOG_LIMIT, false);
+
+        assert!(completed, "warn-only watchdog must not abandon the future");
+        let trips = executor.metrics.watchdog_trips.load(Ordering::Relaxed) - trips_before;
+        assert_eq!(trips, 2);
+    }
+
+    /// Pending `remaining` more times; each poll hands its waker to a new
+    /// thread that wakes it.
+    struct WokenFromThread {
+        remaining: usize,
+    }
+
+    impl Future for WokenFromThread {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.remaining == 0 {
+                return Poll::Ready(());
+            }
+            self.remaining -= 1;
+            let waker = cx.waker().clone();
+            std::thread::spawn(move || waker.wake());
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn watchdog_ignores_tasks_woken_from_other_threads() {
+        let _g = test_guard();
+        let executor = get();
+        let trips_before = executor.metrics.watchdog_trips.load(Ordering::Relaxed);
+
+        executor.set_watchdog(10, true);
+        let completed = executor.block_on(FfiFuture::new(WokenFromThread { remaining: 50 }));
+        executor.set_watchdog(DEFAULT_WATCHDOG_LIMIT, false);
+
+        assert!(completed);
+        assert_eq!(executor.metrics.watchdog_trips.load(Ordering::Relaxed), trips_before);
+    }
+
+    #[test]
+    fn watchdog_abandons_livelocked_block_on() {
+        let _g = test_guard();
+        let executor = get();
+        let trips_before = executor.metrics.watchdog_trips.load(Ordering::Relaxed);
+
+        executor.set_watchdog(50, true);
+        let completed = executor.block_on(FfiFuture::new(SelfWaking { remaining: None }));
+        executor.set_watchdog(DEFAULT_WATCHDOG_LIMIT, false);
+
+        assert!(!completed);
+        assert_eq!(executor.metrics.watchdog_trips.load(Ordering::Relaxed) - trips_before, 1);
+    }
 }
@@ -3,9 +3,11 @@
 //! All functions are `#[no_mangle] pub extern "C"` and use only FFI-safe types.
 //! These are the ONLY public interface of the shared library.
 
-use async_ffi::{FfiContext, FfiFuture};
+use std::sync::atomic::Ordering;
 
-use crate::{executor, reactor};
+use async_ffi::{FfiContext, FfiFuture, LocalFfiFuture};
+
+use crate::{executor, reactor, signal};
 
 // ── IO ──────────────────────────────────────────────────────────────
 
@@ -21,8 +23,9 @@ pub extern "C" fn tau_rt_io_deregister(handle: u64) {
     reactor::get().io_deregister(handle);
 }
 
-/// Poll for readability. Returns 0=Pending, 1=Ready.
+/// Poll for readability. Returns 0=Pending, 1=Ready, 2=Error.
 /// If Pending, stores the waker from `cx` and wakes it when readable.
+/// On Error, `tau_rt_io_last_error` returns the OS error code.
 #[no_mangle]
 pub extern "C" fn tau_rt_io_poll_readable(handle: u64, cx: *mut FfiContext<'_>) -> u8 {
     let ffi_cx = unsafe { &mut *cx };
@@ -30,12 +33,13 @@ pub extern "C" fn tau_rt_io_poll_readable(handle: u64, cx: *mut FfiContext<'_>)
         let waker = std_cx.waker().clone();
         match reactor::get().io_poll_readable(handle, waker) {
             std::task::Poll::Pending => 0,
-            std::task::Poll::Ready(()) => 1,
+            std::task::Poll::Ready(Ok(())) => 1,
+            std::task::Poll::Ready(Err(_)) => 2,
         }
     })
 }
 
-/// Poll for writability. Returns 0=Pending, 1=Ready.
+/// Poll for writability. Returns 0=Pending, 1=Ready, 2=Error.
 #[no_mangle]
 pub extern "C" fn tau_rt_io_poll_writable(handle: u64, cx: *mut FfiContext<'_>) -> u8 {
     let ffi_cx = unsafe { &mut *cx };
@@ -43,11 +47,91 @@ pub extern "C" fn tau_rt_io_poll_writable(handle: u64, cx: *mut FfiContext<'_>)
         let waker = std_cx.waker().clone();
         match reactor::get().io_poll_writable(handle, waker) {
             std::task::Poll::Pending => 0,
-            std::task::Poll::Ready(()) => 1,
+            std::task::Poll::Ready(Ok(())) => 1,
+            std::task::Poll::Ready(Err(_)) => 2,
+        }
+    })
+}
+
+/// Poll for readability and/or writability at once. `interest` and the
+/// result are bitmasks: 1=Readable, 2=Writable. Returns 0=Pending, the
+/// directions that are ready, or 0x80=Error (see `tau_rt_io_last_error`).
+/// If Pending, stores the waker from `cx` for every requested direction.
+#[no_mangle]
+pub extern "C" fn tau_rt_io_poll_ready(handle: u64, interest: u8, cx: *mut FfiContext<'_>) -> u8 {
+    let ffi_cx = unsafe { &mut *cx };
+    ffi_cx.with_context(|std_cx| {
+        let waker = std_cx.waker().clone();
+        let (read, write) = (interest & 1 != 0, interest & 2 != 0);
+        match reactor::get().io_poll_ready(handle, read, write, waker) {
+            std::task::Poll::Pending => 0,
+            std::task::Poll::Ready(Ok((readable, writable))) => {
+                u8::from(readable) | u8::from(writable) << 1
+            }
+            std::task::Poll::Ready(Err(_)) => 0x80,
         }
     })
 }
 
+/// Stop waiting for the directions in the `interest` bitmask (1=Readable,
+/// 2=Writable): their stored wakers are dropped and the poller is re-armed
+/// for the remaining direction only. Returns 0, or a negated OS error code.
+#[no_mangle]
+pub extern "C" fn tau_rt_io_clear_interest(handle: u64, interest: u8) -> i32 {
+    let (read, write) = (interest & 1 != 0, interest & 2 != 0);
+    match reactor::get().io_clear_interest(handle, read, write) {
+        Ok(()) => 0,
+        Err(err) => -err.raw_os_error().unwrap_or(libc::EIO),
+    }
+}
+
+/// OS error code recorded for an IO source after a poll returned 2=Error.
+/// Returns 0 if no error is recorded.
+#[no_mangle]
+pub extern "C" fn tau_rt_io_last_error(handle: u64) -> i32 {
+    reactor::get().io_last_error(handle)
+}
+
+/// Check waited-on fds for having been closed every `nanos` nanoseconds,
+/// waking their waiters with `EBADF`. 0 turns the check off (the default).
+#[no_mangle]
+pub extern "C" fn tau_rt_set_fd_check_interval(nanos: u64) {
+    let interval = (nanos != 0).then(|| std::time::Duration::from_nanos(nanos));
+    reactor::get().set_fd_check_interval(interval);
+}
+
+// ── Processes ───────────────────────────────────────────────────────
+
+/// Watch process `pid` for exit. Returns an IO handle (>= 0), or a negated
+/// OS error code on failure. Release it with `tau_rt_io_deregister`.
+#[no_mangle]
+pub extern "C" fn tau_rt_pidfd_create(pid: i32) -> i64 {
+    match reactor::get().pidfd_create(pid) {
+        Ok(handle) => handle as i64,
+        Err(err) => -(err.raw_os_error().unwrap_or(libc::ENOSYS) as i64),
+    }
+}
+
+/// Poll for process exit. Returns 0=Pending, 1=Exited, 2=Error.
+#[no_mangle]
+pub extern "C" fn tau_rt_pidfd_poll(handle: u64, cx: *mut FfiContext<'_>) -> u8 {
+    tau_rt_io_poll_readable(handle, cx)
+}
+
+// ── Signals ─────────────────────────────────────────────────────────
+
+/// Install the process-wide `SIGINT` handler (first call only) and return a
+/// non-blocking fd that becomes readable, with one byte per signal, each
+/// time Ctrl+C arrives. Returns a negated OS error code on failure. The fd
+/// belongs to the runtime: register it with the reactor, never close it.
+#[no_mangle]
+pub extern "C" fn tau_rt_ctrl_c_fd() -> i32 {
+    match signal::ctrl_c_fd() {
+        Ok(fd) => fd,
+        Err(err) => -err.raw_os_error().unwrap_or(libc::EIO),
+    }
+}
+
 // ── Timers ──────────────────────────────────────────────────────────
 
 /// Create a timer. Deadline is nanoseconds from now. Returns opaque handle.
@@ -62,6 +146,25 @@ pub extern "C" fn tau_rt_timer_cancel(handle: u64) {
     reactor::get().timer_cancel(handle);
 }
 
+/// Monotonic nanoseconds since the runtime started, on the clock timers use.
+#[no_mangle]
+pub extern "C" fn tau_rt_now_nanos() -> u64 {
+    reactor::get().now_nanos()
+}
+
+/// Nanoseconds the runtime clock is ahead of the real monotonic clock
+/// (negative if behind). Zero unless a virtual clock is active.
+#[no_mangle]
+pub extern "C" fn tau_rt_clock_offset_nanos() -> i64 {
+    reactor::get().clock_offset_nanos()
+}
+
+/// Set the shortest sleep a pending timer can make the reactor take.
+#[no_mangle]
+pub extern "C" fn tau_rt_set_min_sleep(nanos: u64) {
+    reactor::get().set_min_sleep(std::time::Duration::from_nanos(nanos));
+}
+
 /// Poll a timer. Returns 0=Pending, 1=Ready.
 #[no_mangle]
 pub extern "C" fn tau_rt_timer_poll(handle: u64, cx: *mut FfiContext<'_>) -> u8 {
@@ -83,6 +186,12 @@ pub extern "C" fn tau_rt_spawn(future: FfiFuture<()>) {
     executor::get().spawn(future);
 }
 
+/// Spawn a `!Send` future that only runs on the calling thread.
+#[no_mangle]
+pub extern "C" fn tau_rt_spawn_local(future: LocalFfiFuture<()>) {
+    executor::get().spawn_local(future);
+}
+
 /// Poll one ready task. Returns 0=no work, 1=did work.
 #[no_mangle]
 pub extern "C" fn tau_rt_try_tick() -> u8 {
@@ -93,9 +202,17 @@ pub extern "C" fn tau_rt_try_tick() -> u8 {
     }
 }
 
+/// Poll up to `max` ready tasks. Returns how many were polled; fewer than
+/// `max` means the queue ran dry.
+#[no_mangle]
+pub extern "C" fn tau_rt_try_tick_n(max: u64) -> u64 {
+    executor::get().try_tick_n(max)
+}
+
 /// Run the reactor once (process IO + timers, wake tasks).
 /// timeout_ms: milliseconds to wait. 0 = non-blocking.
-/// Returns 0=ok, -1=error.
+/// Returns the number of timer/IO events handled (>= 0), or the negated OS
+/// error code of the failing poller wait.
 #[no_mangle]
 pub extern "C" fn tau_rt_react(timeout_ms: u64) -> i32 {
     let timeout = if timeout_ms == 0 {
@@ -104,14 +221,128 @@ pub extern "C" fn tau_rt_react(timeout_ms: u64) -> i32 {
         Some(std::time::Duration::from_millis(timeout_ms))
     };
     match reactor::get().react(timeout) {
-        Ok(()) => 0,
-        Err(_) => -1,
+        Ok(events) => events.min(i32::MAX as usize) as i32,
+        Err(err) => -err.raw_os_error().unwrap_or(libc::EIO),
+    }
+}
+
+/// Park in the reactor until IO, a timer or a cross-thread wakeup (a waker
+/// or spawn from another thread) arrives, or `timeout_nanos` passes
+/// (`u64::MAX` = no timeout). Returns like `tau_rt_react`.
+#[no_mangle]
+pub extern "C" fn tau_rt_park(timeout_nanos: u64) -> i32 {
+    let timeout =
+        (timeout_nanos != u64::MAX).then(|| std::time::Duration::from_nanos(timeout_nanos));
+    match reactor::get().react(timeout) {
+        Ok(events) => events.min(i32::MAX as usize) as i32,
+        Err(err) => -err.raw_os_error().unwrap_or(libc::EIO),
+    }
+}
+
+/// `tau_rt_block_on` result: the watchdog abandoned the future.
+const BLOCK_ON_ABANDONED: u8 = 0;
+/// `tau_rt_block_on` result: the future completed.
+const BLOCK_ON_COMPLETED: u8 = 1;
+/// `tau_rt_block_on` result: called from inside a task the runtime is
+/// polling; the future was dropped unpolled.
+const BLOCK_ON_NESTED: u8 = 2;
+
+/// Run `block_on` on `future` and encode the outcome as a `BLOCK_ON_*` code.
+fn block_on_code(future: FfiFuture<()>, block_on: impl FnOnce(FfiFuture<()>) -> bool) -> u8 {
+    // Refuse rather than panic: a panic must not unwind across the C ABI.
+    if executor::in_task() {
+        rt_warn!("block_on called from inside a task; future dropped");
+        return BLOCK_ON_NESTED;
+    }
+    if block_on(future) {
+        BLOCK_ON_COMPLETED
+    } else {
+        BLOCK_ON_ABANDONED
     }
 }
 
 /// Block the current thread until the future completes.
 /// Drives both reactor and executor internally.
+/// Returns 1 once the future completes, 0 if the watchdog abandoned it, or 2
+/// (dropping the future unpolled) if called from inside a task the runtime
+/// is polling.
 #[no_mangle]
-pub extern "C" fn tau_rt_block_on(future: FfiFuture<()>) {
-    executor::get().block_on(future);
+pub extern "C" fn tau_rt_block_on(future: FfiFuture<()>) -> u8 {
+    block_on_code(future, |future| executor::get().block_on(future))
+}
+
+/// `tau_rt_block_on`, tuning how it waits while no task is ready: up to
+/// `max_spin_iters` non-blocking reactor polls, then blocking reactor waits
+/// of at most `idle_timeout_nanos` each (`u64::MAX` = until an event).
+#[no_mangle]
+pub extern "C" fn tau_rt_block_on_cfg(
+    future: FfiFuture<()>,
+    max_spin_iters: u32,
+    idle_timeout_nanos: u64,
+) -> u8 {
+    let config = executor::BlockOnConfig {
+        max_spin_iters,
+        idle_timeout: (idle_timeout_nanos != u64::MAX)
+            .then(|| std::time::Duration::from_nanos(idle_timeout_nanos)),
+    };
+    block_on_code(future, |future| executor::get().block_on_with(future, config))
+}
+
+/// Configure the `block_on` watchdog: `limit` back-to-back polls of one task
+/// without IO, timer or outside-wake progress trip it (0, the default,
+/// disables); `abandon` != 0 makes `block_on` give up on its future when it
+/// trips.
+#[no_mangle]
+pub extern "C" fn tau_rt_set_watchdog(limit: u64, abandon: u8) {
+    executor::get().set_watchdog(limit, abandon != 0);
+}
+
+/// Stop accepting spawns and drain spawned tasks for up to `timeout_ms`.
+/// Returns the number of tasks still pending.
+#[no_mangle]
+pub extern "C" fn tau_rt_shutdown(timeout_ms: u64) -> u64 {
+    executor::get().shutdown(std::time::Duration::from_millis(timeout_ms))
+}
+
+// ── Metrics ─────────────────────────────────────────────────────────
+
+/// Snapshot of the runtime's monotonic counters.
+#[repr(C)]
+pub struct TauMetrics {
+    pub tasks_spawned: u64,
+    pub tasks_completed: u64,
+    pub tasks_polled: u64,
+    pub timers_fired: u64,
+    pub io_events: u64,
+    pub react_calls: u64,
+    pub watchdog_trips: u64,
+}
+
+/// Number of IO sources currently registered with the reactor.
+#[no_mangle]
+pub extern "C" fn tau_rt_io_count() -> u64 {
+    reactor::get().io_count() as u64
+}
+
+/// Number of timers currently live (created, not yet completed or cancelled).
+#[no_mangle]
+pub extern "C" fn tau_rt_timer_count() -> u64 {
+    reactor::get().timer_count() as u64
+}
+
+/// Write a snapshot of the runtime counters into `out`.
+#[no_mangle]
+pub extern "C" fn tau_rt_metrics(out: *mut TauMetrics) {
+    let executor = &executor::get().metrics;
+    let reactor = &reactor::get().metrics;
+    let snapshot = TauMetrics {
+        tasks_spawned: executor.tasks_spawned.load(Ordering::Relaxed),
+        tasks_completed: executor.tasks_completed.load(Ordering::Relaxed),
+        tasks_polled: executor.tasks_polled.load(Ordering::Relaxed),
+        timers_fired: reactor.timers_fired.load(Ordering::Relaxed),
+        io_events: reactor.io_events.load(Ordering::Relaxed),
+        react_calls: reactor.react_calls.load(Ordering::Relaxed),
+        watchdog_trips: executor.watchdog_trips.load(Ordering::Relaxed),
+    };
+    unsafe { out.write(snapshot) };
 }
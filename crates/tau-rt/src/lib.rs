@@ -7,6 +7,24 @@
 //! The reactor and executor are process-global singletons (behind OnceLock),
 //! shared by the host binary and all plugin cdylibs through dynamic linking.
 
+/// Emit a `tracing` event when the `trace` feature is enabled; expands to
+/// nothing otherwise, so instrumentation is zero-cost by default.
+macro_rules! rt_trace {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "trace")]
+        tracing::trace!($($arg)*);
+    }};
+}
+
+/// Like `rt_trace!`, at warning level.
+macro_rules! rt_warn {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "trace")]
+        tracing::warn!($($arg)*);
+    }};
+}
+
 mod executor;
 mod ffi;
 mod reactor;
+mod signal;
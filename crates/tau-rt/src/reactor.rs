@@ -1,19 +1,46 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Mutex, OnceLock};
-use std::task::{Poll, Waker};
+use std::task::{Poll, RawWakerVTable, Waker};
 use std::time::{Duration, Instant};
 
 use polling::{Event, Events, Poller};
 use slab::Slab;
 
-/// An IO source registered with the reactor.
+/// Default floor for timer-driven sleeps; see `Reactor::set_min_sleep()`.
+const DEFAULT_MIN_SLEEP: Duration = Duration::from_millis(1);
+
+
+/// An IO source registered with the OS poller: one per fd (or watched
+/// process), shared by every registration of it.
 pub(crate) struct Source {
     raw_fd: i32,
     key: usize,
+    /// Keys of the registrations waiting through this source.
+    registrations: Vec<usize>,
     /// Whether we've called poller.add() for this source.
     registered: bool,
+    /// Raw OS error from a failed poller add/modify, or `EBADF` once the fd
+    /// is found closed. Sticky: every later poll reports it instead of
+    /// waiting for readiness that can't arrive.
+    error: Option<i32>,
+    /// The reactor opened `raw_fd` itself (a pidfd) and closes it on deregister.
+    owns_fd: bool,
+    /// Process watched via a kqueue `EVFILT_PROC` filter instead of an fd.
+    #[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+    process: Option<std::num::NonZeroI32>,
+}
+
+/// One `io_register()` (or `pidfd_create()`) call: its owner's wakers and
+/// readiness, so tasks waiting on the same fd through separate
+/// registrations never replace each other's wakers.
+struct Registration {
+    /// Key of the source this registration waits through.
+    source: usize,
+    /// Tells this registration apart from earlier ones that had the same
+    /// slab key; part of its handle.
+    generation: u32,
     /// Waker to fire when readable.
     read_waker: Option<Waker>,
     /// Waker to fire when writable.
@@ -24,6 +51,86 @@ pub(crate) struct Source {
     write_ready: bool,
 }
 
+/// Sources and the registrations on them, under one lock.
+struct IoState {
+    sources: Slab<Source>,
+    /// Keyed by IO handle.
+    registrations: Slab<Registration>,
+    /// Source key for each fd registered with `io_register()`. Process
+    /// watches have sources of their own and aren't listed.
+    fd_keys: HashMap<i32, usize>,
+    /// Generation of the next registration.
+    next_generation: u32,
+}
+
+impl IoState {
+    /// The registration key behind `handle`, if it's still registered. A
+    /// stale handle whose slab key has since been reused carries an older
+    /// generation and doesn't match.
+    fn lookup(&self, handle: u64) -> Option<usize> {
+        let key = (handle & u64::from(u32::MAX)) as usize;
+        let generation = (handle >> 32) as u32;
+        let registration = self.registrations.get(key)?;
+        (registration.generation == generation).then_some(key)
+    }
+
+    /// Add a source for `raw_fd` with no registrations yet.
+    fn insert_source(&mut self, raw_fd: i32, owns_fd: bool) -> usize {
+        let entry = self.sources.vacant_entry();
+        let key = entry.key();
+        entry.insert(Source {
+            raw_fd,
+            key,
+            registrations: Vec::new(),
+            registered: false,
+            error: None,
+            owns_fd,
+            #[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+            process: None,
+        });
+        key
+    }
+
+    /// Add a registration on `source`. Returns its handle: the slab key in
+    /// the low 32 bits and the generation above it. Generations wrap at 31
+    /// bits, so handles stay non-negative as `i64`.
+    fn register(&mut self, source: usize) -> u64 {
+        let generation = self.next_generation;
+        self.next_generation = (generation + 1) & (i32::MAX as u32);
+        let key = self.registrations.insert(Registration {
+            source,
+            generation,
+            read_waker: None,
+            write_waker: None,
+            read_ready: false,
+            write_ready: false,
+        });
+        self.sources[source].registrations.push(key);
+        (u64::from(generation) << 32) | key as u64
+    }
+
+    /// Which directions any registration on `source` is waiting for.
+    fn wanted(&self, source: usize) -> (bool, bool) {
+        let registrations = self.sources[source].registrations.iter();
+        registrations.fold((false, false), |(read, write), &key| {
+            let registration = &self.registrations[key];
+            (
+                read || registration.read_waker.is_some(),
+                write || registration.write_waker.is_some(),
+            )
+        })
+    }
+
+    /// Take the wakers of every registration on `source`.
+    fn take_wakers(&mut self, source: usize, wakers: &mut Vec<Waker>) {
+        for &key in &self.sources[source].registrations {
+            let registration = &mut self.registrations[key];
+            wakers.extend(registration.read_waker.take());
+            wakers.extend(registration.write_waker.take());
+        }
+    }
+}
+
 /// Timer state: BTreeMap for ordered expiry iteration, HashMap for handle→deadline lookup.
 struct TimerState {
     /// Timers ordered by (deadline, id) for efficient expiry scanning.
@@ -32,128 +139,496 @@ struct TimerState {
     deadlines: HashMap<u64, Instant>,
 }
 
+/// Where the reactor reads "now" from when computing timer deadlines.
+#[cfg(test)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ClockSource {
+    /// `Instant::now()` — the default.
+    Real,
+    /// A virtual clock that only moves via `Reactor::advance()`.
+    Manual,
+}
+
+/// Monotonic reactor counters, updated with `Relaxed` ordering.
+#[derive(Default)]
+pub(crate) struct ReactorMetrics {
+    pub(crate) timers_fired: AtomicU64,
+    pub(crate) io_events: AtomicU64,
+    pub(crate) react_calls: AtomicU64,
+}
+
 /// The global reactor: owns the OS poller, IO sources, and timer heap.
 pub(crate) struct Reactor {
     poller: Poller,
-    sources: Mutex<Slab<Source>>,
+    io: Mutex<IoState>,
     timers: Mutex<TimerState>,
     timer_id: AtomicU64,
     events: Mutex<Events>,
+    /// Virtual "now" when the manual clock is active; `None` = real clock.
+    /// Only tests can switch it on, so other builds don't carry it.
+    #[cfg(test)]
+    manual_now: Mutex<Option<Instant>>,
+    /// When the reactor was created; the origin for `now_nanos()`.
+    start: Instant,
+    pub(crate) metrics: ReactorMetrics,
+    /// True while a thread is blocked in `poller.wait()`.
+    parked: AtomicBool,
+    /// Set by `wake_up()`; makes the next `react()` non-blocking so a wake
+    /// that races with parking is never lost.
+    wake_pending: AtomicBool,
+    /// Shortest sleep a pending timer can request, in nanoseconds.
+    min_sleep_nanos: AtomicU64,
+    /// When `react()` next checks waited-on fds for having been closed.
+    fd_check_due: Mutex<Instant>,
+    /// How often `react()` checks waited-on fds for having been closed, in
+    /// nanoseconds; 0 (the default) never checks.
+    fd_check_nanos: AtomicU64,
 }
 
 static REACTOR: OnceLock<Reactor> = OnceLock::new();
 
 pub(crate) fn get() -> &'static Reactor {
-    REACTOR.get_or_init(|| Reactor {
-        poller: Poller::new().expect("failed to create OS poller"),
-        sources: Mutex::new(Slab::new()),
-        timers: Mutex::new(TimerState {
-            heap: BTreeMap::new(),
-            deadlines: HashMap::new(),
-        }),
-        timer_id: AtomicU64::new(0),
-        events: Mutex::new(Events::new()),
-    })
+    REACTOR.get_or_init(Reactor::new)
 }
 
 impl Reactor {
+    fn new() -> Self {
+        Reactor {
+            poller: Poller::new().expect("failed to create OS poller"),
+            io: Mutex::new(IoState {
+                sources: Slab::new(),
+                registrations: Slab::new(),
+                fd_keys: HashMap::new(),
+                next_generation: 0,
+            }),
+            timers: Mutex::new(TimerState {
+                heap: BTreeMap::new(),
+                deadlines: HashMap::new(),
+            }),
+            timer_id: AtomicU64::new(0),
+            events: Mutex::new(Events::new()),
+            #[cfg(test)]
+            manual_now: Mutex::new(None),
+            start: Instant::now(),
+            metrics: ReactorMetrics::default(),
+            parked: AtomicBool::new(false),
+            wake_pending: AtomicBool::new(false),
+            min_sleep_nanos: AtomicU64::new(DEFAULT_MIN_SLEEP.as_nanos() as u64),
+            fd_check_due: Mutex::new(Instant::now()),
+            fd_check_nanos: AtomicU64::new(0),
+        }
+    }
+
+    // ── Clock ───────────────────────────────────────────────────────
+
+    /// Current time according to the active clock source.
+    fn now(&self) -> Instant {
+        self.manual_time().unwrap_or_else(Instant::now)
+    }
+
+    /// The manual clock's time, if it's active.
+    #[cfg(test)]
+    fn manual_time(&self) -> Option<Instant> {
+        *self.manual_now.lock().unwrap()
+    }
+
+    /// Outside tests the manual clock doesn't exist; reading the time takes
+    /// no lock.
+    #[cfg(not(test))]
+    fn manual_time(&self) -> Option<Instant> {
+        None
+    }
+
+    /// Nanoseconds from reactor creation to `now()`.
+    pub(crate) fn now_nanos(&self) -> u64 {
+        let elapsed = self.now().saturating_duration_since(self.start);
+        elapsed.as_nanos().min(u64::MAX as u128) as u64
+    }
+
+    /// How far `now()` is ahead of the real monotonic clock, in nanoseconds.
+    /// Zero on the real clock; negative when a manual clock lags behind.
+    pub(crate) fn clock_offset_nanos(&self) -> i64 {
+        let Some(manual) = self.manual_time() else {
+            return 0;
+        };
+        let real = Instant::now();
+        let nanos = |d: Duration| d.as_nanos().min(i64::MAX as u128) as i64;
+        if manual >= real {
+            nanos(manual - real)
+        } else {
+            -nanos(real - manual)
+        }
+    }
+
+    /// Switch between the real and manual clock. Switching to manual freezes
+    /// time at the current instant.
+    #[cfg(test)]
+    pub(crate) fn set_clock_source(&self, source: ClockSource) {
+        *self.manual_now.lock().unwrap() = match source {
+            ClockSource::Real => None,
+            ClockSource::Manual => Some(Instant::now()),
+        };
+    }
+
+    /// Move the manual clock forward and wake every timer that is now due.
+    /// No-op on the real clock.
+    #[cfg(test)]
+    pub(crate) fn advance(&self, by: Duration) {
+        let now = {
+            let mut manual_now = self.manual_now.lock().unwrap();
+            match manual_now.as_mut() {
+                Some(now) => {
+                    *now += by;
+                    *now
+                }
+                None => return,
+            }
+        };
+        let mut wakers = Vec::new();
+        self.fire_expired_timers(now, &mut wakers);
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
     // ── IO ──────────────────────────────────────────────────────────
 
-    /// Register a file descriptor. Returns an opaque handle (slab key).
+    /// Register a file descriptor. Returns an opaque handle.
     /// The fd is NOT added to the OS poller yet — that happens on first poll.
+    ///
+    /// An fd has at most one source — the OS poller accepts each fd once.
+    /// Registering an fd that is already registered adds a registration to
+    /// the same source, which lives until every registration is
+    /// deregistered. Each registration (handle) keeps its own wakers and
+    /// readiness, so any number of tasks can wait on one fd — a reader and
+    /// a writer, or several readers — without replacing each other's wakers.
+    /// Readiness is reported to every registration; a reader that loses the
+    /// race for the data sees `WouldBlock` and waits again.
     pub(crate) fn io_register(&self, fd: i32) -> u64 {
-        let mut sources = self.sources.lock().unwrap();
-        let entry = sources.vacant_entry();
-        let key = entry.key();
-        entry.insert(Source {
-            raw_fd: fd,
-            key,
-            registered: false,
-            read_waker: None,
-            write_waker: None,
-            read_ready: false,
-            write_ready: false,
-        });
-        key as u64
+        let mut io = self.io.lock().unwrap();
+        let source = match io.fd_keys.get(&fd) {
+            Some(&source) => source,
+            None => {
+                let source = io.insert_source(fd, false);
+                io.fd_keys.insert(fd, source);
+                source
+            }
+        };
+        let handle = io.register(source);
+        rt_trace!(
+            key = source,
+            fd,
+            registrations = io.sources[source].registrations.len(),
+            "io registered"
+        );
+        handle
+    }
+
+    /// Register a source that becomes readable when process `pid` exits.
+    ///
+    /// Linux: a pidfd from `pidfd_open`, owned and closed by the reactor.
+    /// macOS/iOS/FreeBSD: a kqueue `EVFILT_PROC` filter on the pid.
+    /// Poll and deregister it like any other IO source. Every watch gets a
+    /// source of its own, never shared with `io_register()`.
+    pub(crate) fn pidfd_create(&self, pid: i32) -> io::Result<u64> {
+        #[cfg(target_os = "linux")]
+        {
+            let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) };
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let mut io = self.io.lock().unwrap();
+            let source = io.insert_source(fd as i32, true);
+            rt_trace!(key = source, pid, "pidfd opened");
+            Ok(io.register(source))
+        }
+        #[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+        {
+            let pid = std::num::NonZeroI32::new(pid)
+                .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?;
+            let mut io = self.io.lock().unwrap();
+            let source = io.insert_source(-1, false);
+            io.sources[source].process = Some(pid);
+            rt_trace!(key = source, pid, "process watch registered");
+            Ok(io.register(source))
+        }
+        #[cfg(not(any(
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "freebsd"
+        )))]
+        {
+            let _ = pid;
+            Err(io::Error::from(io::ErrorKind::Unsupported))
+        }
     }
 
-    /// Deregister an IO source. Removes from OS poller if registered.
+    /// Deregister an IO handle. Once the last registration of a source
+    /// goes, the source is removed from the OS poller.
+    ///
+    /// Any waker still stored on the registration is woken (after the lock
+    /// is released): a readiness event `react()` already collected for it
+    /// would otherwise be dropped along with the registration, stranding the
+    /// waiter. Polling the handle afterwards reports `EBADF`.
     pub(crate) fn io_deregister(&self, handle: u64) {
-        let mut sources = self.sources.lock().unwrap();
-        let key = handle as usize;
-        if sources.contains(key) {
-            let source = sources.remove(key);
+        let mut io = self.io.lock().unwrap();
+        let Some(key) = io.lookup(handle) else {
+            return;
+        };
+        let registration = io.registrations.remove(key);
+        let stranded = [registration.read_waker, registration.write_waker];
+        let source_key = registration.source;
+        let source = &mut io.sources[source_key];
+        source.registrations.retain(|&other| other != key);
+        if source.registrations.is_empty() {
+            let source = io.sources.remove(source_key);
+            if io.fd_keys.get(&source.raw_fd) == Some(&source_key) {
+                io.fd_keys.remove(&source.raw_fd);
+            }
+            rt_trace!(key = source_key, fd = source.raw_fd, "io deregistered");
+            self.release_source(source);
+        } else {
+            rt_trace!(key = source_key, fd = source.raw_fd, "io registration dropped");
+        }
+        drop(io);
+        for waker in stranded.into_iter().flatten() {
+            waker.wake();
+        }
+    }
+
+    /// Remove a deregistered source from the OS poller and close its fd if
+    /// the reactor owns it.
+    fn release_source(&self, source: Source) {
+        #[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+        if let Some(pid) = source.process {
+            use polling::os::kqueue::{PollerKqueueExt, Process, ProcessOps};
             if source.registered {
-                let borrowed = unsafe { std::os::fd::BorrowedFd::borrow_raw(source.raw_fd) };
-                // Ignore errors — fd may already be closed by caller.
-                let _ = self.poller.delete(&borrowed);
+                let filter = unsafe { Process::from_pid(pid, ProcessOps::Exit) };
+                let _ = self.poller.delete_filter(filter);
             }
+            return;
+        }
+        if source.registered {
+            let borrowed = unsafe { std::os::fd::BorrowedFd::borrow_raw(source.raw_fd) };
+            // Ignore errors — fd may already be closed by caller.
+            let _ = self.poller.delete(borrowed);
+        }
+        if source.owns_fd {
+            unsafe { libc::close(source.raw_fd) };
         }
     }
 
     /// Poll for readability. Stores waker and registers interest.
     /// Returns Ready if already known readable, Pending otherwise.
-    pub(crate) fn io_poll_readable(&self, handle: u64, waker: Waker) -> Poll<()> {
-        let mut sources = self.sources.lock().unwrap();
-        let key = handle as usize;
-        let source = &mut sources[key];
+    /// Returns Ready(Err) if registering interest failed.
+    pub(crate) fn io_poll_readable(&self, handle: u64, waker: Waker) -> Poll<io::Result<()>> {
+        self.io_poll_ready(handle, true, false, waker).map_ok(|_| ())
+    }
 
-        if source.read_ready {
-            source.read_ready = false;
-            return Poll::Ready(());
+    /// Poll for writability. Stores waker and registers interest.
+    /// Returns Ready(Err) if registering interest failed.
+    pub(crate) fn io_poll_writable(&self, handle: u64, waker: Waker) -> Poll<io::Result<()>> {
+        self.io_poll_ready(handle, false, true, waker).map_ok(|_| ())
+    }
+
+    /// Poll for either direction at once. Ready with `(readable, writable)`
+    /// as soon as any requested direction is, consuming the readiness it
+    /// reports; otherwise stores the waker for every requested direction.
+    /// Returns Ready(Err) if registering interest failed.
+    pub(crate) fn io_poll_ready(
+        &self,
+        handle: u64,
+        read: bool,
+        write: bool,
+        waker: Waker,
+    ) -> Poll<io::Result<(bool, bool)>> {
+        let mut io = self.io.lock().unwrap();
+        let Some(key) = io.lookup(handle) else {
+            return Poll::Ready(Err(io::Error::from_raw_os_error(libc::EBADF)));
+        };
+        let source = io.registrations[key].source;
+
+        if let Some(errno) = io.sources[source].error {
+            return Poll::Ready(Err(io::Error::from_raw_os_error(errno)));
         }
 
-        source.read_waker = Some(waker);
-        self.update_interest(source);
-        Poll::Pending
+        let registration = &mut io.registrations[key];
+        let readable = read && registration.read_ready;
+        let writable = write && registration.write_ready;
+        if readable || writable {
+            registration.read_ready &= !readable;
+            registration.write_ready &= !writable;
+            return Poll::Ready(Ok((readable, writable)));
+        }
+
+        if read {
+            registration.read_waker = Some(waker.clone());
+        }
+        if write {
+            registration.write_waker = Some(waker);
+        }
+        self.register_interest(io, source).map_ok(|()| (false, false))
     }
 
-    /// Poll for writability. Stores waker and registers interest.
-    pub(crate) fn io_poll_writable(&self, handle: u64, waker: Waker) -> Poll<()> {
-        let mut sources = self.sources.lock().unwrap();
-        let key = handle as usize;
-        let source = &mut sources[key];
+    /// Stop waiting for the given directions: drop their stored wakers
+    /// (without waking them) and their pending readiness, and re-arm the
+    /// poller for whatever direction is still wanted. Lets one half of a
+    /// split fd go away without disturbing the other.
+    pub(crate) fn io_clear_interest(&self, handle: u64, read: bool, write: bool) -> io::Result<()> {
+        let mut io = self.io.lock().unwrap();
+        let Some(key) = io.lookup(handle) else {
+            return Err(io::Error::from_raw_os_error(libc::EBADF));
+        };
+        let registration = &mut io.registrations[key];
 
-        if source.write_ready {
-            source.write_ready = false;
-            return Poll::Ready(());
+        let mut dropped = Vec::new();
+        if read {
+            dropped.extend(registration.read_waker.take());
+            registration.read_ready = false;
+        }
+        if write {
+            dropped.extend(registration.write_waker.take());
+            registration.write_ready = false;
         }
+        let source_key = registration.source;
+        let (want_read, want_write) = io.wanted(source_key);
+        let source = &mut io.sources[source_key];
+        let result = if source.registered && source.error.is_none() {
+            self.update_interest(source, want_read, want_write)
+        } else {
+            Ok(())
+        };
+        rt_trace!(key = source_key, read, write, "io interest cleared");
+        drop(io);
+        // Dropping a waker can run arbitrary code; do it without the lock.
+        drop(dropped);
+        result
+    }
 
-        source.write_waker = Some(waker);
-        self.update_interest(source);
-        Poll::Pending
+    /// The raw OS error recorded for a handle's source, or 0 if none.
+    pub(crate) fn io_last_error(&self, handle: u64) -> i32 {
+        let io = self.io.lock().unwrap();
+        io.lookup(handle)
+            .and_then(|key| io.sources[io.registrations[key].source].error)
+            .unwrap_or(0)
     }
 
-    /// Sync OS poller interest with current waker state.
-    fn update_interest(&self, source: &mut Source) {
-        let interest = Event::new(
-            source.key,
-            source.read_waker.is_some(),
-            source.write_waker.is_some(),
-        );
+    /// Update poller interest after a waker was stored. On failure, records
+    /// the error on the source and wakes every registration on it (after
+    /// releasing the lock) so each waiter observes it instead of hanging.
+    fn register_interest(
+        &self,
+        mut io: std::sync::MutexGuard<'_, IoState>,
+        key: usize,
+    ) -> Poll<io::Result<()>> {
+        let (read, write) = io.wanted(key);
+        let source = &mut io.sources[key];
+        match self.update_interest(source, read, write) {
+            Ok(()) => Poll::Pending,
+            Err(err) => {
+                rt_trace!(key, fd = source.raw_fd, %err, "io registration failed");
+                source.error = Some(err.raw_os_error().unwrap_or(0));
+                let mut stale = Vec::new();
+                io.take_wakers(key, &mut stale);
+                drop(io);
+                for waker in stale {
+                    waker.wake();
+                }
+                Poll::Ready(Err(err))
+            }
+        }
+    }
+
+    /// Arm the OS poller for the directions some registration waits for.
+    fn update_interest(&self, source: &mut Source, read: bool, write: bool) -> io::Result<()> {
+        #[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+        if let Some(pid) = source.process {
+            use polling::os::kqueue::{PollerKqueueExt, Process, ProcessOps};
+            // Process exit is reported as readable; there's no write side.
+            let _ = (read, write);
+            let filter = unsafe { Process::from_pid(pid, ProcessOps::Exit) };
+            let mode = polling::PollMode::Oneshot;
+            if source.registered {
+                return self.poller.modify_filter(filter, source.key, mode);
+            }
+            self.poller.add_filter(filter, source.key, mode)?;
+            source.registered = true;
+            return Ok(());
+        }
+
+        let interest = Event::new(source.key, read, write);
 
         if source.registered {
             let borrowed = unsafe { std::os::fd::BorrowedFd::borrow_raw(source.raw_fd) };
             // modify() re-arms oneshot interest.
-            let _ = self.poller.modify(&borrowed, interest);
+            self.poller.modify(borrowed, interest)
         } else {
             // First registration — add() is unsafe because we must delete before fd close.
-            unsafe {
-                let _ = self.poller.add(source.raw_fd, interest);
-            }
+            unsafe { self.poller.add(source.raw_fd, interest)? };
             source.registered = true;
+            Ok(())
+        }
+    }
+
+    /// Check fds with waiting tasks for having been closed every `interval`,
+    /// recording `EBADF` on a closed one and waking its waiters. While any
+    /// IO source exists this caps each `react()` wait at `interval`, and
+    /// each check costs a syscall per waited-on fd. `None` (the default)
+    /// turns the check off.
+    ///
+    /// A closed fd whose number has already been reused by a new open file
+    /// passes the check; its waiters only see the error once their interest
+    /// is re-armed.
+    pub(crate) fn set_fd_check_interval(&self, interval: Option<Duration>) {
+        let nanos = interval.map_or(0, |i| i.as_nanos().clamp(1, u64::MAX as u128) as u64);
+        self.fd_check_nanos.store(nanos, Ordering::Relaxed);
+        *self.fd_check_due.lock().unwrap() = Instant::now() + interval.unwrap_or_default();
+    }
+
+    /// The interval set by `set_fd_check_interval()`.
+    fn fd_check_interval(&self) -> Option<Duration> {
+        let nanos = self.fd_check_nanos.load(Ordering::Relaxed);
+        (nanos != 0).then(|| Duration::from_nanos(nanos))
+    }
+
+    // ── Cross-thread wakeup ─────────────────────────────────────────
+
+    /// Interrupt a thread blocked in `react()`, or make the next `react()`
+    /// return immediately if none is blocked yet.
+    ///
+    /// Uses the poller's built-in notification fd (eventfd on Linux, a pipe
+    /// or user event elsewhere), so it is safe to call from any thread. The
+    /// syscall is only made when a thread is actually parked.
+    pub(crate) fn wake_up(&self) {
+        self.wake_pending.store(true, Ordering::SeqCst);
+        if self.parked.load(Ordering::SeqCst) {
+            let _ = self.poller.notify();
         }
     }
 
     // ── Timers ──────────────────────────────────────────────────────
 
+    /// Set the shortest sleep a pending timer can make `react()` take.
+    ///
+    /// A timer due sooner than this rounds its wait up to it rather than
+    /// spinning on near-zero poller timeouts (which the OS truncates to
+    /// zero) until the deadline arrives. Timeouts passed to `react()` by the
+    /// caller are never rounded. Defaults to 1ms; `Duration::ZERO` disables
+    /// the floor.
+    pub(crate) fn set_min_sleep(&self, floor: Duration) {
+        let nanos = floor.as_nanos().min(u64::MAX as u128) as u64;
+        self.min_sleep_nanos.store(nanos, Ordering::Relaxed);
+    }
+
     /// Create a timer that fires `nanos_from_now` nanoseconds from now.
     /// Returns an opaque timer handle.
     pub(crate) fn timer_create(&self, nanos_from_now: u64) -> u64 {
         let id = self.timer_id.fetch_add(1, Ordering::Relaxed);
-        let deadline = Instant::now() + Duration::from_nanos(nanos_from_now);
+        let deadline = self.now() + Duration::from_nanos(nanos_from_now);
         let mut state = self.timers.lock().unwrap();
         state.deadlines.insert(id, deadline);
+        rt_trace!(timer = id, ?deadline, "timer created");
         // Waker is stored on first timer_poll, not here.
         id
     }
@@ -163,6 +638,7 @@ impl Reactor {
         let mut state = self.timers.lock().unwrap();
         if let Some(deadline) = state.deadlines.remove(&id) {
             state.heap.remove(&(deadline, id));
+            rt_trace!(timer = id, ?deadline, "timer cancelled");
         }
     }
 
@@ -174,9 +650,11 @@ impl Reactor {
             None => return Poll::Ready(()), // Already fired or cancelled.
         };
 
-        if Instant::now() >= deadline {
+        if self.now() >= deadline {
             state.deadlines.remove(&id);
             state.heap.remove(&(deadline, id));
+            self.metrics.timers_fired.fetch_add(1, Ordering::Relaxed);
+            rt_trace!(timer = id, ?deadline, "timer fired");
             return Poll::Ready(());
         }
 
@@ -185,77 +663,215 @@ impl Reactor {
         Poll::Pending
     }
 
+    // ── Diagnostics ─────────────────────────────────────────────────
+
+    /// Number of live IO sources. An fd registered several times counts once.
+    pub(crate) fn io_count(&self) -> usize {
+        self.io.lock().unwrap().sources.len()
+    }
+
+    /// Number of timers created and not yet fired-and-polled or cancelled.
+    pub(crate) fn timer_count(&self) -> usize {
+        self.timers.lock().unwrap().deadlines.len()
+    }
+
     // ── React (drives IO + timers) ─────────────────────────────────
 
     /// Process expired timers, poll OS for IO events, wake ready tasks.
-    pub(crate) fn react(&self, timeout: Option<Duration>) -> io::Result<()> {
+    ///
+    /// Returns the number of events handled: fired timers plus IO readiness
+    /// events. `0` means nothing was ready within `timeout`.
+    ///
+    /// An fd closed while still registered silently drops out of the OS
+    /// poller, so no readiness would ever arrive for it. The next poll that
+    /// re-arms it fails (`EBADF`, or `ENOENT` once the number is reused)
+    /// and that error is reported like any failed registration, but a task
+    /// already waiting never re-arms. See `set_fd_check_interval()` for a
+    /// periodic check that wakes such waiters.
+    pub(crate) fn react(&self, timeout: Option<Duration>) -> io::Result<usize> {
+        self.react_inner(timeout, false)
+    }
+
+    /// Like `react()`, but after the first wait keeps polling without
+    /// blocking until the OS reports nothing more, so a single call catches
+    /// up on an IO backlog larger than one batch.
+    pub(crate) fn react_all(&self, timeout: Option<Duration>) -> io::Result<usize> {
+        self.react_inner(timeout, true)
+    }
+
+    fn react_inner(&self, timeout: Option<Duration>, drain: bool) -> io::Result<usize> {
+        self.metrics.react_calls.fetch_add(1, Ordering::Relaxed);
         let mut wakers = Vec::new();
 
-        // 1. Process expired timers.
-        let next_timer = {
-            let now = Instant::now();
-            let mut state = self.timers.lock().unwrap();
-            loop {
-                match state.heap.keys().next().copied() {
-                    Some((deadline, id)) if deadline <= now => {
-                        let waker = state.heap.remove(&(deadline, id)).unwrap();
-                        state.deadlines.remove(&id);
-                        wakers.push(waker);
-                    }
-                    Some((deadline, _)) => break Some(deadline.duration_since(now)),
-                    None => break None,
-                }
-            }
-        };
+        // 1. Process expired timers. Virtual deadlines don't arrive by
+        // waiting, so only the real clock bounds the poll timeout.
+        let manual = self.manual_time().is_some();
+        let next_timer = self
+            .fire_expired_timers(self.now(), &mut wakers)
+            .filter(|_| !manual);
 
-        // 2. Compute effective timeout: min(caller, next_timer).
+        // 2. Compute effective timeout: min(caller, next_timer), with the
+        // timer's share floored at `min_sleep`. If a timer just fired its
+        // task is ready to run, so don't sleep at all.
+        let min_sleep = Duration::from_nanos(self.min_sleep_nanos.load(Ordering::Relaxed));
+        let next_timer = next_timer.map(|t| t.max(min_sleep));
         let effective_timeout = match (timeout, next_timer) {
+            _ if !wakers.is_empty() => Some(Duration::ZERO),
             (None, None) => None,
             (Some(t), None) | (None, Some(t)) => Some(t),
             (Some(a), Some(b)) => Some(a.min(b)),
         };
+        let fd_check = self.fd_check_interval();
+        let fd_check_due = *self.fd_check_due.lock().unwrap();
+        let effective_timeout = if fd_check.is_some() && self.io_count() > 0 {
+            let until_check = fd_check_due.saturating_duration_since(Instant::now());
+            Some(effective_timeout.map_or(until_check, |t| t.min(until_check)))
+        } else {
+            effective_timeout
+        };
 
-        // 3. Poll OS for IO events.
+        // 3. Poll OS for IO events. Publish `parked` before checking
+        // `wake_pending` — paired with the reverse order in `wake_up()`, one
+        // side always sees the other.
         let event_list: Vec<(usize, bool, bool)> = {
             let mut events = self.events.lock().unwrap();
             events.clear();
-            self.poller.wait(&mut events, effective_timeout)?;
-            events
+            self.parked.store(true, Ordering::SeqCst);
+            let effective_timeout = if self.wake_pending.swap(false, Ordering::SeqCst) {
+                Some(Duration::ZERO)
+            } else {
+                effective_timeout
+            };
+            let result = self.poller.wait(&mut events, effective_timeout);
+            self.parked.store(false, Ordering::SeqCst);
+            result?;
+            let mut list: Vec<(usize, bool, bool)> = events
                 .iter()
                 .map(|ev| (ev.key, ev.readable, ev.writable))
-                .collect()
+                .collect();
+            // Oneshot interest means a drained source isn't reported again,
+            // so this stops once every ready source has been seen.
+            while drain && !events.is_empty() {
+                events.clear();
+                self.poller.wait(&mut events, Some(Duration::ZERO))?;
+                list.extend(events.iter().map(|ev| (ev.key, ev.readable, ev.writable)));
+            }
+            list
         };
 
+        // The wait may have slept through a deadline (it was rounded up to
+        // `min_sleep`); fire those timers now rather than on the next call.
+        if !manual && next_timer.is_some() {
+            self.fire_expired_timers(self.now(), &mut wakers);
+        }
+        let timers_fired = wakers.len();
+
         // 4. Process IO events — collect wakers.
+        let mut io_events = 0;
         {
-            let mut sources = self.sources.lock().unwrap();
+            let mut guard = self.io.lock().unwrap();
+            let io = &mut *guard;
             for (key, readable, writable) in event_list {
-                if let Some(source) = sources.get_mut(key) {
+                let Some(source) = io.sources.get(key) else {
+                    continue;
+                };
+                self.metrics.io_events.fetch_add(1, Ordering::Relaxed);
+                io_events += 1;
+                rt_trace!(key, fd = source.raw_fd, readable, writable, "io ready");
+                for &registration in &source.registrations {
+                    let registration = &mut io.registrations[registration];
                     if readable {
-                        source.read_ready = true;
-                        if let Some(waker) = source.read_waker.take() {
-                            wakers.push(waker);
-                        }
+                        registration.read_ready = true;
+                        wakers.extend(registration.read_waker.take());
                     }
                     if writable {
-                        source.write_ready = true;
-                        if let Some(waker) = source.write_waker.take() {
-                            wakers.push(waker);
-                        }
+                        registration.write_ready = true;
+                        wakers.extend(registration.write_waker.take());
                     }
                 }
             }
+            let now = Instant::now();
+            if let Some(interval) = fd_check.filter(|_| now >= fd_check_due) {
+                *self.fd_check_due.lock().unwrap() = now + interval;
+                io_events += self.fail_closed_sources(io, &mut wakers);
+            }
         }
 
-        // 5. Wake all — tasks re-enter the executor's ready queue.
-        for waker in wakers {
+        // 5. Wake all — tasks re-enter the executor's ready queue. A task
+        // waiting on several sources that fired together is woken once.
+        for waker in dedup_wakers(wakers) {
             waker.wake();
         }
 
-        Ok(())
+        Ok(timers_fired + io_events)
+    }
+
+    /// Record `EBADF` on every waited-on source whose fd has been closed,
+    /// collecting its wakers. Returns how many sources failed.
+    ///
+    /// An fd whose number was reused after it closed looks open here; its
+    /// waiters only find out when their interest is next re-armed.
+    fn fail_closed_sources(&self, io: &mut IoState, wakers: &mut Vec<Waker>) -> usize {
+        let closed: Vec<usize> = io
+            .sources
+            .iter()
+            .filter(|&(key, source)| {
+                let (read, write) = io.wanted(key);
+                // Process watches have no fd (`raw_fd` is -1).
+                (read || write)
+                    && source.raw_fd >= 0
+                    && source.error.is_none()
+                    && unsafe { libc::fcntl(source.raw_fd, libc::F_GETFD) } < 0
+                    && io::Error::last_os_error().raw_os_error() == Some(libc::EBADF)
+            })
+            .map(|(key, _)| key)
+            .collect();
+        for &key in &closed {
+            rt_trace!(key, fd = io.sources[key].raw_fd, "io source fd closed");
+            io.sources[key].error = Some(libc::EBADF);
+            io.take_wakers(key, wakers);
+        }
+        closed.len()
+    }
+
+    /// Remove every timer due at `now`, collecting its waker.
+    /// Returns the time until the next pending deadline, if any.
+    ///
+    /// The due timers are the front of `heap`, split off in one go: a batch
+    /// that all expire together (a burst released at the same instant) costs
+    /// a single split rather than a lookup and removal each.
+    fn fire_expired_timers(&self, now: Instant, wakers: &mut Vec<Waker>) -> Option<Duration> {
+        let mut state = self.timers.lock().unwrap();
+        // Ids are handed out from 0, so none reaches `u64::MAX`: every key
+        // below this one is due and everything from it on is pending.
+        let pending = state.heap.split_off(&(now, u64::MAX));
+        let expired = std::mem::replace(&mut state.heap, pending);
+        self.metrics
+            .timers_fired
+            .fetch_add(expired.len() as u64, Ordering::Relaxed);
+        wakers.reserve(expired.len());
+        for (key, waker) in expired {
+            state.deadlines.remove(&key.1);
+            rt_trace!(timer = key.1, deadline = ?key.0, "timer fired");
+            wakers.push(waker);
+        }
+        let next = state.heap.keys().next()?;
+        Some(next.0.duration_since(now))
     }
 }
 
+/// Drop wakers that would wake the same task as an earlier one, keeping
+/// first-seen order. Two wakers match when their data and vtable pointers
+/// do, which is what `Waker::will_wake` compares; hashing the pair keeps a
+/// large batch (thousands of timers expiring together) linear.
+fn dedup_wakers(wakers: Vec<Waker>) -> Vec<Waker> {
+    let mut seen = HashSet::with_capacity(wakers.len());
+    wakers
+        .into_iter()
+        .filter(|waker| seen.insert((waker.data(), waker.vtable() as *const RawWakerVTable)))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -264,7 +880,7 @@ mod tests {
     fn reactor_initializes() {
         let reactor = get();
         // Verify we can lock all mutexes (no poisoning).
-        drop(reactor.sources.lock().unwrap());
+        drop(reactor.io.lock().unwrap());
         drop(reactor.timers.lock().unwrap());
         drop(reactor.events.lock().unwrap());
     }
@@ -336,6 +952,624 @@ mod tests {
         assert!(flag.load(Ordering::SeqCst), "timer waker should have fired");
     }
 
+    #[test]
+    fn manual_clock_advance_fires_timer() {
+        // A private reactor so the manual clock can't affect other tests.
+        let reactor = Reactor::new();
+        reactor.set_clock_source(ClockSource::Manual);
+
+        let id = reactor.timer_create(50_000_000); // 50ms
+        let flag = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let flag_clone = flag.clone();
+        let waker = waker_from_fn(move || {
+            flag_clone.store(true, Ordering::SeqCst);
+        });
+        assert_eq!(reactor.timer_poll(id, waker), Poll::Pending);
+
+        reactor.advance(Duration::from_millis(49));
+        assert!(!flag.load(Ordering::SeqCst), "timer fired too early");
+
+        reactor.advance(Duration::from_millis(1));
+        assert!(flag.load(Ordering::SeqCst), "timer should fire at 50ms");
+        assert_eq!(reactor.timer_poll(id, futures_waker()), Poll::Ready(()));
+    }
+
+    #[test]
+    fn now_nanos_advances_on_real_clock() {
+        let reactor = Reactor::new();
+        let before = reactor.now_nanos();
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(reactor.now_nanos() >= before + 5_000_000);
+        assert_eq!(reactor.clock_offset_nanos(), 0);
+    }
+
+    #[test]
+    fn now_nanos_frozen_on_manual_clock_until_advance() {
+        let reactor = Reactor::new();
+        reactor.set_clock_source(ClockSource::Manual);
+        let frozen = reactor.now_nanos();
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(reactor.now_nanos(), frozen);
+        assert!(reactor.clock_offset_nanos() <= -5_000_000);
+
+        reactor.advance(Duration::from_secs(1));
+        assert_eq!(reactor.now_nanos(), frozen + 1_000_000_000);
+        assert!(reactor.clock_offset_nanos() > 0);
+
+        reactor.set_clock_source(ClockSource::Real);
+        assert_eq!(reactor.clock_offset_nanos(), 0);
+    }
+
+    #[test]
+    fn advance_is_noop_on_real_clock() {
+        let reactor = Reactor::new();
+        let id = reactor.timer_create(1_000_000_000);
+        assert_eq!(reactor.timer_poll(id, futures_waker()), Poll::Pending);
+
+        reactor.advance(Duration::from_secs(10));
+        assert_eq!(reactor.timer_poll(id, futures_waker()), Poll::Pending);
+    }
+
+    #[test]
+    fn wake_up_interrupts_blocking_react() {
+        let reactor = std::sync::Arc::new(Reactor::new());
+        let reactor_clone = reactor.clone();
+
+        let parked = std::thread::spawn(move || {
+            let start = Instant::now();
+            reactor_clone.react(None).unwrap();
+            start.elapsed()
+        });
+
+        std::thread::sleep(Duration::from_millis(20));
+        reactor.wake_up();
+
+        let elapsed = parked.join().unwrap();
+        assert!(
+            elapsed < Duration::from_secs(5),
+            "react(None) should return after wake_up: {:?}",
+            elapsed
+        );
+    }
+
+    #[test]
+    fn short_timer_fires_without_spinning() {
+        let reactor = Reactor::new();
+        let fired = std::sync::Arc::new(AtomicBool::new(false));
+        let fired_clone = fired.clone();
+        let id = reactor.timer_create(100_000);
+        let waker = waker_from_fn(move || fired_clone.store(true, Ordering::SeqCst));
+        assert_eq!(reactor.timer_poll(id, waker), Poll::Pending);
+
+        let mut calls = 0;
+        while !fired.load(Ordering::SeqCst) {
+            assert!(calls < 2, "react() spun {} times on a 100µs timer", calls);
+            reactor.react(Some(Duration::from_secs(5))).unwrap();
+            calls += 1;
+        }
+    }
+
+    #[test]
+    fn expired_timer_skips_the_wait() {
+        let reactor = Reactor::new();
+        let id = reactor.timer_create(1_000_000);
+        assert_eq!(reactor.timer_poll(id, futures_waker()), Poll::Pending);
+        std::thread::sleep(Duration::from_millis(5));
+
+        let start = Instant::now();
+        assert_eq!(reactor.react(Some(Duration::from_secs(5))).unwrap(), 1);
+        assert!(
+            start.elapsed() < Duration::from_secs(1),
+            "react() blocked with an expired timer: {:?}",
+            start.elapsed()
+        );
+    }
+
+    #[test]
+    fn wake_up_before_react_is_not_lost() {
+        let reactor = Reactor::new();
+        reactor.wake_up();
+        let start = Instant::now();
+        reactor.react(None).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn trace_emits_timer_fired_event() {
+        use std::sync::{Arc, Mutex};
+
+        /// Minimal subscriber that records each event's message.
+        struct Capture(Arc<Mutex<Vec<String>>>);
+
+        impl tracing::Subscriber for Capture {
+            fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+                tracing::span::Id::from_u64(1)
+            }
+            fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+            fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+            fn event(&self, event: &tracing::Event<'_>) {
+                struct Message<'a>(&'a mut String);
+                impl tracing::field::Visit for Message<'_> {
+                    fn record_debug(
+                        &mut self,
+                        field: &tracing::field::Field,
+                        value: &dyn std::fmt::Debug,
+                    ) {
+                        if field.name() == "message" {
+                            *self.0 = format!("{:?}", value);
+                        }
+                    }
+                }
+                let mut message = String::new();
+                event.record(&mut Message(&mut message));
+                self.0.lock().unwrap().push(message);
+            }
+            fn enter(&self, _: &tracing::span::Id) {}
+            fn exit(&self, _: &tracing::span::Id) {}
+        }
+
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        tracing::subscriber::with_default(Capture(messages.clone()), || {
+            let reactor = Reactor::new();
+            reactor.set_clock_source(ClockSource::Manual);
+            let id = reactor.timer_create(10_000_000);
+            assert_eq!(reactor.timer_poll(id, futures_waker()), Poll::Pending);
+            reactor.advance(Duration::from_millis(10));
+        });
+
+        let messages = messages.lock().unwrap();
+        assert!(messages.iter().any(|m| m == "timer created"));
+        assert!(messages.iter().any(|m| m == "timer fired"));
+    }
+
+    #[test]
+    fn pidfd_readable_after_child_exits() {
+        let reactor = Reactor::new();
+        let mut child = std::process::Command::new("/bin/true").spawn().unwrap();
+        let handle = reactor.pidfd_create(child.id() as i32).unwrap();
+
+        let waker = futures_waker();
+        let start = Instant::now();
+        let mut poll = reactor.io_poll_readable(handle, waker.clone());
+        while poll.is_pending() {
+            assert!(start.elapsed() < Duration::from_secs(5), "child exit not observed");
+            reactor.react(Some(Duration::from_millis(50))).unwrap();
+            poll = reactor.io_poll_readable(handle, waker.clone());
+        }
+        assert!(matches!(poll, Poll::Ready(Ok(()))));
+
+        reactor.io_deregister(handle);
+        // Reap the zombie.
+        child.wait().unwrap();
+    }
+
+    #[test]
+    fn poll_on_invalid_fd_reports_error() {
+        let reactor = Reactor::new();
+        // Far above any RLIMIT_NOFILE, so it can never be an open fd.
+        let handle = reactor.io_register(1_000_000);
+
+        match reactor.io_poll_readable(handle, futures_waker()) {
+            Poll::Ready(Err(err)) => assert_eq!(err.raw_os_error(), Some(9)), // EBADF
+            other => panic!("expected registration error, got {:?}", other),
+        }
+        assert_eq!(reactor.io_last_error(handle), 9);
+
+        // Sticky: the other direction sees the same error.
+        assert!(matches!(
+            reactor.io_poll_writable(handle, futures_waker()),
+            Poll::Ready(Err(_))
+        ));
+        reactor.io_deregister(handle);
+    }
+
+    #[test]
+    fn task_waiting_on_timer_and_io_is_woken_once() {
+        use std::io::Write;
+        use std::os::fd::AsRawFd;
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::Arc;
+
+        let reactor = Reactor::new();
+        let wakes = Arc::new(AtomicUsize::new(0));
+        let wakes_clone = wakes.clone();
+        let waker = waker_from_fn(move || {
+            wakes_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+        // Same task (clones of one waker) waits on a socket and a timer.
+        let (reader, mut writer) = std::os::unix::net::UnixStream::pair().unwrap();
+        let handle = reactor.io_register(reader.as_raw_fd());
+        assert!(reactor.io_poll_readable(handle, waker.clone()).is_pending());
+        let timer = reactor.timer_create(1_000_000); // 1ms
+        assert_eq!(reactor.timer_poll(timer, waker.clone()), Poll::Pending);
+
+        // Both become ready before the next react.
+        writer.write_all(b"x").unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+
+        let events = reactor.react(Some(Duration::from_millis(100))).unwrap();
+        assert_eq!(events, 2);
+        assert_eq!(wakes.load(Ordering::SeqCst), 1);
+
+        reactor.io_deregister(handle);
+    }
+
+    #[test]
+    fn react_all_wakes_every_ready_source_in_one_call() {
+        use std::io::Write;
+        use std::os::fd::AsRawFd;
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::Arc;
+
+        // More ready sources than one wait() batch can hold.
+        const SOURCES: usize = 16;
+
+        let reactor = Reactor::new();
+        *reactor.events.lock().unwrap() =
+            Events::with_capacity(std::num::NonZeroUsize::new(4).unwrap());
+        let woken = Arc::new(AtomicUsize::new(0));
+        let mut pairs = Vec::new();
+        let mut handles = Vec::new();
+        for _ in 0..SOURCES {
+            let (reader, mut writer) = std::os::unix::net::UnixStream::pair().unwrap();
+            let handle = reactor.io_register(reader.as_raw_fd());
+            let woken = woken.clone();
+            let waker = waker_from_fn(move || {
+                woken.fetch_add(1, Ordering::SeqCst);
+            });
+            assert!(reactor.io_poll_readable(handle, waker).is_pending());
+            writer.write_all(b"x").unwrap();
+            pairs.push((reader, writer));
+            handles.push(handle);
+        }
+
+        let events = reactor.react_all(Some(Duration::from_millis(100))).unwrap();
+        assert_eq!(events, SOURCES);
+        assert_eq!(woken.load(Ordering::SeqCst), SOURCES);
+
+        for handle in handles {
+            reactor.io_deregister(handle);
+        }
+    }
+
+    #[test]
+    fn deregister_wakes_pending_waiter() {
+        use std::os::fd::AsRawFd;
+        use std::sync::Arc;
+
+        let reactor = Reactor::new();
+        let (reader, _writer) = std::os::unix::net::UnixStream::pair().unwrap();
+        let handle = reactor.io_register(reader.as_raw_fd());
+        let woken = Arc::new(AtomicBool::new(false));
+        let woken_clone = woken.clone();
+        let waker = waker_from_fn(move || woken_clone.store(true, Ordering::SeqCst));
+        assert!(reactor.io_poll_readable(handle, waker).is_pending());
+
+        reactor.io_deregister(handle);
+        assert!(woken.load(Ordering::SeqCst));
+        match reactor.io_poll_readable(handle, futures_waker()) {
+            Poll::Ready(Err(err)) => assert_eq!(err.raw_os_error(), Some(libc::EBADF)),
+            other => panic!("expected EBADF after deregister, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn closed_fd_wakes_waiter_with_error() {
+        use std::os::fd::AsRawFd;
+        use std::sync::Arc;
+
+        const CHECK_INTERVAL: Duration = Duration::from_millis(50);
+        let reactor = Reactor::new();
+        reactor.set_fd_check_interval(Some(CHECK_INTERVAL));
+        let (reader, _writer) = std::os::unix::net::UnixStream::pair().unwrap();
+        // A high fd number, so no other test's fd takes its place once it's
+        // closed (fds are allocated lowest-first).
+        let fd = unsafe { libc::fcntl(reader.as_raw_fd(), libc::F_DUPFD_CLOEXEC, 200) };
+        assert!(fd >= 0);
+        drop(reader);
+        let handle = reactor.io_register(fd);
+        let woken = Arc::new(AtomicBool::new(false));
+        let woken_clone = woken.clone();
+        let waker = waker_from_fn(move || woken_clone.store(true, Ordering::SeqCst));
+        assert!(reactor.io_poll_readable(handle, waker).is_pending());
+
+        // Closed out from under the reactor: the poller never reports it.
+        unsafe { libc::close(fd) };
+        let start = Instant::now();
+        reactor.react(None).unwrap();
+        assert!(start.elapsed() < CHECK_INTERVAL * 10);
+        assert!(woken.load(Ordering::SeqCst));
+        match reactor.io_poll_readable(handle, futures_waker()) {
+            Poll::Ready(Err(err)) => assert_eq!(err.raw_os_error(), Some(libc::EBADF)),
+            other => panic!("expected EBADF for a closed fd, got {:?}", other),
+        }
+        assert_eq!(reactor.io_last_error(handle), libc::EBADF);
+        reactor.io_deregister(handle);
+    }
+
+    #[test]
+    fn rearming_a_reused_fd_number_reports_the_error() {
+        use std::os::fd::AsRawFd;
+
+        let reactor = Reactor::new();
+        let (reader, _writer) = std::os::unix::net::UnixStream::pair().unwrap();
+        let (other, _other_peer) = std::os::unix::net::UnixStream::pair().unwrap();
+        let fd = unsafe { libc::fcntl(reader.as_raw_fd(), libc::F_DUPFD_CLOEXEC, 200) };
+        assert!(fd >= 0);
+        let handle = reactor.io_register(fd);
+        assert!(reactor.io_poll_readable(handle, futures_waker()).is_pending());
+
+        // Close the fd and put a different socket under the same number —
+        // the periodic check can't tell, but re-arming interest can.
+        unsafe { libc::close(fd) };
+        assert_eq!(unsafe { libc::dup2(other.as_raw_fd(), fd) }, fd);
+        match reactor.io_poll_readable(handle, futures_waker()) {
+            Poll::Ready(Err(err)) => assert!(matches!(
+                err.raw_os_error(),
+                Some(libc::ENOENT) | Some(libc::EBADF)
+            )),
+            other => panic!("expected an error re-arming a reused fd, got {:?}", other),
+        }
+        reactor.io_deregister(handle);
+        unsafe { libc::close(fd) };
+    }
+
+    #[test]
+    fn poll_ready_reports_both_directions_together() {
+        use std::io::Write;
+        use std::os::fd::AsRawFd;
+
+        let reactor = Reactor::new();
+        let (reader, mut writer) = std::os::unix::net::UnixStream::pair().unwrap();
+        let handle = reactor.io_register(reader.as_raw_fd());
+
+        // Read-only interest waits: nothing to read yet.
+        assert!(reactor.io_poll_ready(handle, true, false, futures_waker()).is_pending());
+        writer.write_all(b"x").unwrap();
+        assert!(reactor.io_poll_ready(handle, true, true, futures_waker()).is_pending());
+        reactor.react(Some(Duration::from_secs(1))).unwrap();
+
+        assert!(matches!(
+            reactor.io_poll_ready(handle, true, true, futures_waker()),
+            Poll::Ready(Ok((true, true)))
+        ));
+        // Both readinesses were consumed.
+        assert!(reactor.io_poll_ready(handle, true, true, futures_waker()).is_pending());
+        reactor.io_deregister(handle);
+    }
+
+    #[test]
+    fn clearing_read_interest_keeps_write_armed() {
+        use std::io::Write;
+        use std::os::fd::AsRawFd;
+
+        let reactor = Reactor::new();
+        let (reader, mut writer) = std::os::unix::net::UnixStream::pair().unwrap();
+        let handle = reactor.io_register(reader.as_raw_fd());
+
+        let read_woken = std::sync::Arc::new(AtomicBool::new(false));
+        let write_woken = std::sync::Arc::new(AtomicBool::new(false));
+        let flag = read_woken.clone();
+        let read_waker = waker_from_fn(move || flag.store(true, Ordering::SeqCst));
+        let flag = write_woken.clone();
+        let write_waker = waker_from_fn(move || flag.store(true, Ordering::SeqCst));
+        assert!(reactor.io_poll_readable(handle, read_waker).is_pending());
+        assert!(reactor.io_poll_writable(handle, write_waker).is_pending());
+
+        reactor.io_clear_interest(handle, true, false).unwrap();
+        writer.write_all(b"x").unwrap();
+        reactor.react(Some(Duration::from_secs(1))).unwrap();
+
+        assert!(write_woken.load(Ordering::SeqCst), "write readiness still fires");
+        assert!(!read_woken.load(Ordering::SeqCst), "cleared read waker is dropped");
+        assert!(matches!(
+            reactor.io_poll_writable(handle, futures_waker()),
+            Poll::Ready(Ok(()))
+        ));
+        reactor.io_deregister(handle);
+    }
+
+    #[test]
+    fn io_and_timer_counts_track_live_handles() {
+        use std::os::fd::AsRawFd;
+
+        let reactor = Reactor::new();
+        let (a, b) = std::os::unix::net::UnixStream::pair().unwrap();
+        let a_handle = reactor.io_register(a.as_raw_fd());
+        let b_handle = reactor.io_register(b.as_raw_fd());
+        let timer = reactor.timer_create(60_000_000_000);
+        assert_eq!(reactor.io_count(), 2);
+        assert_eq!(reactor.timer_count(), 1);
+
+        reactor.io_deregister(a_handle);
+        reactor.io_deregister(b_handle);
+        reactor.timer_cancel(timer);
+        assert_eq!(reactor.io_count(), 0);
+        assert_eq!(reactor.timer_count(), 0);
+    }
+
+    #[test]
+    fn registering_an_fd_twice_shares_one_source() {
+        use std::io::Write;
+        use std::os::fd::AsRawFd;
+        use std::sync::Arc;
+
+        let reactor = Reactor::new();
+        let (reader, mut writer) = std::os::unix::net::UnixStream::pair().unwrap();
+        let read_half = reactor.io_register(reader.as_raw_fd());
+        let write_half = reactor.io_register(reader.as_raw_fd());
+        assert_ne!(read_half, write_half);
+        assert_eq!(reactor.io_count(), 1);
+
+        // A reader and a writer wait on the same fd without clobbering
+        // each other's interest.
+        let readable = Arc::new(AtomicBool::new(false));
+        let readable_clone = readable.clone();
+        let writable = Arc::new(AtomicBool::new(false));
+        let writable_clone = writable.clone();
+        let read_waker = waker_from_fn(move || readable_clone.store(true, Ordering::SeqCst));
+        let write_waker = waker_from_fn(move || writable_clone.store(true, Ordering::SeqCst));
+        assert!(reactor.io_poll_readable(read_half, read_waker).is_pending());
+        assert!(reactor.io_poll_writable(write_half, write_waker).is_pending());
+        writer.write_all(b"x").unwrap();
+        reactor.react(Some(Duration::from_secs(1))).unwrap();
+        assert!(readable.load(Ordering::SeqCst));
+        assert!(writable.load(Ordering::SeqCst));
+
+        // The source outlives the first deregistration...
+        reactor.io_deregister(read_half);
+        assert!(reactor.io_poll_readable(write_half, futures_waker()).is_ready());
+        assert!(matches!(
+            reactor.io_poll_writable(write_half, futures_waker()),
+            Poll::Ready(Ok(()))
+        ));
+
+        // ...and is gone after the last.
+        reactor.io_deregister(write_half);
+        match reactor.io_poll_writable(write_half, futures_waker()) {
+            Poll::Ready(Err(err)) => assert_eq!(err.raw_os_error(), Some(libc::EBADF)),
+            other => panic!("expected EBADF after last deregister, got {:?}", other),
+        }
+        assert!(reactor.io.lock().unwrap().fd_keys.is_empty());
+    }
+
+    #[test]
+    fn readers_sharing_an_fd_are_all_woken() {
+        use std::io::Write;
+        use std::os::fd::AsRawFd;
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::Arc;
+
+        let reactor = Reactor::new();
+        let (reader, mut writer) = std::os::unix::net::UnixStream::pair().unwrap();
+        let first = reactor.io_register(reader.as_raw_fd());
+        let second = reactor.io_register(reader.as_raw_fd());
+
+        let woken = Arc::new(AtomicUsize::new(0));
+        for handle in [first, second] {
+            let woken = woken.clone();
+            let waker = waker_from_fn(move || {
+                woken.fetch_add(1, Ordering::SeqCst);
+            });
+            assert!(reactor.io_poll_readable(handle, waker).is_pending());
+        }
+        writer.write_all(b"x").unwrap();
+        reactor.react(Some(Duration::from_secs(1))).unwrap();
+
+        assert_eq!(woken.load(Ordering::SeqCst), 2, "neither waker was replaced");
+        reactor.io_deregister(first);
+        reactor.io_deregister(second);
+    }
+
+    #[test]
+    fn deregister_racing_react_never_strands_a_waker() {
+        use std::io::Write;
+        use std::os::fd::AsRawFd;
+        use std::sync::Arc;
+
+        const ROUNDS: usize = 500;
+
+        let reactor = Reactor::new();
+        let stop = AtomicBool::new(false);
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                while !stop.load(Ordering::SeqCst) {
+                    reactor.react(Some(Duration::from_millis(1))).unwrap();
+                }
+            });
+
+            // Stop the react thread even when an assert below fails, so the
+            // test fails instead of hanging on the scope's join.
+            struct StopOnDrop<'a>(&'a AtomicBool);
+            impl Drop for StopOnDrop<'_> {
+                fn drop(&mut self) {
+                    self.0.store(true, Ordering::SeqCst);
+                }
+            }
+            let _stop = StopOnDrop(&stop);
+
+            for round in 0..ROUNDS {
+                let (reader, mut writer) = std::os::unix::net::UnixStream::pair().unwrap();
+                let handle = reactor.io_register(reader.as_raw_fd());
+                let woken = Arc::new(AtomicBool::new(false));
+                let woken_clone = woken.clone();
+                let waker = waker_from_fn(move || woken_clone.store(true, Ordering::SeqCst));
+                assert!(reactor.io_poll_readable(handle, waker).is_pending());
+
+                // Arm readiness while the react thread may be mid-wait, then
+                // deregister without waiting for it.
+                writer.write_all(b"x").unwrap();
+                reactor.io_deregister(handle);
+
+                // Either react or deregister wakes the task; give a react that
+                // already took the waker time to call it.
+                let deadline = Instant::now() + Duration::from_secs(1);
+                while !woken.load(Ordering::SeqCst) {
+                    assert!(Instant::now() < deadline, "waker stranded in round {}", round);
+                    std::thread::yield_now();
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn stale_handle_does_not_reach_a_reused_slot() {
+        use std::os::fd::AsRawFd;
+
+        let reactor = Reactor::new();
+        let (a, b) = std::os::unix::net::UnixStream::pair().unwrap();
+        let stale = reactor.io_register(a.as_raw_fd());
+        reactor.io_deregister(stale);
+        let live = reactor.io_register(b.as_raw_fd());
+        assert_ne!(stale, live);
+
+        match reactor.io_poll_readable(stale, futures_waker()) {
+            Poll::Ready(Err(err)) => assert_eq!(err.raw_os_error(), Some(libc::EBADF)),
+            other => panic!("expected EBADF for a stale handle, got {:?}", other),
+        }
+        reactor.io_deregister(stale);
+        assert_eq!(reactor.io_count(), 1, "stale deregister left the live source alone");
+        reactor.io_deregister(live);
+    }
+
+    #[test]
+    fn one_react_fires_a_batch_of_expired_timers() {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::Arc;
+
+        const TIMERS: usize = 10_000;
+        let reactor = Reactor::new();
+        let woken = Arc::new(AtomicUsize::new(0));
+        for _ in 0..TIMERS {
+            let id = reactor.timer_create(100_000_000); // 100ms
+            let woken = woken.clone();
+            let waker = waker_from_fn(move || {
+                woken.fetch_add(1, Ordering::SeqCst);
+            });
+            assert_eq!(reactor.timer_poll(id, waker), Poll::Pending);
+        }
+
+        std::thread::sleep(Duration::from_millis(110));
+        assert_eq!(reactor.react(Some(Duration::ZERO)).unwrap(), TIMERS);
+
+        assert_eq!(woken.load(Ordering::SeqCst), TIMERS);
+        assert_eq!(reactor.timer_count(), 0);
+    }
+
+    #[test]
+    fn dedup_wakers_keeps_distinct_tasks() {
+        let a = futures_waker();
+        let b = futures_waker();
+        let unique = dedup_wakers(vec![a.clone(), b.clone(), a.clone(), b]);
+        assert_eq!(unique.len(), 2);
+        assert!(unique[0].will_wake(&a));
+    }
+
     // ── Test helpers ────────────────────────────────────────────────
 
     /// Create a no-op waker for testing.
@@ -0,0 +1,93 @@
+//! Ctrl+C (`SIGINT`) delivery through a self-pipe.
+//!
+//! The signal handler is process-wide, so it lives here with the other
+//! process-global state. It only writes a byte to a non-blocking pipe; tasks
+//! wait for the read end to become readable through the reactor like any
+//! other fd.
+
+use std::io;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::OnceLock;
+
+/// Write end of the pipe, for the signal handler. -1 until installed.
+static WRITE_FD: AtomicI32 = AtomicI32::new(-1);
+
+/// Read end of the pipe, or the errno installing the handler failed with.
+static READ_FD: OnceLock<Result<i32, i32>> = OnceLock::new();
+
+/// Install the `SIGINT` handler (once per process) and return the pipe's
+/// read end. Each delivered signal makes it readable with one more byte.
+/// The fd stays open for the life of the process.
+pub(crate) fn ctrl_c_fd() -> io::Result<i32> {
+    let result =
+        READ_FD.get_or_init(|| install().map_err(|err| err.raw_os_error().unwrap_or(libc::EIO)));
+    (*result).map_err(io::Error::from_raw_os_error)
+}
+
+fn install() -> io::Result<i32> {
+    let mut fds = [0 as libc::c_int; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    for fd in fds {
+        unsafe {
+            libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC);
+            let flags = libc::fcntl(fd, libc::F_GETFL);
+            libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK);
+        }
+    }
+    WRITE_FD.store(fds[1], Ordering::SeqCst);
+
+    let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+    action.sa_sigaction = on_sigint as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    action.sa_flags = libc::SA_RESTART;
+    unsafe { libc::sigemptyset(&mut action.sa_mask) };
+    if unsafe { libc::sigaction(libc::SIGINT, &action, std::ptr::null_mut()) } < 0 {
+        let err = io::Error::last_os_error();
+        WRITE_FD.store(-1, Ordering::SeqCst);
+        unsafe {
+            libc::close(fds[0]);
+            libc::close(fds[1]);
+        }
+        return Err(err);
+    }
+    rt_trace!(read_fd = fds[0], "ctrl-c handler installed");
+    Ok(fds[0])
+}
+
+/// Async-signal-safe: one `write`, with `errno` preserved for the code the
+/// signal interrupted. A full pipe drops the byte — a wakeup is pending.
+extern "C" fn on_sigint(_: libc::c_int) {
+    let fd = WRITE_FD.load(Ordering::Relaxed);
+    if fd < 0 {
+        return;
+    }
+    let errno = errno_location();
+    let saved = (!errno.is_null()).then(|| unsafe { *errno });
+    unsafe { libc::write(fd, [1u8].as_ptr() as *const libc::c_void, 1) };
+    if let Some(saved) = saved {
+        unsafe { *errno = saved };
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn errno_location() -> *mut libc::c_int {
+    unsafe { libc::__errno_location() }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+fn errno_location() -> *mut libc::c_int {
+    unsafe { libc::__error() }
+}
+
+/// Elsewhere `errno` goes unrestored.
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd"
+)))]
+fn errno_location() -> *mut libc::c_int {
+    std::ptr::null_mut()
+}
@@ -1,51 +1,152 @@
 // Component trait and Container.
 
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::theme::Theme;
 
 /// Core trait for all UI components.
 ///
 /// Components render themselves as lines of text. The only required method is
-/// `render()` — input handling and invalidation have default no-op implementations.
+/// `render()` — input handling and invalidation have default no-op implementations,
+/// and components are not focusable unless they say so.
 pub trait Component {
     /// Render this component at the given terminal width.
     /// Returns a list of lines (each line is a string, possibly with ANSI codes).
     fn render(&self, width: u16) -> Vec<String>;
 
+    /// Render with the highlight styles of `theme`. Built-in components
+    /// draw from it and containers pass it on to their children; the
+    /// default ignores it and calls `render()`.
+    fn render_themed(&self, width: u16, _theme: &Theme) -> Vec<String> {
+        self.render(width)
+    }
+
     /// Handle a key input event. Only called when the component has focus.
     fn handle_input(&mut self, _event: &KeyEvent) {}
 
     /// Invalidate cached state. Called to force re-rendering.
     fn invalidate(&mut self) {}
+
+    /// Whether this component takes key input and can be a focus target.
+    fn focusable(&self) -> bool {
+        false
+    }
+
+    /// Give or take away focus. Components that draw a cursor or highlight
+    /// while focused override this; the default ignores it.
+    fn set_focused(&mut self, _focused: bool) {}
+
+    /// Whether the component always renders exactly one line, so Up / Down
+    /// mean nothing to it. Used by `Form` to move focus with the arrows.
+    fn is_single_line(&self) -> bool {
+        false
+    }
+
+    /// The component's current value as text, for components that hold one.
+    /// Used by `Form::values()`.
+    fn form_value(&self) -> Option<String> {
+        None
+    }
+
+    /// How many lines `render(width)` would return, if the component can
+    /// tell without rendering. `None` (the default) means "render to find
+    /// out". Layouts use this to size children before committing to a render.
+    fn desired_height(&self, _width: u16) -> Option<usize> {
+        None
+    }
+
+    /// For components that route input to one of several children: which
+    /// child gets it (`None` for none). The default ignores it.
+    fn focus_child(&mut self, _index: Option<usize>) {}
 }
 
 /// A container that holds child components and renders them vertically.
 ///
-/// `render()` concatenates all children's rendered lines in order.
-/// `invalidate()` propagates to all children.
+/// `render()` concatenates all visible children's rendered lines in order.
+/// `invalidate()` propagates to all children. `handle_input()` goes to the
+/// focused child (see `set_focus()`), if it is visible, so containers nest
+/// inside other components and route keys down the tree.
 pub struct Container {
-    children: Vec<Box<dyn Component>>,
+    /// Each child, and whether it is visible.
+    children: Vec<(Box<dyn Component>, bool)>,
+    /// Which child receives key input.
+    focus: Focus,
 }
 
 impl Container {
     pub fn new() -> Self {
         Container {
             children: Vec::new(),
+            focus: Focus::new(),
         }
     }
 
-    /// Add a child component to the end of the container.
+    /// Make Tab / Shift+Tab move focus to the next / previous visible,
+    /// focusable child (wrapping) instead of forwarding the key. Off by
+    /// default, so a nested `Form` keeps its own Tab handling.
+    pub fn set_tab_cycles_focus(&mut self, enabled: bool) {
+        self.focus.set_tab_cycles(enabled);
+    }
+
+    /// The first visible, focusable child after (`forward`) or before `from`,
+    /// wrapping around; `from` itself is not considered.
+    pub(crate) fn next_focusable(&self, from: usize, forward: bool) -> Option<usize> {
+        Focus::next(&self.children, from, forward)
+    }
+
+    /// Add a child component to the end of the container. Children start visible.
     pub fn add_child(&mut self, child: Box<dyn Component>) {
-        self.children.push(child);
+        self.children.push((child, true));
     }
 
     /// Remove the child at the given index. Panics if out of bounds.
+    ///
+    /// Removing the focused child tells it it lost focus and moves focus to
+    /// the next visible, focusable child, if there is one; focus on a later
+    /// child follows it to its new index.
     pub fn remove_child(&mut self, index: usize) -> Box<dyn Component> {
-        self.children.remove(index)
+        let (mut child, _) = self.children.remove(index);
+        self.focus.removed(&mut self.children, index, child.as_mut());
+        child
     }
 
     /// Remove all children.
     pub fn clear(&mut self) {
         self.children.clear();
+        self.focus.clear();
+    }
+
+    /// Set which child receives key input. Pass `None` to clear focus.
+    /// Out-of-bounds indices clear focus too.
+    ///
+    /// The previously focused child is told it lost focus and the new one
+    /// that it gained it (`Component::set_focused`).
+    pub fn set_focus(&mut self, index: Option<usize>) {
+        self.focus.set(&mut self.children, index);
+    }
+
+    /// Index of the focused child, if any.
+    pub fn focused(&self) -> Option<usize> {
+        self.focus.index()
+    }
+
+    /// Show or hide the child at the given index. Panics if out of bounds.
+    ///
+    /// A hidden child renders zero rows and doesn't receive key input, but
+    /// keeps its state and its index. Hiding the focused child moves focus
+    /// to the next visible, focusable child, if there is one.
+    pub fn set_child_visible(&mut self, index: usize, visible: bool) {
+        self.children[index].1 = visible;
+        if !visible && self.focus.index() == Some(index) {
+            if let Some(next) = self.next_focusable(index, true) {
+                self.set_focus(Some(next));
+            }
+        }
+    }
+
+    /// Whether the child at the given index is visible. `false` if out of bounds.
+    pub fn is_child_visible(&self, index: usize) -> bool {
+        self.children.get(index).is_some_and(|&(_, visible)| visible)
     }
 
     /// Number of children.
@@ -60,7 +161,230 @@ impl Container {
 
     /// Get a mutable reference to the child at the given index.
     pub fn child_mut(&mut self, index: usize) -> Option<&mut Box<dyn Component>> {
-        self.children.get_mut(index)
+        self.children.get_mut(index).map(|(child, _)| child)
+    }
+}
+
+/// The first index in `0..len` after (`forward`) or before `from` that
+/// `pick` accepts, wrapping around; `from` itself is not considered.
+pub(crate) fn next_index(
+    len: usize,
+    from: usize,
+    forward: bool,
+    pick: impl Fn(usize) -> bool,
+) -> Option<usize> {
+    (1..len)
+        .map(|step| {
+            if forward {
+                (from + step) % len
+            } else {
+                (from + len - step) % len
+            }
+        })
+        .find(|&idx| pick(idx))
+}
+
+/// One child of a layout, as seen by [`Focus`]. Layouts keep different data
+/// next to each child; this is how `Focus` gets at the component.
+pub(crate) trait Slot {
+    /// The component in this slot, if it holds one.
+    fn component(&self) -> Option<&dyn Component>;
+
+    /// Mutable access to the component in this slot, if it holds one.
+    fn component_mut(&mut self) -> Option<&mut Box<dyn Component>>;
+
+    /// Whether the slot is shown. Hidden slots don't take focus or input.
+    fn shown(&self) -> bool {
+        true
+    }
+
+    /// Whether the slot can be focused: shown, and holding a focusable
+    /// component.
+    fn takes_focus(&self) -> bool {
+        self.shown() && self.component().is_some_and(|c| c.focusable())
+    }
+}
+
+impl Slot for Box<dyn Component> {
+    fn component(&self) -> Option<&dyn Component> {
+        Some(self.as_ref())
+    }
+
+    fn component_mut(&mut self) -> Option<&mut Box<dyn Component>> {
+        Some(self)
+    }
+}
+
+impl Slot for Option<Box<dyn Component>> {
+    fn component(&self) -> Option<&dyn Component> {
+        self.as_deref()
+    }
+
+    fn component_mut(&mut self) -> Option<&mut Box<dyn Component>> {
+        self.as_mut()
+    }
+}
+
+/// A `Container` child and whether it is visible.
+impl Slot for (Box<dyn Component>, bool) {
+    fn component(&self) -> Option<&dyn Component> {
+        Some(self.0.as_ref())
+    }
+
+    fn component_mut(&mut self) -> Option<&mut Box<dyn Component>> {
+        Some(&mut self.0)
+    }
+
+    fn shown(&self) -> bool {
+        self.1
+    }
+}
+
+/// Which child of a layout receives key input and focus. `Container` and
+/// the other layouts each keep one and pass their children in as a slice of
+/// [`Slot`]s.
+pub(crate) struct Focus {
+    index: Option<usize>,
+    /// Whether the layout itself has focus. The focused child is only told
+    /// it has focus while this is set.
+    active: bool,
+    /// Whether Tab / Shift+Tab move focus instead of reaching the child.
+    tab_cycles: bool,
+}
+
+impl Focus {
+    pub(crate) fn new() -> Self {
+        Focus {
+            index: None,
+            active: true,
+            tab_cycles: false,
+        }
+    }
+
+    pub(crate) fn set_tab_cycles(&mut self, enabled: bool) {
+        self.tab_cycles = enabled;
+    }
+
+    pub(crate) fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Focus the child at `index`; `None` or an out-of-bounds index clears
+    /// focus. The old child is told it lost focus, the new one that it has
+    /// it if the layout does.
+    pub(crate) fn set<S: Slot>(&mut self, slots: &mut [S], index: Option<usize>) {
+        if let Some(old) = self.index.and_then(|idx| slots[idx].component_mut()) {
+            old.set_focused(false);
+        }
+        self.index = index.filter(|&idx| idx < slots.len());
+        if let Some(new) = self.index.and_then(|idx| slots[idx].component_mut()) {
+            new.set_focused(self.active);
+        }
+    }
+
+    /// The first child after (`forward`) or before `from` that can take
+    /// focus, wrapping around; `from` itself is not considered.
+    pub(crate) fn next<S: Slot>(slots: &[S], from: usize, forward: bool) -> Option<usize> {
+        next_index(slots.len(), from, forward, |idx| slots[idx].takes_focus())
+    }
+
+    /// Focus the next (`forward`) or previous child that can take focus.
+    pub(crate) fn cycle<S: Slot>(&mut self, slots: &mut [S], forward: bool) {
+        let Some(current) = self.index else {
+            return;
+        };
+        if let Some(idx) = Self::next(slots, current, forward) {
+            self.set(slots, Some(idx));
+        }
+    }
+
+    /// Hand focus to the child at `index` if no child has it and this one
+    /// can take it. The child is told once the layout gains focus.
+    pub(crate) fn offer<S: Slot>(&mut self, slots: &[S], index: usize) {
+        if self.index.is_none() && slots[index].takes_focus() {
+            self.index = Some(index);
+        }
+    }
+
+    /// Account for `child` having been removed from `index`; later slots
+    /// have moved down by one.
+    pub(crate) fn removed<S: Slot>(
+        &mut self,
+        slots: &mut [S],
+        index: usize,
+        child: &mut dyn Component,
+    ) {
+        match self.index {
+            Some(focused) if focused > index => self.index = Some(focused - 1),
+            Some(focused) if focused == index => self.lost(slots, index, child),
+            _ => {}
+        }
+    }
+
+    /// Account for `child` having left the slot at `index`, which stays in
+    /// place, emptied or holding a replacement.
+    pub(crate) fn replaced<S: Slot>(
+        &mut self,
+        slots: &mut [S],
+        index: usize,
+        child: &mut dyn Component,
+    ) {
+        if self.index == Some(index) {
+            self.lost(slots, index, child);
+        } else {
+            self.offer(slots, index);
+        }
+    }
+
+    /// `child` had focus and is gone from `index`: tell it, and focus the
+    /// first child from `index` on that can take focus, wrapping around.
+    fn lost<S: Slot>(&mut self, slots: &mut [S], index: usize, child: &mut dyn Component) {
+        child.set_focused(false);
+        self.index = None;
+        let len = slots.len();
+        let next = (0..len)
+            .map(|step| (index + step) % len)
+            .find(|&idx| slots[idx].takes_focus());
+        self.set(slots, next);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.index = None;
+    }
+
+    /// The focused child, unless its slot is hidden or empty.
+    pub(crate) fn focused_mut<'a, S: Slot>(
+        &self,
+        slots: &'a mut [S],
+    ) -> Option<&'a mut Box<dyn Component>> {
+        let slot = &mut slots[self.index?];
+        if !slot.shown() {
+            return None;
+        }
+        slot.component_mut()
+    }
+
+    /// Pass `event` to the focused child, or move focus on Tab / Shift+Tab
+    /// if those cycle focus.
+    pub(crate) fn handle_input<S: Slot>(&mut self, slots: &mut [S], event: &KeyEvent) {
+        if self.tab_cycles {
+            match event.code {
+                KeyCode::Tab => return self.cycle(slots, true),
+                KeyCode::BackTab => return self.cycle(slots, false),
+                _ => {}
+            }
+        }
+        if let Some(child) = self.focused_mut(slots) {
+            child.handle_input(event);
+        }
+    }
+
+    /// The layout gained or lost focus: pass it on to the focused child.
+    pub(crate) fn set_focused<S: Slot>(&mut self, slots: &mut [S], focused: bool) {
+        self.active = focused;
+        if let Some(child) = self.index.and_then(|idx| slots[idx].component_mut()) {
+            child.set_focused(focused);
+        }
     }
 }
 
@@ -72,23 +396,49 @@ impl Default for Container {
 
 impl Component for Container {
     fn render(&self, width: u16) -> Vec<String> {
+        self.render_themed(width, &Theme::default())
+    }
+
+    fn render_themed(&self, width: u16, theme: &Theme) -> Vec<String> {
         let mut lines = Vec::new();
-        for child in &self.children {
-            lines.extend(child.render(width));
+        for (child, visible) in &self.children {
+            if *visible {
+                lines.extend(child.render_themed(width, theme));
+            }
         }
         lines
     }
 
+    fn handle_input(&mut self, event: &KeyEvent) {
+        self.focus.handle_input(&mut self.children, event);
+    }
+
     fn invalidate(&mut self) {
-        for child in &mut self.children {
+        for (child, _) in &mut self.children {
             child.invalidate();
         }
     }
+
+    fn focusable(&self) -> bool {
+        self.focus.index().is_some()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focus.set_focused(&mut self.children, focused);
+    }
+
+    fn focus_child(&mut self, index: Option<usize>) {
+        self.set_focus(index);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::components::BoxComponent;
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
 
     /// A mock component that returns fixed lines on render.
     struct MockComponent {
@@ -115,6 +465,36 @@ mod tests {
         }
     }
 
+    /// Records the keys it receives and its focus state.
+    struct KeyRecorder {
+        keys: Rc<RefCell<Vec<char>>>,
+        focused: Rc<Cell<bool>>,
+    }
+
+    impl Component for KeyRecorder {
+        fn render(&self, _width: u16) -> Vec<String> {
+            vec![self.keys.borrow().iter().collect()]
+        }
+
+        fn handle_input(&mut self, event: &KeyEvent) {
+            if let KeyCode::Char(c) = event.code {
+                self.keys.borrow_mut().push(c);
+            }
+        }
+
+        fn focusable(&self) -> bool {
+            true
+        }
+
+        fn set_focused(&mut self, focused: bool) {
+            self.focused.set(focused);
+        }
+    }
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
     #[test]
     fn empty_container_renders_empty_vec() {
         let container = Container::new();
@@ -151,6 +531,37 @@ mod tests {
         assert_eq!(lines, vec!["a", "c"]);
     }
 
+    #[test]
+    fn hidden_child_renders_no_rows() {
+        let mut container = Container::new();
+        container.add_child(Box::new(MockComponent::new(vec!["a"])));
+        container.add_child(Box::new(MockComponent::new(vec!["b1", "b2"])));
+        container.add_child(Box::new(MockComponent::new(vec!["c"])));
+
+        container.set_child_visible(1, false);
+        assert!(!container.is_child_visible(1));
+        assert_eq!(container.render(80), vec!["a", "c"]);
+        assert_eq!(container.len(), 3);
+
+        container.set_child_visible(1, true);
+        assert_eq!(container.render(80), vec!["a", "b1", "b2", "c"]);
+    }
+
+    #[test]
+    fn visibility_follows_child_on_remove() {
+        let mut container = Container::new();
+        container.add_child(Box::new(MockComponent::new(vec!["a"])));
+        container.add_child(Box::new(MockComponent::new(vec!["b"])));
+        container.add_child(Box::new(MockComponent::new(vec!["c"])));
+        container.set_child_visible(2, false);
+
+        container.remove_child(0);
+        assert!(container.is_child_visible(0));
+        assert!(!container.is_child_visible(1));
+        assert!(!container.is_child_visible(5));
+        assert_eq!(container.render(80), vec!["b"]);
+    }
+
     #[test]
     fn container_clear() {
         let mut container = Container::new();
@@ -206,6 +617,10 @@ mod tests {
         );
         mock.handle_input(&key_event);
         // MockComponent overrides invalidate, but the default on Component is a no-op
+        assert!(!mock.focusable());
+        mock.set_focused(true);
+        assert_eq!(mock.form_value(), None);
+        assert!(!mock.is_single_line());
     }
 
     #[test]
@@ -239,4 +654,159 @@ mod tests {
         let lines = child.render(80);
         assert_eq!(lines, vec!["hello"]);
     }
+
+    // ── Focus ──────────────────────────────────────────────────────
+
+    #[test]
+    fn input_goes_to_focused_child_only() {
+        let first = Rc::new(RefCell::new(Vec::new()));
+        let second = Rc::new(RefCell::new(Vec::new()));
+        let mut container = Container::new();
+        for keys in [&first, &second] {
+            container.add_child(Box::new(KeyRecorder {
+                keys: keys.clone(),
+                focused: Rc::new(Cell::new(false)),
+            }));
+        }
+
+        container.handle_input(&key('x')); // no focus: dropped
+        assert!(!container.focusable());
+        container.set_focus(Some(1));
+        assert!(container.focusable());
+        container.handle_input(&key('a'));
+        container.set_focus(Some(0));
+        container.handle_input(&key('b'));
+
+        assert_eq!(*first.borrow(), vec!['b']);
+        assert_eq!(*second.borrow(), vec!['a']);
+    }
+
+    #[test]
+    fn set_focus_notifies_children() {
+        let first = Rc::new(Cell::new(false));
+        let second = Rc::new(Cell::new(false));
+        let mut container = Container::new();
+        for focused in [&first, &second] {
+            container.add_child(Box::new(KeyRecorder {
+                keys: Rc::new(RefCell::new(Vec::new())),
+                focused: focused.clone(),
+            }));
+        }
+
+        container.set_focus(Some(0));
+        assert!(first.get());
+        container.set_focus(Some(1));
+        assert!(!first.get());
+        assert!(second.get());
+        container.set_focus(Some(7));
+        assert!(!second.get());
+        assert_eq!(container.focused(), None);
+    }
+
+    #[test]
+    fn hidden_focused_child_receives_no_input() {
+        let keys = Rc::new(RefCell::new(Vec::new()));
+        let mut container = Container::new();
+        container.add_child(Box::new(KeyRecorder {
+            keys: keys.clone(),
+            focused: Rc::new(Cell::new(false)),
+        }));
+        container.set_focus(Some(0));
+        container.set_child_visible(0, false);
+        container.handle_input(&key('a'));
+        assert!(keys.borrow().is_empty());
+    }
+
+    #[test]
+    fn hiding_focused_child_moves_focus_to_next_focusable() {
+        let first = Rc::new(Cell::new(false));
+        let third = Rc::new(Cell::new(false));
+        let mut container = Container::new();
+        container.add_child(Box::new(KeyRecorder {
+            keys: Rc::new(RefCell::new(Vec::new())),
+            focused: first.clone(),
+        }));
+        container.add_child(Box::new(MockComponent::new(vec!["label"])));
+        container.add_child(Box::new(KeyRecorder {
+            keys: Rc::new(RefCell::new(Vec::new())),
+            focused: third.clone(),
+        }));
+        container.set_focus(Some(0));
+
+        container.set_child_visible(0, false);
+        assert_eq!(container.focused(), Some(2));
+        assert!(!first.get());
+        assert!(third.get());
+
+        // Showing the child again leaves focus where it moved.
+        container.set_child_visible(0, true);
+        assert_eq!(container.focused(), Some(2));
+    }
+
+    #[test]
+    fn focus_follows_child_on_remove() {
+        let mut container = Container::new();
+        container.add_child(Box::new(MockComponent::new(vec!["a"])));
+        container.add_child(Box::new(MockComponent::new(vec!["b"])));
+        container.add_child(Box::new(MockComponent::new(vec!["c"])));
+        container.set_focus(Some(2));
+        container.remove_child(0);
+        assert_eq!(container.focused(), Some(1));
+        container.remove_child(1);
+        assert_eq!(container.focused(), None);
+    }
+
+    #[test]
+    fn tab_cycles_focus_over_visible_focusable_children() {
+        let mut container = Container::new();
+        container.add_child(Box::new(KeyRecorder {
+            keys: Rc::new(RefCell::new(Vec::new())),
+            focused: Rc::new(Cell::new(false)),
+        }));
+        container.add_child(Box::new(MockComponent::new(vec!["label"])));
+        for _ in 0..2 {
+            container.add_child(Box::new(KeyRecorder {
+                keys: Rc::new(RefCell::new(Vec::new())),
+                focused: Rc::new(Cell::new(false)),
+            }));
+        }
+        container.set_child_visible(3, false);
+        container.focus_child(Some(0));
+
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+        let back_tab = KeyEvent::new(KeyCode::BackTab, KeyModifiers::NONE);
+        container.handle_input(&tab); // cycling off: forwarded
+        assert_eq!(container.focused(), Some(0));
+
+        container.set_tab_cycles_focus(true);
+        container.handle_input(&tab); // skips the label and the hidden child
+        assert_eq!(container.focused(), Some(2));
+        container.handle_input(&tab);
+        assert_eq!(container.focused(), Some(0));
+        container.handle_input(&back_tab);
+        assert_eq!(container.focused(), Some(2));
+    }
+
+    #[test]
+    fn nested_container_in_box_renders_and_routes_input() {
+        let keys = Rc::new(RefCell::new(Vec::new()));
+        let mut inner = Container::new();
+        inner.add_child(Box::new(MockComponent::new(vec!["title"])));
+        inner.add_child(Box::new(KeyRecorder {
+            keys: keys.clone(),
+            focused: Rc::new(Cell::new(false)),
+        }));
+        inner.set_focus(Some(1));
+
+        let mut boxed = BoxComponent::new(1, 0);
+        boxed.add_child(Box::new(inner));
+        let mut root = Container::new();
+        root.add_child(Box::new(boxed));
+        root.set_focus(Some(0));
+
+        root.handle_input(&key('h'));
+        root.handle_input(&key('i'));
+        assert_eq!(*keys.borrow(), vec!['h', 'i']);
+        assert_eq!(root.render(8), vec![" title  ", " hi     "]);
+    }
 }
@@ -1,14 +1,21 @@
 // BoxComponent — wraps children with padding and optional background color.
 
-use crate::component::Component;
+use crossterm::event::KeyEvent;
+
+use crate::component::{Component, Focus};
+use crate::theme::Theme;
 use crate::utils::visible_width;
 
 /// A box component that wraps children with padding and optional background color.
 ///
 /// Renders children vertically, adding horizontal and vertical padding.
 /// Optionally applies a background color (raw ANSI code) to every line including padding.
+/// Key input and focus go to the focused child: the first focusable one
+/// added, until `focus_child` picks another.
 pub struct BoxComponent {
     children: Vec<Box<dyn Component>>,
+    /// Which child receives key input.
+    focus: Focus,
     padding_x: u16,
     padding_y: u16,
     bg: Option<String>,
@@ -22,6 +29,7 @@ impl BoxComponent {
     pub fn new(padding_x: u16, padding_y: u16) -> Self {
         BoxComponent {
             children: Vec::new(),
+            focus: Focus::new(),
             padding_x,
             padding_y,
             bg: None,
@@ -33,24 +41,40 @@ impl BoxComponent {
         self.bg = Some(ansi_code.to_string());
     }
 
+    /// Make Tab / Shift+Tab move focus to the next / previous focusable
+    /// child (wrapping) instead of forwarding the key. Off by default.
+    pub fn set_tab_cycles_focus(&mut self, enabled: bool) {
+        self.focus.set_tab_cycles(enabled);
+    }
+
     /// Add a child component.
     pub fn add_child(&mut self, child: Box<dyn Component>) {
         self.children.push(child);
+        self.focus.offer(&self.children, self.children.len() - 1);
     }
 
     /// Remove the child at the given index. Panics if out of bounds.
+    ///
+    /// Removing the focused child moves focus to the next focusable one.
     pub fn remove_child(&mut self, index: usize) -> Box<dyn Component> {
-        self.children.remove(index)
+        let mut child = self.children.remove(index);
+        self.focus.removed(&mut self.children, index, child.as_mut());
+        child
     }
 
     /// Remove all children.
     pub fn clear(&mut self) {
         self.children.clear();
+        self.focus.clear();
     }
 }
 
 impl Component for BoxComponent {
     fn render(&self, width: u16) -> Vec<String> {
+        self.render_themed(width, &Theme::default())
+    }
+
+    fn render_themed(&self, width: u16, theme: &Theme) -> Vec<String> {
         if self.children.is_empty() {
             return vec![];
         }
@@ -61,7 +85,7 @@ impl Component for BoxComponent {
         // Collect all child lines rendered at inner width
         let mut child_lines = Vec::new();
         for child in &self.children {
-            child_lines.extend(child.render(inner_width as u16));
+            child_lines.extend(child.render_themed(inner_width as u16, theme));
         }
 
         // If children produce nothing, return empty
@@ -110,17 +134,59 @@ impl Component for BoxComponent {
         lines
     }
 
+    fn handle_input(&mut self, event: &KeyEvent) {
+        self.focus.handle_input(&mut self.children, event);
+    }
+
     fn invalidate(&mut self) {
         for child in &mut self.children {
             child.invalidate();
         }
     }
+
+    fn focusable(&self) -> bool {
+        self.focus.index().is_some()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focus.set_focused(&mut self.children, focused);
+    }
+
+    fn focus_child(&mut self, index: Option<usize>) {
+        self.focus.set(&mut self.children, index);
+    }
+
+    /// Children's heights plus vertical padding, or `None` if any child
+    /// can't report its height.
+    fn desired_height(&self, width: u16) -> Option<usize> {
+        let inner_width = (width as usize).saturating_sub(2 * self.padding_x as usize);
+        let mut content = 0;
+        for child in &self.children {
+            content += child.desired_height(inner_width as u16)?;
+        }
+        if content == 0 {
+            return Some(0);
+        }
+        Some(content + 2 * self.padding_y as usize)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::components::text::Text;
+    use crate::components::Input;
+    use crate::utils::strip_ansi;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    /// Rendered lines without styling or trailing padding.
+    fn plain(b: &BoxComponent) -> Vec<String> {
+        b.render(10).iter().map(|l| strip_ansi(l).trim_end().to_string()).collect()
+    }
 
     /// Mock component returning fixed lines.
     struct MockChild {
@@ -285,4 +351,62 @@ mod tests {
         assert!(lines[3].trim().is_empty());
         assert!(lines[4].trim().is_empty());
     }
+
+    #[test]
+    fn desired_height_adds_padding_to_children() {
+        let mut b = BoxComponent::new(1, 2);
+        b.add_child(Box::new(Text::new("hello world", 0, 0)));
+        b.add_child(Box::new(Text::new("more", 0, 0)));
+        assert_eq!(b.desired_height(9), Some(b.render(9).len()));
+        assert_eq!(b.desired_height(9), Some(7));
+    }
+
+    #[test]
+    fn desired_height_unknown_if_any_child_is() {
+        let mut b = BoxComponent::new(0, 0);
+        b.add_child(Box::new(Text::new("known", 0, 0)));
+        b.add_child(Box::new(MockChild::new(vec!["unknown"])));
+        assert_eq!(b.desired_height(20), None);
+    }
+
+    #[test]
+    fn input_goes_to_focused_child() {
+        let mut b = BoxComponent::new(0, 0);
+        b.add_child(Box::new(Text::new("title", 0, 0)));
+        b.add_child(Box::new(Input::new()));
+        b.add_child(Box::new(Input::new()));
+        assert!(b.focusable());
+        b.set_focused(true);
+        b.handle_input(&key(KeyCode::Char('a')));
+        b.focus_child(Some(2));
+        b.handle_input(&key(KeyCode::Char('b')));
+        assert_eq!(plain(&b), vec!["title", "> a", "> b"]);
+        assert!(!b.render(10)[1].contains("\x1b[7m"));
+        assert!(b.render(10)[2].contains("\x1b[7m"));
+    }
+
+    #[test]
+    fn tab_cycles_focus_and_removal_moves_it_on() {
+        let mut b = BoxComponent::new(0, 0);
+        b.add_child(Box::new(Input::new()));
+        b.add_child(Box::new(Input::new()));
+        b.set_tab_cycles_focus(true);
+        b.set_focused(true);
+        b.handle_input(&key(KeyCode::Tab));
+        b.handle_input(&key(KeyCode::Char('x')));
+        assert_eq!(plain(&b), vec![">", "> x"]);
+
+        let removed = b.remove_child(1);
+        assert!(!removed.render(10)[0].contains("\x1b[7m"));
+        b.handle_input(&key(KeyCode::Char('y')));
+        assert_eq!(plain(&b), vec!["> y"]);
+        assert!(b.render(10)[0].contains("\x1b[7m"));
+    }
+
+    #[test]
+    fn not_focusable_without_focusable_children() {
+        let mut b = BoxComponent::new(0, 0);
+        b.add_child(Box::new(Text::new("title", 0, 0)));
+        assert!(!b.focusable());
+    }
 }
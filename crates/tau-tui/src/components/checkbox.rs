@@ -0,0 +1,158 @@
+// Checkbox component — a labelled boolean toggle.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::component::Component;
+use crate::theme::Theme;
+use crate::utils::{truncate_to_width, visible_width};
+
+/// A single-line toggle rendered as `[x] label` or `[ ] label`.
+///
+/// Space toggles the state and invokes `on_change` with the new value. While
+/// focused, the box is drawn in inverse video.
+pub struct Checkbox {
+    label: String,
+    checked: bool,
+    /// Whether this checkbox currently has focus (highlights the box).
+    pub focused: bool,
+    /// Callback invoked with the new state after Space toggles it.
+    pub on_change: Option<Box<dyn FnMut(bool)>>,
+}
+
+impl Checkbox {
+    /// Create an unchecked checkbox with the given label.
+    pub fn new(label: &str) -> Self {
+        Checkbox {
+            label: label.to_string(),
+            checked: false,
+            focused: true,
+            on_change: None,
+        }
+    }
+
+    /// Whether the box is checked.
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    /// Set the state without invoking `on_change`.
+    pub fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+    }
+
+    /// Flip the state and notify `on_change`.
+    fn toggle(&mut self) {
+        self.checked = !self.checked;
+        if let Some(ref mut cb) = self.on_change {
+            cb(self.checked);
+        }
+    }
+}
+
+impl Component for Checkbox {
+    fn render(&self, width: u16) -> Vec<String> {
+        self.render_themed(width, &Theme::default())
+    }
+
+    fn render_themed(&self, width: u16, theme: &Theme) -> Vec<String> {
+        if width == 0 {
+            return vec![String::new()];
+        }
+        let width = width as usize;
+        let mark = if self.checked { "[x]" } else { "[ ]" };
+        let mut line = if self.focused {
+            format!("{} {}", theme.cursor.paint(mark), self.label)
+        } else {
+            format!("{} {}", mark, self.label)
+        };
+        if visible_width(&line) > width {
+            line = truncate_to_width(&line, width, "…");
+        }
+        let pad = width.saturating_sub(visible_width(&line));
+        line.push_str(&" ".repeat(pad));
+        vec![line]
+    }
+
+    fn handle_input(&mut self, event: &KeyEvent) {
+        if event.code == KeyCode::Char(' ') {
+            self.toggle();
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn is_single_line(&self) -> bool {
+        true
+    }
+
+    /// `"true"` or `"false"`.
+    fn form_value(&self) -> Option<String> {
+        Some(self.checked.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::strip_ansi;
+    use crossterm::event::KeyModifiers;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn renders_unchecked_then_checked() {
+        let mut checkbox = Checkbox::new("Enable sync");
+        checkbox.focused = false;
+        assert_eq!(checkbox.render(20), vec!["[ ] Enable sync     "]);
+        checkbox.set_checked(true);
+        assert_eq!(checkbox.render(20), vec!["[x] Enable sync     "]);
+    }
+
+    #[test]
+    fn space_toggles_and_notifies() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let changes_clone = changes.clone();
+        let mut checkbox = Checkbox::new("Wrap lines");
+        checkbox.on_change = Some(Box::new(move |checked| {
+            changes_clone.borrow_mut().push(checked);
+        }));
+
+        checkbox.handle_input(&key(KeyCode::Char(' ')));
+        assert!(checkbox.is_checked());
+        checkbox.handle_input(&key(KeyCode::Char('x'))); // ignored
+        checkbox.handle_input(&key(KeyCode::Char(' ')));
+        assert!(!checkbox.is_checked());
+        assert_eq!(*changes.borrow(), vec![true, false]);
+    }
+
+    #[test]
+    fn focused_box_is_highlighted() {
+        let checkbox = Checkbox::new("a");
+        let line = &checkbox.render(10)[0];
+        assert!(line.starts_with("\x1b[7m[ ]\x1b[27m a"));
+        assert_eq!(visible_width(line), 10);
+    }
+
+    #[test]
+    fn long_label_truncated_to_width() {
+        let checkbox = Checkbox::new("a rather long label");
+        let line = &checkbox.render(10)[0];
+        assert_eq!(visible_width(line), 10);
+        assert!(strip_ansi(line).ends_with('…'));
+    }
+
+    #[test]
+    fn is_focusable() {
+        assert!(Checkbox::new("a").focusable());
+    }
+}
@@ -0,0 +1,393 @@
+// Flex component — a vertical stack whose children share a fixed height.
+
+use crossterm::event::KeyEvent;
+
+use crate::component::{Component, Focus, Slot};
+use crate::theme::Theme;
+
+/// How a [`Flex`] child is sized.
+///
+/// A child with weight 0 is content-sized: it gets its natural height (from
+/// `Component::desired_height`, or by rendering). Weighted children share
+/// the rows left after that, in proportion to their weights. `min` and `max`
+/// bound the result either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlexChild {
+    /// Share of the leftover rows; 0 means content-sized.
+    pub weight: usize,
+    /// Fewest rows the child gets.
+    pub min: usize,
+    /// Most rows the child gets, if bounded.
+    pub max: Option<usize>,
+}
+
+impl FlexChild {
+    /// A child taking `weight` shares of the leftover rows.
+    pub fn weighted(weight: usize) -> Self {
+        FlexChild {
+            weight,
+            min: 0,
+            max: None,
+        }
+    }
+
+    /// A child sized to its content.
+    pub fn content() -> Self {
+        Self::weighted(0)
+    }
+
+    /// Set the minimum number of rows.
+    pub fn min(mut self, min: usize) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Set the maximum number of rows.
+    pub fn max(mut self, max: usize) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    fn clamp(&self, rows: usize) -> usize {
+        rows.min(self.max.unwrap_or(usize::MAX)).max(self.min)
+    }
+}
+
+impl Default for FlexChild {
+    fn default() -> Self {
+        Self::weighted(1)
+    }
+}
+
+/// A vertical layout that divides a fixed height among its children.
+///
+/// Content-sized children take their natural height first. Each weighted
+/// child then starts at its `min`, and the rows still free are split by
+/// weight (capped at each child's `max`), earlier children getting the odd
+/// rows. Every child is clipped or padded with empty lines to exactly its
+/// allotment. If the minimums alone exceed the height, the bottom is cut off.
+///
+/// The height comes from [`set_height`](Self::set_height), typically the
+/// terminal height minus any fixed chrome. Until it is set, every child
+/// renders at its natural height.
+///
+/// Key input and focus go to the focused child: the first focusable one
+/// added, until `focus_child` picks another.
+pub struct Flex {
+    children: Vec<(Box<dyn Component>, FlexChild)>,
+    /// Which child receives key input.
+    focus: Focus,
+    height: Option<usize>,
+}
+
+impl Flex {
+    /// Create an empty Flex with no height set.
+    pub fn new() -> Self {
+        Flex {
+            children: Vec::new(),
+            focus: Focus::new(),
+            height: None,
+        }
+    }
+
+    /// Make Tab / Shift+Tab move focus to the next / previous focusable
+    /// child (wrapping) instead of forwarding the key. Off by default.
+    pub fn set_tab_cycles_focus(&mut self, enabled: bool) {
+        self.focus.set_tab_cycles(enabled);
+    }
+
+    /// Add a child below the existing ones.
+    pub fn add_child(&mut self, child: Box<dyn Component>, sizing: FlexChild) {
+        self.children.push((child, sizing));
+        self.focus.offer(&self.children, self.children.len() - 1);
+    }
+
+    /// Remove the child at the given index. Panics if out of bounds.
+    ///
+    /// Removing the focused child tells it it lost focus and moves focus to
+    /// the next focusable child, if there is one.
+    pub fn remove_child(&mut self, index: usize) -> Box<dyn Component> {
+        let (mut child, _) = self.children.remove(index);
+        self.focus.removed(&mut self.children, index, child.as_mut());
+        child
+    }
+
+    /// Get a mutable reference to the child at `index`.
+    pub fn child_mut(&mut self, index: usize) -> Option<&mut Box<dyn Component>> {
+        self.children.get_mut(index).map(|(child, _)| child)
+    }
+
+    /// Set the total number of rows to lay the children out in.
+    pub fn set_height(&mut self, height: u16) {
+        self.height = Some(height as usize);
+    }
+
+    /// Rows allotted to each child at `width`, plus the lines of any child
+    /// that had to be rendered to learn its natural height.
+    fn layout(&self, width: u16, theme: &Theme) -> (Vec<usize>, Vec<Option<Vec<String>>>) {
+        let mut rendered = vec![None; self.children.len()];
+        let mut sizes = Vec::with_capacity(self.children.len());
+        for (i, (child, sizing)) in self.children.iter().enumerate() {
+            if sizing.weight > 0 && self.height.is_some() {
+                sizes.push(sizing.min);
+                continue;
+            }
+            let natural = child.desired_height(width).unwrap_or_else(|| {
+                let lines = child.render_themed(width, theme);
+                let len = lines.len();
+                rendered[i] = Some(lines);
+                len
+            });
+            sizes.push(sizing.clamp(natural));
+        }
+
+        let Some(height) = self.height else {
+            return (sizes, rendered);
+        };
+        let mut remaining = height.saturating_sub(sizes.iter().sum());
+        while remaining > 0 {
+            let growable: Vec<usize> = (0..self.children.len())
+                .filter(|&i| {
+                    let sizing = &self.children[i].1;
+                    sizing.weight > 0 && sizes[i] < sizing.max.unwrap_or(usize::MAX)
+                })
+                .collect();
+            if growable.is_empty() {
+                break;
+            }
+            let total_weight: usize = growable.iter().map(|&i| self.children[i].1.weight).sum();
+            let mut left = remaining;
+            for &i in &growable {
+                let sizing = &self.children[i].1;
+                let room = sizing.max.unwrap_or(usize::MAX) - sizes[i];
+                let share = (remaining * sizing.weight / total_weight).min(room);
+                sizes[i] += share;
+                left -= share;
+            }
+            // Rounding leftovers, one row each from the top.
+            for &i in &growable {
+                if left == 0 {
+                    break;
+                }
+                if sizes[i] < self.children[i].1.max.unwrap_or(usize::MAX) {
+                    sizes[i] += 1;
+                    left -= 1;
+                }
+            }
+            if left == remaining {
+                break;
+            }
+            remaining = left;
+        }
+        (sizes, rendered)
+    }
+}
+
+/// A `Flex` child and its sizing.
+impl Slot for (Box<dyn Component>, FlexChild) {
+    fn component(&self) -> Option<&dyn Component> {
+        Some(self.0.as_ref())
+    }
+
+    fn component_mut(&mut self) -> Option<&mut Box<dyn Component>> {
+        Some(&mut self.0)
+    }
+}
+
+impl Default for Flex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for Flex {
+    fn render(&self, width: u16) -> Vec<String> {
+        self.render_themed(width, &Theme::default())
+    }
+
+    fn render_themed(&self, width: u16, theme: &Theme) -> Vec<String> {
+        let (sizes, mut rendered) = self.layout(width, theme);
+        let mut lines = Vec::new();
+        for (i, (child, _)) in self.children.iter().enumerate() {
+            let mut child_lines = rendered[i]
+                .take()
+                .unwrap_or_else(|| child.render_themed(width, theme));
+            child_lines.resize(sizes[i], String::new());
+            lines.extend(child_lines);
+        }
+        if let Some(height) = self.height {
+            lines.truncate(height);
+        }
+        lines
+    }
+
+    fn handle_input(&mut self, event: &KeyEvent) {
+        self.focus.handle_input(&mut self.children, event);
+    }
+
+    fn invalidate(&mut self) {
+        for (child, _) in &mut self.children {
+            child.invalidate();
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        self.focus.index().is_some()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focus.set_focused(&mut self.children, focused);
+    }
+
+    fn focus_child(&mut self, index: Option<usize>) {
+        self.focus.set(&mut self.children, index);
+    }
+
+    fn desired_height(&self, width: u16) -> Option<usize> {
+        match self.height {
+            Some(height) => {
+                let (sizes, _) = self.layout(width, &Theme::default());
+                Some(sizes.iter().sum::<usize>().min(height))
+            }
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Input, Text};
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn has_cursor(input: &dyn Component) -> bool {
+        input.render(20)[0].contains("\x1b[7m")
+    }
+
+    /// Renders `len` numbered lines, without reporting a desired height.
+    struct Lines(usize);
+
+    impl Component for Lines {
+        fn render(&self, _width: u16) -> Vec<String> {
+            (0..self.0).map(|i| i.to_string()).collect()
+        }
+    }
+
+    /// Rows allotted to each child, checked against the rendered total.
+    fn heights(flex: &Flex, width: u16) -> Vec<usize> {
+        let (sizes, _) = flex.layout(width, &Theme::default());
+        assert_eq!(flex.render(width).len(), sizes.iter().sum::<usize>());
+        sizes
+    }
+
+    #[test]
+    fn weights_split_height() {
+        let mut flex = Flex::new();
+        flex.add_child(Box::new(Lines(100)), FlexChild::weighted(3));
+        flex.add_child(Box::new(Lines(0)), FlexChild::weighted(1));
+        flex.set_height(24);
+        assert_eq!(heights(&flex, 40), vec![18, 6]);
+
+        let lines = flex.render(40);
+        assert_eq!(lines[0], "0");
+        assert_eq!(lines[17], "17");
+        assert!(lines[18..].iter().all(|l| l.is_empty()));
+    }
+
+    #[test]
+    fn odd_rows_go_to_earlier_children() {
+        let mut flex = Flex::new();
+        flex.add_child(Box::new(Lines(0)), FlexChild::weighted(1));
+        flex.add_child(Box::new(Lines(0)), FlexChild::weighted(1));
+        flex.set_height(5);
+        assert_eq!(heights(&flex, 40), vec![3, 2]);
+    }
+
+    #[test]
+    fn content_sized_children_take_their_natural_height() {
+        let mut flex = Flex::new();
+        flex.add_child(Box::new(Text::new("title", 0, 0)), FlexChild::content());
+        flex.add_child(Box::new(Lines(0)), FlexChild::weighted(1));
+        flex.add_child(Box::new(Lines(2)), FlexChild::content());
+        flex.set_height(24);
+        assert_eq!(heights(&flex, 40), vec![1, 21, 2]);
+    }
+
+    #[test]
+    fn max_caps_a_child_and_frees_rows_for_others() {
+        let mut flex = Flex::new();
+        flex.add_child(Box::new(Lines(0)), FlexChild::weighted(3));
+        flex.add_child(Box::new(Lines(0)), FlexChild::weighted(1).max(2));
+        flex.set_height(24);
+        assert_eq!(heights(&flex, 40), vec![22, 2]);
+    }
+
+    #[test]
+    fn min_is_met_before_weights_apply() {
+        let mut flex = Flex::new();
+        flex.add_child(Box::new(Lines(0)), FlexChild::weighted(3));
+        flex.add_child(Box::new(Lines(0)), FlexChild::weighted(1).min(10));
+        flex.set_height(24);
+        // 14 free rows split 3:1 → 10 + 3, the odd row to the first child.
+        assert_eq!(heights(&flex, 40), vec![11, 13]);
+    }
+
+    #[test]
+    fn minimums_exceeding_height_are_cut_off() {
+        let mut flex = Flex::new();
+        flex.add_child(Box::new(Lines(10)), FlexChild::weighted(1).min(8));
+        flex.add_child(Box::new(Lines(10)), FlexChild::weighted(1).min(8));
+        flex.set_height(10);
+        let lines = flex.render(40);
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[8], "0");
+        assert_eq!(flex.desired_height(40), Some(10));
+    }
+
+    #[test]
+    fn without_height_children_render_naturally() {
+        let mut flex = Flex::new();
+        flex.add_child(Box::new(Lines(3)), FlexChild::weighted(2));
+        flex.add_child(Box::new(Lines(1)), FlexChild::weighted(1).min(2));
+        assert_eq!(flex.render(40), vec!["0", "1", "2", "0", ""]);
+        assert_eq!(flex.desired_height(40), None);
+    }
+
+    #[test]
+    fn input_goes_to_focused_child() {
+        let mut flex = Flex::new();
+        flex.add_child(Box::new(Lines(3)), FlexChild::weighted(1));
+        flex.add_child(Box::new(Input::new()), FlexChild::content());
+        flex.add_child(Box::new(Input::new()), FlexChild::content());
+        assert!(flex.focusable());
+        flex.set_focused(true);
+        flex.handle_input(&key(KeyCode::Char('a')));
+        flex.focus_child(Some(2));
+        flex.handle_input(&key(KeyCode::Char('b')));
+        assert_eq!(flex.child_mut(1).unwrap().form_value().as_deref(), Some("a"));
+        assert_eq!(flex.child_mut(2).unwrap().form_value().as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn removing_focused_child_moves_focus_on() {
+        let mut flex = Flex::new();
+        flex.add_child(Box::new(Input::new()), FlexChild::content());
+        flex.add_child(Box::new(Input::new()), FlexChild::content());
+        flex.set_focused(true);
+        flex.focus_child(Some(1));
+        assert!(!has_cursor(flex.child_mut(0).unwrap().as_ref()));
+
+        let removed = flex.remove_child(1);
+        assert!(!has_cursor(removed.as_ref()));
+        assert!(has_cursor(flex.child_mut(0).unwrap().as_ref()));
+        flex.handle_input(&key(KeyCode::Char('x')));
+        assert_eq!(flex.child_mut(0).unwrap().form_value().as_deref(), Some("x"));
+
+        flex.remove_child(0);
+        assert!(!flex.focusable());
+    }
+}
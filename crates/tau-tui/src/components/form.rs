@@ -0,0 +1,349 @@
+// Form component — named fields with automatic focus navigation.
+
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::component::{next_index, Component};
+use crate::theme::Theme;
+use crate::utils::truncate_to_width;
+
+/// A named field in a [`Form`].
+struct FormField {
+    name: String,
+    label: String,
+    component: Box<dyn Component>,
+}
+
+/// A vertical stack of named fields with one focused field receiving input.
+///
+/// Each field renders as its label (bold while focused, omitted if empty)
+/// followed by the field itself. Only focusable fields take focus:
+///
+/// - Tab / Shift+Tab move focus to the next / previous field, wrapping.
+/// - Down / Up do the same, but only while the focused field is single-line
+///   (`Component::is_single_line`) — multi-line fields such as `RadioGroup`
+///   keep the arrows for themselves.
+/// - Enter is forwarded to the focused field; on the last focusable field it
+///   then fires `on_submit`. Alt+Enter submits from any field.
+///
+/// All other keys go to the focused field. While the form itself is
+/// unfocused (see `set_focused`), no field shows focus.
+pub struct Form {
+    fields: Vec<FormField>,
+    /// Index into `fields` of the focused field.
+    focused: Option<usize>,
+    /// Whether the form itself has focus; the focused field only shows it
+    /// while this is set.
+    has_focus: bool,
+    /// Callback invoked on submit with the values of all fields.
+    pub on_submit: Option<Box<dyn FnMut(&HashMap<String, String>)>>,
+}
+
+impl Form {
+    /// Create an empty form.
+    pub fn new() -> Self {
+        Form {
+            fields: Vec::new(),
+            focused: None,
+            has_focus: true,
+            on_submit: None,
+        }
+    }
+
+    /// Append a field. The first focusable field added takes focus; the rest
+    /// start unfocused.
+    pub fn add_field(&mut self, name: &str, label: &str, mut component: Box<dyn Component>) {
+        let takes_focus = self.focused.is_none() && component.focusable();
+        component.set_focused(takes_focus && self.has_focus);
+        if takes_focus {
+            self.focused = Some(self.fields.len());
+        }
+        self.fields.push(FormField {
+            name: name.to_string(),
+            label: label.to_string(),
+            component,
+        });
+    }
+
+    /// Name of the focused field, if any.
+    pub fn focused_name(&self) -> Option<&str> {
+        self.focused.map(|idx| self.fields[idx].name.as_str())
+    }
+
+    /// Move focus to the field called `name`. Ignored if there is no such
+    /// focusable field.
+    pub fn focus(&mut self, name: &str) {
+        if let Some(idx) = self
+            .fields
+            .iter()
+            .position(|f| f.name == name && f.component.focusable())
+        {
+            self.set_focus(idx);
+        }
+    }
+
+    /// Get a mutable reference to the field called `name`.
+    pub fn field_mut(&mut self, name: &str) -> Option<&mut Box<dyn Component>> {
+        self.fields
+            .iter_mut()
+            .find(|f| f.name == name)
+            .map(|f| &mut f.component)
+    }
+
+    /// Current values of all fields that hold one, keyed by field name.
+    pub fn values(&self) -> HashMap<String, String> {
+        self.fields
+            .iter()
+            .filter_map(|f| f.component.form_value().map(|v| (f.name.clone(), v)))
+            .collect()
+    }
+
+    fn set_focus(&mut self, idx: usize) {
+        if let Some(old) = self.focused {
+            self.fields[old].component.set_focused(false);
+        }
+        self.fields[idx].component.set_focused(self.has_focus);
+        self.focused = Some(idx);
+    }
+
+    /// Focus the next (`forward`) or previous focusable field, wrapping.
+    fn cycle_focus(&mut self, forward: bool) {
+        let Some(current) = self.focused else {
+            return;
+        };
+        let next = next_index(self.fields.len(), current, forward, |idx| {
+            self.fields[idx].component.focusable()
+        });
+        if let Some(idx) = next {
+            self.set_focus(idx);
+        }
+    }
+
+    fn is_last_focusable(&self, idx: usize) -> bool {
+        !self.fields[idx + 1..].iter().any(|f| f.component.focusable())
+    }
+
+    fn submit(&mut self) {
+        let values = self.values();
+        if let Some(ref mut cb) = self.on_submit {
+            cb(&values);
+        }
+    }
+}
+
+impl Default for Form {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for Form {
+    fn render(&self, width: u16) -> Vec<String> {
+        self.render_themed(width, &Theme::default())
+    }
+
+    fn render_themed(&self, width: u16, theme: &Theme) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (i, field) in self.fields.iter().enumerate() {
+            if !field.label.is_empty() {
+                let label = truncate_to_width(&field.label, width as usize, "…");
+                if self.focused == Some(i) && !label.is_empty() {
+                    lines.push(theme.accent.paint(&label));
+                } else {
+                    lines.push(label);
+                }
+            }
+            lines.extend(field.component.render_themed(width, theme));
+        }
+        lines
+    }
+
+    fn handle_input(&mut self, event: &KeyEvent) {
+        let Some(idx) = self.focused else {
+            return;
+        };
+        let single_line = self.fields[idx].component.is_single_line();
+        match event.code {
+            KeyCode::Tab => self.cycle_focus(true),
+            KeyCode::BackTab => self.cycle_focus(false),
+            KeyCode::Down if single_line => self.cycle_focus(true),
+            KeyCode::Up if single_line => self.cycle_focus(false),
+            KeyCode::Enter if event.modifiers.contains(KeyModifiers::ALT) => self.submit(),
+            KeyCode::Enter => {
+                self.fields[idx].component.handle_input(event);
+                if self.is_last_focusable(idx) {
+                    self.submit();
+                }
+            }
+            _ => self.fields[idx].component.handle_input(event),
+        }
+    }
+
+    fn invalidate(&mut self) {
+        for field in &mut self.fields {
+            field.component.invalidate();
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        self.focused.is_some()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.has_focus = focused;
+        if let Some(idx) = self.focused {
+            self.fields[idx].component.set_focused(focused);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Checkbox, Input, RadioGroup, Text};
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_str(form: &mut Form, s: &str) {
+        for c in s.chars() {
+            form.handle_input(&key(KeyCode::Char(c)));
+        }
+    }
+
+    fn login_form() -> Form {
+        let mut form = Form::new();
+        form.add_field("user", "Username", Box::new(Input::new()));
+        form.add_field("pass", "Password", Box::new(Input::new()));
+        form
+    }
+
+    #[test]
+    fn tab_moves_between_fields_and_values_collects_them() {
+        let mut form = login_form();
+        assert_eq!(form.focused_name(), Some("user"));
+        type_str(&mut form, "alice");
+        form.handle_input(&key(KeyCode::Tab));
+        assert_eq!(form.focused_name(), Some("pass"));
+        type_str(&mut form, "hunter2");
+
+        let values = form.values();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values["user"], "alice");
+        assert_eq!(values["pass"], "hunter2");
+    }
+
+    #[test]
+    fn focus_cycles_with_wrapping_in_both_directions() {
+        let mut form = login_form();
+        form.handle_input(&key(KeyCode::Tab));
+        form.handle_input(&key(KeyCode::Tab));
+        assert_eq!(form.focused_name(), Some("user"));
+        form.handle_input(&key(KeyCode::BackTab));
+        assert_eq!(form.focused_name(), Some("pass"));
+        form.handle_input(&key(KeyCode::Up));
+        assert_eq!(form.focused_name(), Some("user"));
+        form.handle_input(&key(KeyCode::Down));
+        assert_eq!(form.focused_name(), Some("pass"));
+    }
+
+    #[test]
+    fn only_focused_input_draws_cursor() {
+        let mut form = login_form();
+        form.handle_input(&key(KeyCode::Tab));
+        let lines = form.render(20);
+        // Label, field, label, field
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "Username");
+        assert!(!lines[1].contains("\x1b[7m"));
+        assert_eq!(lines[2], "\x1b[1mPassword\x1b[22m");
+        assert!(lines[3].contains("\x1b[7m"));
+    }
+
+    #[test]
+    fn non_focusable_fields_are_skipped() {
+        let mut form = Form::new();
+        form.add_field("intro", "", Box::new(Text::new("Fill in:", 0, 0)));
+        form.add_field("name", "Name", Box::new(Input::new()));
+        form.add_field("agree", "", Box::new(Checkbox::new("I agree")));
+        assert_eq!(form.focused_name(), Some("name"));
+        form.handle_input(&key(KeyCode::Tab));
+        assert_eq!(form.focused_name(), Some("agree"));
+        form.handle_input(&key(KeyCode::Tab));
+        assert_eq!(form.focused_name(), Some("name"));
+        assert!(!form.values().contains_key("intro"));
+    }
+
+    #[test]
+    fn arrows_stay_inside_multi_line_field() {
+        let mut form = Form::new();
+        form.add_field(
+            "theme",
+            "Theme",
+            Box::new(RadioGroup::new(vec!["light".into(), "dark".into()])),
+        );
+        form.add_field("name", "Name", Box::new(Input::new()));
+        form.handle_input(&key(KeyCode::Down));
+        form.handle_input(&key(KeyCode::Char(' ')));
+        assert_eq!(form.focused_name(), Some("theme"));
+        assert_eq!(form.values()["theme"], "dark");
+    }
+
+    #[test]
+    fn enter_on_last_field_submits() {
+        let submitted = Rc::new(RefCell::new(None));
+        let submitted_clone = submitted.clone();
+        let mut form = login_form();
+        form.on_submit = Some(Box::new(move |values| {
+            *submitted_clone.borrow_mut() = Some(values.clone());
+        }));
+
+        type_str(&mut form, "bob");
+        form.handle_input(&key(KeyCode::Enter));
+        assert!(submitted.borrow().is_none(), "Enter on first field must not submit");
+
+        form.handle_input(&key(KeyCode::Tab));
+        type_str(&mut form, "pw");
+        form.handle_input(&key(KeyCode::Enter));
+        let values = submitted.borrow_mut().take().unwrap();
+        assert_eq!(values["user"], "bob");
+        assert_eq!(values["pass"], "pw");
+    }
+
+    #[test]
+    fn alt_enter_submits_from_any_field() {
+        let count = Rc::new(Cell::new(0));
+        let count_clone = count.clone();
+        let mut form = login_form();
+        form.on_submit = Some(Box::new(move |_| count_clone.set(count_clone.get() + 1)));
+        form.handle_input(&KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT));
+        assert_eq!(count.get(), 1);
+    }
+
+    #[test]
+    fn focus_by_name() {
+        let mut form = login_form();
+        form.focus("pass");
+        assert_eq!(form.focused_name(), Some("pass"));
+        form.focus("missing");
+        assert_eq!(form.focused_name(), Some("pass"));
+    }
+
+    #[test]
+    fn unfocused_form_hides_field_focus() {
+        let mut form = login_form();
+        form.set_focused(false);
+        assert!(!form.render(20).iter().any(|l| l.contains("\x1b[7m")));
+        form.handle_input(&key(KeyCode::Tab));
+        assert!(!form.render(20).iter().any(|l| l.contains("\x1b[7m")));
+
+        form.set_focused(true);
+        let lines = form.render(20);
+        assert!(!lines[1].contains("\x1b[7m"));
+        assert!(lines[3].contains("\x1b[7m"));
+    }
+}
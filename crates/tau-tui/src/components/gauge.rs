@@ -0,0 +1,181 @@
+// Gauge component — a one-line bar showing a value within a range.
+
+use crate::component::Component;
+
+/// A one-line meter for a value within a range, such as CPU usage or
+/// signal strength.
+///
+/// Unlike a progress bar it implies no completion: the bar just shows where
+/// the value sits between `min` and `max`. The filled part takes the color
+/// of the threshold band the value falls in, and an optional numeric label
+/// follows the bar. Values outside the range are clamped to it.
+pub struct Gauge {
+    min: f64,
+    max: f64,
+    value: f64,
+    /// Lower bound and ANSI color of each band, sorted by bound.
+    thresholds: Vec<(f64, String)>,
+    /// Decimal places of the label, or `None` for no label.
+    label_decimals: Option<usize>,
+}
+
+impl Gauge {
+    /// Create a gauge over `min..=max`, showing `min`.
+    pub fn new(min: f64, max: f64) -> Self {
+        Gauge {
+            min,
+            max,
+            value: min,
+            thresholds: Vec::new(),
+            label_decimals: None,
+        }
+    }
+
+    /// Set the value, clamped to the gauge's range.
+    pub fn set_value(&mut self, value: f64) {
+        self.value = if value.is_nan() {
+            self.min
+        } else {
+            value.clamp(self.min.min(self.max), self.max.max(self.min))
+        };
+    }
+
+    /// The current (clamped) value.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Set the color bands as `(lower_bound, ansi_code)` pairs, e.g.
+    /// `&[(0.0, "\x1b[32m"), (60.0, "\x1b[33m"), (85.0, "\x1b[31m")]`.
+    ///
+    /// The bar takes the color of the highest bound at or below the value;
+    /// below every bound it is uncolored. Order doesn't matter.
+    pub fn set_thresholds(&mut self, thresholds: &[(f64, &str)]) {
+        self.thresholds = thresholds
+            .iter()
+            .map(|&(bound, color)| (bound, color.to_string()))
+            .collect();
+        self.thresholds.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+
+    /// Show the value after the bar with `decimals` decimal places, or hide
+    /// it with `None` (the default).
+    pub fn set_label(&mut self, decimals: Option<usize>) {
+        self.label_decimals = decimals;
+    }
+
+    /// Fraction of the range the value covers, in `0.0..=1.0`.
+    fn fraction(&self) -> f64 {
+        let span = self.max - self.min;
+        if span > 0.0 {
+            ((self.value - self.min) / span).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// The color of the band the value falls in.
+    fn color(&self) -> Option<&str> {
+        self.thresholds
+            .iter()
+            .rev()
+            .find(|(bound, _)| *bound <= self.value)
+            .map(|(_, color)| color.as_str())
+    }
+}
+
+impl Component for Gauge {
+    fn render(&self, width: u16) -> Vec<String> {
+        let width = width as usize;
+        let label = self
+            .label_decimals
+            .map(|decimals| format!(" {:.*}", decimals, self.value))
+            .filter(|label| label.len() < width)
+            .unwrap_or_default();
+        let bar_width = width - label.len();
+        let filled = (self.fraction() * bar_width as f64).round() as usize;
+
+        let mut line = String::new();
+        if filled > 0 {
+            match self.color() {
+                Some(color) => {
+                    line.push_str(color);
+                    line.push_str(&"█".repeat(filled));
+                    line.push_str("\x1b[0m");
+                }
+                None => line.push_str(&"█".repeat(filled)),
+            }
+        }
+        line.push_str(&"░".repeat(bar_width - filled));
+        line.push_str(&label);
+        vec![line]
+    }
+
+    fn desired_height(&self, _width: u16) -> Option<usize> {
+        Some(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::visible_width;
+
+    const GREEN: &str = "\x1b[32m";
+    const YELLOW: &str = "\x1b[33m";
+    const RED: &str = "\x1b[31m";
+
+    fn cpu_gauge(value: f64) -> Gauge {
+        let mut gauge = Gauge::new(0.0, 100.0);
+        gauge.set_thresholds(&[(0.0, GREEN), (60.0, YELLOW), (85.0, RED)]);
+        gauge.set_value(value);
+        gauge
+    }
+
+    #[test]
+    fn yellow_band_value_fills_proportionally_in_yellow() {
+        let line = &cpu_gauge(70.0).render(10)[0];
+        assert_eq!(line, "\x1b[33m███████\x1b[0m░░░");
+    }
+
+    #[test]
+    fn bands_pick_highest_bound_at_or_below_value() {
+        assert!(cpu_gauge(59.9).render(10)[0].starts_with(GREEN));
+        assert!(cpu_gauge(60.0).render(10)[0].starts_with(YELLOW));
+        assert!(cpu_gauge(100.0).render(10)[0].starts_with(RED));
+
+        let mut gauge = Gauge::new(0.0, 100.0);
+        gauge.set_thresholds(&[(50.0, RED)]);
+        gauge.set_value(30.0);
+        assert_eq!(gauge.render(10)[0], "███░░░░░░░");
+    }
+
+    #[test]
+    fn out_of_range_values_are_clamped() {
+        let mut gauge = cpu_gauge(150.0);
+        assert_eq!(gauge.value(), 100.0);
+        assert_eq!(gauge.render(4)[0], "\x1b[31m████\x1b[0m");
+        gauge.set_value(-5.0);
+        assert_eq!(gauge.value(), 0.0);
+        assert_eq!(gauge.render(4)[0], "░░░░");
+    }
+
+    #[test]
+    fn label_follows_bar_within_width() {
+        let mut gauge = cpu_gauge(42.0);
+        gauge.set_label(Some(1));
+        let line = &gauge.render(15)[0];
+        assert!(line.ends_with("░ 42.0"));
+        assert_eq!(visible_width(line), 15);
+        // A label that doesn't fit is dropped rather than the bar.
+        assert_eq!(visible_width(&gauge.render(4)[0]), 4);
+    }
+
+    #[test]
+    fn empty_range_renders_empty_bar() {
+        let mut gauge = Gauge::new(5.0, 5.0);
+        gauge.set_value(7.0);
+        assert_eq!(gauge.value(), 5.0);
+        assert_eq!(gauge.render(3)[0], "░░░");
+    }
+}
@@ -0,0 +1,439 @@
+// Grid component — a 2D layout of cells in rows and columns.
+
+use crossterm::event::KeyEvent;
+
+use crate::component::{Component, Focus};
+use crate::theme::Theme;
+use crate::utils::{truncate_to_width, visible_width};
+
+/// How a [`Grid`] row or column is sized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Track {
+    /// Exactly this many columns or rows.
+    Fixed(usize),
+    /// A share of the space left after fixed and auto tracks, in proportion
+    /// to the weights.
+    Weight(usize),
+    /// Sized to content: a column to the widest line its cells render, a
+    /// row to its tallest cell (by `Component::desired_height`, or by
+    /// rendering).
+    Auto,
+}
+
+/// A grid of cells, each holding one component.
+///
+/// Columns share the width given to `render`; rows share the height set by
+/// [`set_height`](Self::set_height). Fixed and auto tracks are sized first,
+/// in order, each capped by the space still left; weighted tracks then split
+/// the remainder, earlier ones getting the odd column or row. Until a height
+/// is set, weighted rows are sized like auto rows.
+///
+/// Every cell is clipped or padded to its box, and any SGR state it leaves
+/// open is reset at the box's right edge. Empty cells render as blanks.
+///
+/// Key input and focus go to the focused cell: the first focusable one set,
+/// until `focus_child` picks another by its row-major index
+/// (`row * columns + col`).
+pub struct Grid {
+    columns: Vec<Track>,
+    rows: Vec<Track>,
+    /// Row-major, `rows.len() * columns.len()` slots.
+    cells: Vec<Option<Box<dyn Component>>>,
+    /// Which cell receives key input.
+    focus: Focus,
+    height: Option<usize>,
+}
+
+impl Grid {
+    /// Create a grid with the given column and row tracks and no cells.
+    pub fn new(columns: Vec<Track>, rows: Vec<Track>) -> Self {
+        let cells = (0..columns.len() * rows.len()).map(|_| None).collect();
+        Grid {
+            columns,
+            rows,
+            cells,
+            focus: Focus::new(),
+            height: None,
+        }
+    }
+
+    /// Make Tab / Shift+Tab move focus to the next / previous focusable
+    /// cell (wrapping, row-major) instead of forwarding the key. Off by
+    /// default.
+    pub fn set_tab_cycles_focus(&mut self, enabled: bool) {
+        self.focus.set_tab_cycles(enabled);
+    }
+
+    /// Put `child` in the cell at `row`, `col`, returning the component it
+    /// replaces. Panics if out of bounds.
+    ///
+    /// Replacing the focused cell's component moves focus as removing it
+    /// would (see [`take_cell`](Self::take_cell)).
+    pub fn set_cell(
+        &mut self,
+        row: usize,
+        col: usize,
+        child: Box<dyn Component>,
+    ) -> Option<Box<dyn Component>> {
+        let index = self.index(row, col);
+        let old = self.cells[index].replace(child);
+        self.vacated(index, old)
+    }
+
+    /// Empty the cell at `row`, `col`, returning its component. Panics if
+    /// out of bounds.
+    ///
+    /// Emptying the focused cell tells its component it lost focus and moves
+    /// focus to the next focusable cell, if there is one.
+    pub fn take_cell(&mut self, row: usize, col: usize) -> Option<Box<dyn Component>> {
+        let index = self.index(row, col);
+        let old = self.cells[index].take();
+        self.vacated(index, old)
+    }
+
+    /// Update focus after `old` left the cell at `index`, and hand it back.
+    fn vacated(
+        &mut self,
+        index: usize,
+        mut old: Option<Box<dyn Component>>,
+    ) -> Option<Box<dyn Component>> {
+        match old.as_mut() {
+            Some(child) => self.focus.replaced(&mut self.cells, index, child.as_mut()),
+            None => self.focus.offer(&self.cells, index),
+        }
+        old
+    }
+
+    /// Get a mutable reference to the component at `row`, `col`.
+    pub fn cell_mut(&mut self, row: usize, col: usize) -> Option<&mut Box<dyn Component>> {
+        if row >= self.rows.len() || col >= self.columns.len() {
+            return None;
+        }
+        let index = self.index(row, col);
+        self.cells[index].as_mut()
+    }
+
+    /// Set the total number of rows to lay the grid out in.
+    pub fn set_height(&mut self, height: u16) {
+        self.height = Some(height as usize);
+    }
+
+    fn index(&self, row: usize, col: usize) -> usize {
+        assert!(
+            row < self.rows.len() && col < self.columns.len(),
+            "cell ({row}, {col}) outside a {}x{} grid",
+            self.rows.len(),
+            self.columns.len()
+        );
+        row * self.columns.len() + col
+    }
+
+    fn cell(&self, row: usize, col: usize) -> Option<&dyn Component> {
+        self.cells[row * self.columns.len() + col].as_deref()
+    }
+
+    /// Size every column and row for `width`.
+    fn layout(&self, width: u16, theme: &Theme) -> Layout {
+        let cols = self.columns.len();
+        let widths = distribute(Some(width as usize), &self.columns, |col, room| {
+            (0..self.rows.len())
+                .filter_map(|row| self.cell(row, col))
+                .flat_map(|cell| cell.render_themed(room as u16, theme))
+                .map(|line| visible_width(&line))
+                .max()
+                .unwrap_or(0)
+        });
+
+        let mut rendered: Vec<Option<Vec<String>>> = vec![None; self.cells.len()];
+        let heights = distribute(self.height, &self.rows, |row, _room| {
+            (0..cols)
+                .map(|col| {
+                    let Some(cell) = self.cell(row, col) else {
+                        return 0;
+                    };
+                    let cell_width = widths[col] as u16;
+                    cell.desired_height(cell_width).unwrap_or_else(|| {
+                        let lines = cell.render_themed(cell_width, theme);
+                        let len = lines.len();
+                        rendered[row * cols + col] = Some(lines);
+                        len
+                    })
+                })
+                .max()
+                .unwrap_or(0)
+        });
+        Layout {
+            widths,
+            heights,
+            rendered,
+        }
+    }
+}
+
+/// The outcome of [`Grid::layout`].
+struct Layout {
+    widths: Vec<usize>,
+    heights: Vec<usize>,
+    /// Lines of the cells (row-major) that had to be rendered to size an
+    /// auto row.
+    rendered: Vec<Option<Vec<String>>>,
+}
+
+/// Split `total` (unbounded if `None`) among `tracks`. `natural(i, room)`
+/// gives the content size of track `i` given `room` to spare; weighted
+/// tracks fall back to it when `total` is unbounded.
+fn distribute(
+    total: Option<usize>,
+    tracks: &[Track],
+    mut natural: impl FnMut(usize, usize) -> usize,
+) -> Vec<usize> {
+    let mut sizes = vec![0; tracks.len()];
+    let mut used = 0;
+    for (i, track) in tracks.iter().enumerate() {
+        let room = total.map_or(usize::MAX, |total| total - used);
+        sizes[i] = match *track {
+            Track::Fixed(size) => size.min(room),
+            Track::Auto => natural(i, room).min(room),
+            Track::Weight(_) if total.is_none() => natural(i, room),
+            Track::Weight(_) => continue,
+        };
+        used += sizes[i];
+    }
+
+    let Some(total) = total else {
+        return sizes;
+    };
+    let weight = |track: &Track| match *track {
+        Track::Weight(weight) => weight,
+        _ => 0,
+    };
+    let total_weight: usize = tracks.iter().map(weight).sum();
+    if total_weight == 0 {
+        return sizes;
+    }
+    let leftover = total - used;
+    let mut left = leftover;
+    for (size, track) in sizes.iter_mut().zip(tracks) {
+        if weight(track) > 0 {
+            *size = leftover * weight(track) / total_weight;
+            left -= *size;
+        }
+    }
+    // Rounding leftovers, one each from the first weighted track.
+    for (size, track) in sizes.iter_mut().zip(tracks) {
+        if left == 0 {
+            break;
+        }
+        if weight(track) > 0 {
+            *size += 1;
+            left -= 1;
+        }
+    }
+    sizes
+}
+
+impl Component for Grid {
+    fn render(&self, width: u16) -> Vec<String> {
+        self.render_themed(width, &Theme::default())
+    }
+
+    fn render_themed(&self, width: u16, theme: &Theme) -> Vec<String> {
+        let Layout {
+            widths,
+            heights,
+            mut rendered,
+        } = self.layout(width, theme);
+        let cols = self.columns.len();
+        let mut lines = Vec::new();
+        for (row, &height) in heights.iter().enumerate() {
+            let cells: Vec<Vec<String>> = (0..cols)
+                .map(|col| match self.cell(row, col) {
+                    Some(cell) => rendered[row * cols + col]
+                        .take()
+                        .unwrap_or_else(|| cell.render_themed(widths[col] as u16, theme)),
+                    None => Vec::new(),
+                })
+                .collect();
+            for y in 0..height {
+                let mut line = String::new();
+                for (cell, &col_width) in cells.iter().zip(&widths) {
+                    let text = cell.get(y).map(String::as_str).unwrap_or("");
+                    let text = truncate_to_width(text, col_width, "");
+                    let pad = col_width - visible_width(&text);
+                    line.push_str(&text);
+                    if text.contains('\x1b') {
+                        line.push_str("\x1b[0m");
+                    }
+                    line.push_str(&" ".repeat(pad));
+                }
+                lines.push(line);
+            }
+        }
+        if let Some(height) = self.height {
+            lines.truncate(height);
+        }
+        lines
+    }
+
+    fn handle_input(&mut self, event: &KeyEvent) {
+        self.focus.handle_input(&mut self.cells, event);
+    }
+
+    fn invalidate(&mut self) {
+        for cell in self.cells.iter_mut().flatten() {
+            cell.invalidate();
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        self.focus.index().is_some()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focus.set_focused(&mut self.cells, focused);
+    }
+
+    fn focus_child(&mut self, index: Option<usize>) {
+        self.focus.set(&mut self.cells, index);
+    }
+
+    fn desired_height(&self, width: u16) -> Option<usize> {
+        let height = self.height?;
+        let rows: usize = self.layout(width, &Theme::default()).heights.iter().sum();
+        Some(rows.min(height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Input;
+    use crate::utils::strip_ansi;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    /// Renders fixed lines regardless of width, without a desired height.
+    struct Stub(Vec<&'static str>);
+
+    impl Component for Stub {
+        fn render(&self, _width: u16) -> Vec<String> {
+            self.0.iter().map(|s| s.to_string()).collect()
+        }
+    }
+
+    fn stub(lines: &[&'static str]) -> Box<dyn Component> {
+        Box::new(Stub(lines.to_vec()))
+    }
+
+    #[test]
+    fn two_by_two_cells_land_in_their_quadrants() {
+        let mut grid = Grid::new(
+            vec![Track::Weight(1), Track::Weight(1)],
+            vec![Track::Auto, Track::Auto],
+        );
+        grid.set_cell(0, 0, stub(&["TL", "tl"]));
+        grid.set_cell(0, 1, stub(&["TR"]));
+        grid.set_cell(1, 0, stub(&["BL"]));
+        grid.set_cell(1, 1, stub(&["BR", "br", "b"]));
+        assert_eq!(
+            grid.render(10),
+            vec![
+                "TL   TR   ",
+                "tl        ",
+                "BL   BR   ",
+                "     br   ",
+                "     b    ",
+            ]
+        );
+    }
+
+    #[test]
+    fn fixed_auto_and_weighted_columns() {
+        let mut grid = Grid::new(
+            vec![Track::Fixed(3), Track::Auto, Track::Weight(2), Track::Weight(1)],
+            vec![Track::Auto],
+        );
+        grid.set_cell(0, 0, stub(&["a"]));
+        grid.set_cell(0, 1, stub(&["auto"]));
+        grid.set_cell(0, 2, stub(&["w2"]));
+        grid.set_cell(0, 3, stub(&["w1"]));
+        // 20 - 3 - 4 = 13 left: 8 and 4, the odd column to the first.
+        assert_eq!(grid.render(20), vec!["a  autow2       w1  "]);
+    }
+
+    #[test]
+    fn empty_cells_render_blank() {
+        let mut grid = Grid::new(vec![Track::Fixed(2), Track::Fixed(2)], vec![Track::Fixed(1)]);
+        grid.set_cell(0, 1, stub(&["x"]));
+        assert_eq!(grid.render(10), vec!["  x "]);
+        assert_eq!(grid.take_cell(0, 1).unwrap().render(1), vec!["x"]);
+        assert_eq!(grid.render(10), vec!["    "]);
+    }
+
+    #[test]
+    fn cells_are_clipped_to_their_box() {
+        let mut grid = Grid::new(vec![Track::Fixed(3), Track::Fixed(2)], vec![Track::Fixed(1)]);
+        grid.set_cell(0, 0, stub(&["abcdef", "second"]));
+        grid.set_cell(0, 1, stub(&["\x1b[31mred\x1b[0m"]));
+        let lines = grid.render(10);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(strip_ansi(&lines[0]), "abcre");
+        // The cut-off red is closed before the line goes on.
+        assert!(lines[0].ends_with("\x1b[31mre\x1b[0m"));
+    }
+
+    #[test]
+    fn weighted_rows_share_set_height() {
+        let mut grid = Grid::new(
+            vec![Track::Weight(1)],
+            vec![Track::Fixed(1), Track::Weight(1), Track::Weight(1)],
+        );
+        grid.set_cell(0, 0, stub(&["header"]));
+        grid.set_cell(1, 0, stub(&["top"]));
+        grid.set_cell(2, 0, stub(&["bottom"]));
+        grid.set_height(6);
+        let lines = grid.render(8);
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "header  ");
+        assert_eq!(lines[1], "top     ");
+        assert_eq!(lines[4], "bottom  ");
+        assert_eq!(grid.desired_height(8), Some(6));
+    }
+
+    #[test]
+    fn auto_rows_use_desired_height() {
+        let mut grid = Grid::new(vec![Track::Weight(1)], vec![Track::Auto, Track::Auto]);
+        grid.set_cell(0, 0, Box::new(crate::components::Spacer::new(2)));
+        grid.set_cell(1, 0, stub(&["below"]));
+        assert_eq!(grid.render(5), vec!["     ", "     ", "below"]);
+    }
+
+    #[test]
+    fn input_goes_to_focused_cell() {
+        let mut grid = Grid::new(vec![Track::Auto, Track::Weight(1)], vec![Track::Auto; 2]);
+        assert!(!grid.focusable());
+        grid.set_cell(0, 0, stub(&["user: "]));
+        grid.set_cell(0, 1, Box::new(Input::new()));
+        grid.set_cell(1, 0, stub(&["pass: "]));
+        grid.set_cell(1, 1, Box::new(Input::new()));
+        assert!(grid.focusable());
+        grid.set_focused(true);
+        grid.handle_input(&key(KeyCode::Char('a')));
+        grid.focus_child(Some(3));
+        grid.handle_input(&key(KeyCode::Char('b')));
+        assert_eq!(grid.cell_mut(0, 1).unwrap().form_value().as_deref(), Some("a"));
+        assert_eq!(grid.cell_mut(1, 1).unwrap().form_value().as_deref(), Some("b"));
+
+        grid.set_tab_cycles_focus(true);
+        grid.handle_input(&key(KeyCode::Tab));
+        grid.handle_input(&key(KeyCode::Char('c')));
+        assert_eq!(grid.cell_mut(0, 1).unwrap().form_value().as_deref(), Some("ac"));
+
+        grid.take_cell(0, 1);
+        grid.handle_input(&key(KeyCode::Char('d')));
+        assert_eq!(grid.cell_mut(1, 1).unwrap().form_value().as_deref(), Some("bd"));
+    }
+}
@@ -0,0 +1,290 @@
+// HBox component — lays children out side by side.
+
+use crossterm::event::KeyEvent;
+
+use crate::component::{Component, Focus, Slot};
+use crate::theme::Theme;
+use crate::utils::{truncate_to_width, visible_width};
+
+/// A horizontal layout: children render left to right as columns.
+///
+/// Each child takes its natural width — the widest line it renders when given
+/// the width still available. Children added with
+/// [`add_flex_child`](Self::add_flex_child) split whatever width is left
+/// over in proportion to their weights, earlier ones getting the odd
+/// columns. Columns are padded to their width and rows to the tallest child,
+/// so every line spans the full width whenever a flex child is present. To
+/// push siblings apart, add a blank flex child:
+/// `hbox.add_flex_child(Box::new(Spacer::new(1)), 1)`.
+///
+/// Children that pad their lines to the full width they're given (like `Input`)
+/// take all remaining space; place them last.
+///
+/// Key input and focus go to the focused child: the first focusable one
+/// added, until `focus_child` picks another.
+pub struct HBox {
+    /// Each child, and its share of the leftover width; 0 means natural width.
+    children: Vec<(Box<dyn Component>, usize)>,
+    /// Which child receives key input.
+    focus: Focus,
+}
+
+impl HBox {
+    /// Create an empty HBox.
+    pub fn new() -> Self {
+        HBox {
+            children: Vec::new(),
+            focus: Focus::new(),
+        }
+    }
+
+    /// Make Tab / Shift+Tab move focus to the next / previous focusable
+    /// child (wrapping) instead of forwarding the key. Off by default.
+    pub fn set_tab_cycles_focus(&mut self, enabled: bool) {
+        self.focus.set_tab_cycles(enabled);
+    }
+
+    /// Add a child at its natural width to the right of the existing ones.
+    pub fn add_child(&mut self, child: Box<dyn Component>) {
+        self.add_flex_child(child, 0);
+    }
+
+    /// Add a child to the right of the existing ones that takes `weight`
+    /// shares of the leftover width instead of its natural width. A weight
+    /// of 0 is the same as [`add_child`](Self::add_child).
+    pub fn add_flex_child(&mut self, child: Box<dyn Component>, weight: usize) {
+        self.children.push((child, weight));
+        self.focus.offer(&self.children, self.children.len() - 1);
+    }
+
+    /// Remove the child at the given index. Panics if out of bounds.
+    ///
+    /// Removing the focused child moves focus to the next focusable one.
+    pub fn remove_child(&mut self, index: usize) -> Box<dyn Component> {
+        let (mut child, _) = self.children.remove(index);
+        self.focus.removed(&mut self.children, index, child.as_mut());
+        child
+    }
+
+    /// Get a mutable reference to the child at `index`.
+    pub fn child_mut(&mut self, index: usize) -> Option<&mut Box<dyn Component>> {
+        self.children.get_mut(index).map(|(child, _)| child)
+    }
+}
+
+/// An `HBox` child and its flex weight.
+impl Slot for (Box<dyn Component>, usize) {
+    fn component(&self) -> Option<&dyn Component> {
+        Some(self.0.as_ref())
+    }
+
+    fn component_mut(&mut self) -> Option<&mut Box<dyn Component>> {
+        Some(&mut self.0)
+    }
+}
+
+impl Default for HBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for HBox {
+    fn render(&self, width: u16) -> Vec<String> {
+        self.render_themed(width, &Theme::default())
+    }
+
+    fn render_themed(&self, width: u16, theme: &Theme) -> Vec<String> {
+        let width = width as usize;
+
+        // Fixed children first, left to right, each capped by what's left.
+        let mut columns: Vec<Option<(usize, Vec<String>)>> = Vec::new();
+        let mut used = 0;
+        for (child, weight) in &self.children {
+            if *weight > 0 {
+                columns.push(None);
+                continue;
+            }
+            let lines = child.render_themed((width - used) as u16, theme);
+            let col_width = lines
+                .iter()
+                .map(|l| visible_width(l))
+                .max()
+                .unwrap_or(0)
+                .min(width - used);
+            used += col_width;
+            columns.push(Some((col_width, lines)));
+        }
+
+        // Flex children share the leftover by weight; the columns lost to
+        // rounding go to the earliest ones.
+        let total: usize = self.children.iter().map(|(_, weight)| weight).sum();
+        let leftover = width - used;
+        let mut odd = leftover
+            - self
+                .children
+                .iter()
+                .map(|(_, weight)| leftover * weight / total.max(1))
+                .sum::<usize>();
+        let columns: Vec<(usize, Vec<String>)> = columns
+            .into_iter()
+            .zip(&self.children)
+            .map(|(column, (child, weight))| {
+                column.unwrap_or_else(|| {
+                    let extra = usize::from(odd > 0);
+                    odd -= extra;
+                    let share = leftover * weight / total + extra;
+                    (share, child.render_themed(share as u16, theme))
+                })
+            })
+            .collect();
+
+        let height = columns.iter().map(|(_, lines)| lines.len()).max().unwrap_or(0);
+        (0..height)
+            .map(|row| {
+                let mut line = String::new();
+                for (col_width, lines) in &columns {
+                    let cell = lines.get(row).map(String::as_str).unwrap_or("");
+                    let cell = truncate_to_width(cell, *col_width, "");
+                    let pad = col_width - visible_width(&cell);
+                    line.push_str(&cell);
+                    line.push_str(&" ".repeat(pad));
+                }
+                line
+            })
+            .collect()
+    }
+
+    fn handle_input(&mut self, event: &KeyEvent) {
+        self.focus.handle_input(&mut self.children, event);
+    }
+
+    fn invalidate(&mut self) {
+        for (child, _) in &mut self.children {
+            child.invalidate();
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        self.focus.index().is_some()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focus.set_focused(&mut self.children, focused);
+    }
+
+    fn focus_child(&mut self, index: Option<usize>) {
+        self.focus.set(&mut self.children, index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Input, Spacer};
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    /// Returns fixed lines regardless of width.
+    struct Fixed(Vec<&'static str>);
+
+    impl Component for Fixed {
+        fn render(&self, _width: u16) -> Vec<String> {
+            self.0.iter().map(|s| s.to_string()).collect()
+        }
+    }
+
+    #[test]
+    fn children_placed_side_by_side_at_natural_width() {
+        let mut hbox = HBox::new();
+        hbox.add_child(Box::new(Fixed(vec!["ab"])));
+        hbox.add_child(Box::new(Spacer::horizontal(3)));
+        hbox.add_child(Box::new(Fixed(vec!["cd"])));
+        assert_eq!(hbox.render(40), vec!["ab   cd"]);
+    }
+
+    #[test]
+    fn flex_spacer_pushes_siblings_apart() {
+        let mut hbox = HBox::new();
+        hbox.add_child(Box::new(Fixed(vec!["left"])));
+        hbox.add_flex_child(Box::new(Spacer::new(1)), 1);
+        hbox.add_child(Box::new(Fixed(vec!["right"])));
+        let lines = hbox.render(20);
+        assert_eq!(lines, vec!["left           right"]);
+        assert_eq!(visible_width(&lines[0]), 20);
+    }
+
+    #[test]
+    fn multiple_flex_spacers_split_leftover() {
+        let mut hbox = HBox::new();
+        hbox.add_flex_child(Box::new(Spacer::new(1)), 1);
+        hbox.add_child(Box::new(Fixed(vec!["mid"])));
+        hbox.add_flex_child(Box::new(Spacer::new(1)), 1);
+        // 10 - 3 = 7 leftover: 4 left, 3 right
+        assert_eq!(hbox.render(10), vec!["    mid   "]);
+    }
+
+    #[test]
+    fn rows_padded_to_tallest_child() {
+        let mut hbox = HBox::new();
+        hbox.add_child(Box::new(Fixed(vec!["a", "bbb"])));
+        hbox.add_child(Box::new(Spacer::horizontal(1)));
+        hbox.add_child(Box::new(Fixed(vec!["x"])));
+        assert_eq!(hbox.render(40), vec!["a   x", "bbb  "]);
+    }
+
+    #[test]
+    fn overflowing_children_are_clipped() {
+        let mut hbox = HBox::new();
+        hbox.add_child(Box::new(Fixed(vec!["abcdef"])));
+        hbox.add_child(Box::new(Fixed(vec!["ghij"])));
+        hbox.add_flex_child(Box::new(Spacer::new(1)), 1);
+        assert_eq!(hbox.render(8), vec!["abcdefgh"]);
+    }
+
+    #[test]
+    fn empty_hbox_renders_nothing() {
+        assert!(HBox::new().render(40).is_empty());
+    }
+
+    #[test]
+    fn flex_children_split_leftover_by_weight() {
+        let mut hbox = HBox::new();
+        hbox.add_flex_child(Box::new(Fixed(vec!["a"])), 1);
+        hbox.add_child(Box::new(Fixed(vec!["|"])));
+        hbox.add_flex_child(Box::new(Fixed(vec!["b"])), 2);
+        // 11 - 1 = 10 leftover: 3 and 6 by weight, the odd column to the first
+        assert_eq!(hbox.render(11), vec!["a   |b     "]);
+    }
+
+    #[test]
+    fn input_goes_to_focused_child() {
+        let mut hbox = HBox::new();
+        hbox.add_child(Box::new(Fixed(vec!["name: "])));
+        hbox.add_child(Box::new(Input::new()));
+        hbox.add_child(Box::new(Input::new()));
+        assert!(hbox.focusable());
+        hbox.set_focused(true);
+        hbox.handle_input(&key(KeyCode::Char('a')));
+        hbox.focus_child(Some(2));
+        hbox.handle_input(&key(KeyCode::Char('b')));
+        assert_eq!(hbox.child_mut(1).unwrap().form_value().as_deref(), Some("a"));
+        assert_eq!(hbox.child_mut(2).unwrap().form_value().as_deref(), Some("b"));
+
+        hbox.set_tab_cycles_focus(true);
+        hbox.handle_input(&key(KeyCode::Tab));
+        hbox.handle_input(&key(KeyCode::Char('c')));
+        assert_eq!(hbox.child_mut(1).unwrap().form_value().as_deref(), Some("ac"));
+    }
+
+    #[test]
+    fn not_focusable_without_focusable_children() {
+        let mut hbox = HBox::new();
+        hbox.add_child(Box::new(Fixed(vec!["a"])));
+        hbox.add_flex_child(Box::new(Spacer::new(1)), 1);
+        assert!(!hbox.focusable());
+    }
+}
@@ -0,0 +1,178 @@
+// HScrollable component — a horizontal viewport onto content wider than the
+// screen.
+
+use std::cell::Cell;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::component::Component;
+use crate::theme::Theme;
+use crate::utils::{slice_columns, visible_width};
+
+/// Shows a window of `width` columns onto a child rendered wider, for
+/// tables and other content that shouldn't be wrapped or truncated.
+///
+/// The child renders at the fixed `content_width` given to [`new`](Self::new);
+/// each line is then cut to the visible columns with
+/// [`slice_columns`](crate::utils::slice_columns), keeping its styling. Left /
+/// Right scroll by one column, Home / End jump to either edge; other keys go
+/// to the child. The offset is clamped at render time so the last column
+/// never scrolls past the right edge of the viewport.
+pub struct HScrollable {
+    child: Box<dyn Component>,
+    content_width: u16,
+    /// First visible column. A `Cell` so `render(&self)` can clamp it.
+    col_offset: Cell<usize>,
+}
+
+impl HScrollable {
+    /// Wrap `child`, rendering it `content_width` columns wide.
+    pub fn new(child: Box<dyn Component>, content_width: u16) -> Self {
+        HScrollable {
+            child,
+            content_width,
+            col_offset: Cell::new(0),
+        }
+    }
+
+    /// The first visible column, as of the last render.
+    pub fn col_offset(&self) -> usize {
+        self.col_offset.get()
+    }
+
+    /// Scroll so `col` is the first visible column. Clamped on the next
+    /// render.
+    pub fn set_col_offset(&mut self, col: usize) {
+        self.col_offset.set(col);
+    }
+
+    /// Get a mutable reference to the wrapped component.
+    pub fn child_mut(&mut self) -> &mut Box<dyn Component> {
+        &mut self.child
+    }
+}
+
+impl Component for HScrollable {
+    fn render(&self, width: u16) -> Vec<String> {
+        self.render_themed(width, &Theme::default())
+    }
+
+    fn render_themed(&self, width: u16, theme: &Theme) -> Vec<String> {
+        let width = width as usize;
+        let lines = self.child.render_themed(self.content_width, theme);
+        let widest = lines.iter().map(|line| visible_width(line)).max().unwrap_or(0);
+        let offset = self.col_offset.get().min(widest.saturating_sub(width));
+        self.col_offset.set(offset);
+
+        lines
+            .iter()
+            .map(|line| {
+                let mut visible = slice_columns(line, offset, offset + width);
+                let pad = width - visible_width(&visible);
+                if visible.contains('\x1b') {
+                    visible.push_str("\x1b[0m");
+                }
+                visible.push_str(&" ".repeat(pad));
+                visible
+            })
+            .collect()
+    }
+
+    fn handle_input(&mut self, event: &KeyEvent) {
+        let offset = self.col_offset.get();
+        match event.code {
+            KeyCode::Left => self.col_offset.set(offset.saturating_sub(1)),
+            KeyCode::Right => self.col_offset.set(offset.saturating_add(1)),
+            KeyCode::Home => self.col_offset.set(0),
+            KeyCode::End => self.col_offset.set(usize::MAX),
+            _ => self.child.handle_input(event),
+        }
+    }
+
+    fn invalidate(&mut self) {
+        self.child.invalidate();
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.child.set_focused(focused);
+    }
+
+    fn is_single_line(&self) -> bool {
+        self.child.is_single_line()
+    }
+
+    fn desired_height(&self, _width: u16) -> Option<usize> {
+        self.child.desired_height(self.content_width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Text;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    /// 120 columns: "000000000011111111112222…", column `c` showing `c / 10`.
+    fn wide_line() -> String {
+        (0..120).map(|col| char::from(b'0' + (col / 10) as u8)).collect()
+    }
+
+    #[test]
+    fn shows_the_columns_from_the_offset() {
+        let line = wide_line();
+        let mut view = HScrollable::new(Box::new(Text::new(&line, 0, 0)), 120);
+        assert_eq!(view.render(40), vec![line[0..40].to_string()]);
+
+        view.set_col_offset(25);
+        assert_eq!(view.render(40), vec![line[25..65].to_string()]);
+        assert_eq!(view.col_offset(), 25);
+    }
+
+    #[test]
+    fn keys_scroll_and_clamp_at_the_edges() {
+        let line = wide_line();
+        let mut view = HScrollable::new(Box::new(Text::new(&line, 0, 0)), 120);
+        view.render(40);
+
+        view.handle_input(&key(KeyCode::Right));
+        view.handle_input(&key(KeyCode::Right));
+        assert_eq!(view.render(40), vec![line[2..42].to_string()]);
+        view.handle_input(&key(KeyCode::Left));
+        assert_eq!(view.render(40), vec![line[1..41].to_string()]);
+
+        view.handle_input(&key(KeyCode::End));
+        assert_eq!(view.render(40), vec![line[80..120].to_string()]);
+        assert_eq!(view.col_offset(), 80);
+        // Already at the right edge.
+        view.handle_input(&key(KeyCode::Right));
+        assert_eq!(view.render(40), vec![line[80..120].to_string()]);
+
+        view.handle_input(&key(KeyCode::Home));
+        assert_eq!(view.render(40), vec![line[0..40].to_string()]);
+        view.handle_input(&key(KeyCode::Left));
+        assert_eq!(view.col_offset(), 0);
+    }
+
+    #[test]
+    fn wide_char_at_left_edge_renders_as_space() {
+        let mut view = HScrollable::new(Box::new(Text::new("a你好世界", 0, 0)), 9);
+        view.set_col_offset(2);
+        assert_eq!(view.render(4), vec![" 好 "]);
+    }
+
+    #[test]
+    fn styles_carry_into_the_viewport_and_are_closed() {
+        let text = "\x1b[32mgreen text\x1b[0m";
+        let mut view = HScrollable::new(Box::new(Text::new(text, 0, 0)), 10);
+        view.set_col_offset(6);
+        assert_eq!(view.render(3), vec!["\x1b[32mtex\x1b[0m"]);
+    }
+}
@@ -7,11 +7,24 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use unicode_width::UnicodeWidthChar;
 
 use crate::component::Component;
+use crate::theme::Theme;
+use crate::utils::{strip_ansi, visible_width};
 
 /// A single-line text input component with cursor, editing, and horizontal scrolling.
 ///
-/// Displays a `"> "` prompt followed by the current text. When focused, shows an
-/// inverse-video cursor at the cursor position. Supports basic Emacs-style keybindings.
+/// Displays a prompt (`"> "` unless changed with [`set_prompt`](Self::set_prompt))
+/// followed by the current text. When focused, shows an inverse-video cursor at the
+/// cursor position. Supports basic Emacs-style keybindings.
+///
+/// Shift with Left/Right/Home/End (and Ctrl+Shift+Left/Right) selects text
+/// from an anchor; the selection renders in inverse video in place of the
+/// cursor. Typing, Tab, yank, Backspace and Delete replace the selection,
+/// Ctrl+C copies it to the kill ring and Ctrl+X cuts it there. Any other
+/// key clears it.
+///
+/// With a [completer](Self::set_completer), Tab completes the word before
+/// the cursor: a single suggestion is accepted, several are left in
+/// [`completions`](Self::completions) for the app to list.
 pub struct Input {
     /// The current text content.
     buffer: String,
@@ -26,10 +39,87 @@ pub struct Input {
     pub on_submit: Option<Box<dyn FnMut(&str)>>,
     /// Callback invoked when Escape is pressed.
     pub on_escape: Option<Box<dyn FnMut()>>,
+    /// Text removed by the most recent kill (Ctrl+K/U/W), pasted by Ctrl+Y.
+    killed: String,
+    /// Direction of the kill made by the previous key, if that key was a kill.
+    /// Consecutive kills in the same direction accumulate into `killed`.
+    last_kill: Option<KillDirection>,
+    /// Typed characters for which this returns false are dropped.
+    char_filter: Option<CharFilter>,
+    /// When this returns false for the current value, the prompt renders red.
+    validator: Option<Validator>,
+    /// Maximum number of characters the value may hold.
+    max_length: Option<usize>,
+    /// Spaces a Tab inserts; `None` leaves Tab unhandled.
+    tab_width: Option<usize>,
+    /// Where a Shift+movement selection started (character index). The
+    /// selection spans from here to the cursor.
+    selection_anchor: Option<usize>,
+    /// What word movement and word deletion treat as a word.
+    word_mode: WordMode,
+    /// Drawn before the text; may contain ANSI styling.
+    prompt: String,
+    /// Visible columns `prompt` takes up.
+    prompt_width: usize,
+    /// Suggests replacements for the word before the cursor on Tab.
+    completer: Option<Completer>,
+    /// Suggestions from the last Tab that matched more than once; empty
+    /// when no list is open.
+    completions: Vec<String>,
+}
+
+/// Where word movement (Ctrl+Left/Right) and word deletion (Ctrl+W,
+/// Ctrl+Backspace) find word boundaries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WordMode {
+    /// Words are runs of anything but spaces, so `foo.bar/baz` is one word.
+    #[default]
+    Whitespace,
+    /// Like readline and vim: words are runs of alphanumerics and `_`, and
+    /// each run of other non-space characters is a word of its own, so
+    /// `foo.bar` is three words.
+    Punctuation,
+}
+
+/// The kind of character a word is made of, under some `WordMode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Space,
+    Word,
+    Punct,
+}
+
+impl WordMode {
+    fn class(self, c: char) -> CharClass {
+        match self {
+            WordMode::Whitespace if c == ' ' => CharClass::Space,
+            WordMode::Whitespace => CharClass::Word,
+            WordMode::Punctuation if c.is_whitespace() => CharClass::Space,
+            WordMode::Punctuation if c.is_alphanumeric() || c == '_' => CharClass::Word,
+            WordMode::Punctuation => CharClass::Punct,
+        }
+    }
+}
+
+/// Predicate deciding whether a typed character is accepted.
+pub type CharFilter = Box<dyn Fn(char) -> bool>;
+
+/// Predicate deciding whether the whole input value is valid.
+pub type Validator = Box<dyn Fn(&str) -> bool>;
+
+/// Suggests replacements for the word before the cursor, given that word.
+pub type Completer = Box<dyn Fn(&str) -> Vec<String>>;
+
+/// Which side of the cursor a kill removed text from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KillDirection {
+    /// Ctrl+K: text after the cursor. Appended to the kill ring.
+    Forward,
+    /// Ctrl+U / Ctrl+W: text before the cursor. Prepended to the kill ring.
+    Backward,
 }
 
 const PROMPT: &str = "> ";
-const PROMPT_WIDTH: usize = 2;
 
 impl Input {
     /// Create a new empty Input.
@@ -41,19 +131,203 @@ impl Input {
             scroll_offset: Cell::new(0),
             on_submit: None,
             on_escape: None,
+            killed: String::new(),
+            last_kill: None,
+            char_filter: None,
+            validator: None,
+            max_length: None,
+            tab_width: None,
+            selection_anchor: None,
+            word_mode: WordMode::default(),
+            prompt: PROMPT.to_string(),
+            prompt_width: visible_width(PROMPT),
+            completer: None,
+            completions: Vec::new(),
         }
     }
 
+    /// Replace the `"> "` prompt. It may be styled with ANSI codes (close
+    /// any it opens) and be any number of columns wide, including none.
+    /// When the validator rejects the value, it's drawn in the theme's error
+    /// style (red) without its own styling.
+    pub fn set_prompt(&mut self, prompt: &str) {
+        self.prompt = prompt.to_string();
+        self.prompt_width = visible_width(prompt);
+    }
+
+    /// Restrict which characters can be typed. `None` accepts everything.
+    ///
+    /// Only applies to key input — `set_value()` and yank are not filtered.
+    pub fn set_filter(&mut self, filter: Option<CharFilter>) {
+        self.char_filter = filter;
+    }
+
+    /// Validate the whole value on every render; an invalid value turns the
+    /// prompt red. `None` treats every value as valid.
+    pub fn set_validator(&mut self, validator: Option<Validator>) {
+        self.validator = validator;
+    }
+
+    /// Cap the value at `max` characters (not bytes or columns). Typing and
+    /// yanking stop at the cap; a longer current value is truncated.
+    /// `None` removes the cap.
+    pub fn set_max_length(&mut self, max: Option<usize>) {
+        self.max_length = max;
+        if let Some(max) = max {
+            if self.char_count() > max {
+                self.buffer.truncate(self.char_to_byte(max));
+                self.cursor = self.cursor.min(max);
+                self.selection_anchor = self.selection_anchor.map(|anchor| anchor.min(max));
+            }
+        }
+    }
+
+    /// Make Tab insert `width` spaces. With `None` (the default) the input
+    /// ignores Tab, leaving it for focus navigation in a parent such as
+    /// `Form`.
+    pub fn set_tab_width(&mut self, width: Option<usize>) {
+        self.tab_width = width;
+    }
+
+    /// Complete the word before the cursor on Tab. The word runs back to
+    /// the previous space, whatever the word boundary, so paths and flags
+    /// complete whole. `None` (the default) turns completion off.
+    ///
+    /// A completer takes Tab over from [`set_tab_width`](Self::set_tab_width).
+    pub fn set_completer(&mut self, completer: Option<Completer>) {
+        self.completer = completer;
+        self.completions.clear();
+    }
+
+    /// The suggestions on offer, after a Tab that matched more than once.
+    ///
+    /// They follow the word as it's edited and close when it's completed,
+    /// on Escape, or when nothing matches any more. To show them, put a
+    /// `SelectList` of them in an overlay offset by
+    /// [`completion_column`](Self::completion_column) and pass the chosen
+    /// index to [`accept_completion`](Self::accept_completion).
+    pub fn completions(&self) -> &[String] {
+        &self.completions
+    }
+
+    /// Replace the word before the cursor with `completions()[index]`,
+    /// leaving the cursor after it, and close the list. Does nothing if
+    /// `index` is out of range. The replacement is cut short at the max
+    /// length, if one is set.
+    pub fn accept_completion(&mut self, index: usize) {
+        let Some(completion) = self.completions.get(index) else {
+            return;
+        };
+        let start = self.word_start();
+        let room = self.room().saturating_add(self.cursor - start);
+        let completion: String = completion.chars().take(room).collect();
+        let (start_byte, end_byte) = (self.char_to_byte(start), self.char_to_byte(self.cursor));
+        self.buffer.replace_range(start_byte..end_byte, &completion);
+        self.cursor = start + completion.chars().count();
+        self.selection_anchor = None;
+        self.completions.clear();
+    }
+
+    /// Column, counted from the input's left edge, where the word being
+    /// completed starts as of the last render. Line a completion list up
+    /// under it.
+    pub fn completion_column(&self) -> usize {
+        let chars: Vec<char> = self.buffer.chars().collect();
+        let offset = self.scroll_offset.get().min(chars.len());
+        let start = self.word_start().max(offset);
+        self.prompt_width + chars_col_width(&chars[offset..start])
+    }
+
+    /// Character index where the word before the cursor starts.
+    fn word_start(&self) -> usize {
+        let before = &self.buffer[..self.char_to_byte(self.cursor)];
+        before
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| c.is_whitespace())
+            .map_or(0, |(i, c)| before[..i + c.len_utf8()].chars().count())
+    }
+
+    /// Ask the completer about the word before the cursor. A single
+    /// suggestion is accepted unless `list_only`; otherwise the suggestions
+    /// become `completions`.
+    fn complete(&mut self, list_only: bool) {
+        let Some(ref completer) = self.completer else {
+            return;
+        };
+        let start = self.char_to_byte(self.word_start());
+        let word = &self.buffer[start..self.char_to_byte(self.cursor)];
+        self.completions = completer(word);
+        if self.completions.len() == 1 && !list_only {
+            self.accept_completion(0);
+        }
+    }
+
+    /// Set what word movement and word deletion treat as a word.
+    pub fn set_word_boundary(&mut self, mode: WordMode) {
+        self.word_mode = mode;
+    }
+
+    /// Characters that can still be added before reaching the cap.
+    fn room(&self) -> usize {
+        self.max_length
+            .map_or(usize::MAX, |max| max.saturating_sub(self.char_count()))
+    }
+
+    /// Whether the filter lets `c` be typed (true if there is none).
+    fn accepts(&self, c: char) -> bool {
+        self.char_filter.as_ref().is_none_or(|accept| accept(c))
+    }
+
+    /// Whether the current value passes the validator (true if there is none).
+    pub fn is_valid(&self) -> bool {
+        self.validator.as_ref().is_none_or(|valid| valid(&self.buffer))
+    }
+
     /// Get the current text content.
     pub fn value(&self) -> &str {
         &self.buffer
     }
 
-    /// Set the text content and reset cursor to the end.
+    /// Set the text content and reset cursor to the end. Truncated to the
+    /// max length, if one is set.
     pub fn set_value(&mut self, s: &str) {
-        self.buffer = s.to_string();
+        self.buffer = match self.max_length {
+            Some(max) => s.chars().take(max).collect(),
+            None => s.to_string(),
+        };
         self.cursor = self.char_count();
         self.scroll_offset.set(0);
+        self.selection_anchor = None;
+        self.completions.clear();
+    }
+
+    /// The selected character range, if any text is selected.
+    pub fn selection(&self) -> Option<std::ops::Range<usize>> {
+        let anchor = self.selection_anchor?;
+        (anchor != self.cursor).then(|| anchor.min(self.cursor)..anchor.max(self.cursor))
+    }
+
+    /// The selected text (empty if nothing is selected).
+    pub fn selected_text(&self) -> &str {
+        match self.selection() {
+            Some(range) => {
+                &self.buffer[self.char_to_byte(range.start)..self.char_to_byte(range.end)]
+            }
+            None => "",
+        }
+    }
+
+    /// Remove the selected text, leaving the cursor where it started.
+    /// Returns the removed text.
+    fn delete_selection(&mut self) -> String {
+        let Some(range) = self.selection() else {
+            return String::new();
+        };
+        let (start, end) = (self.char_to_byte(range.start), self.char_to_byte(range.end));
+        self.cursor = range.start;
+        self.selection_anchor = None;
+        self.buffer.drain(start..end).collect()
     }
 
     /// Number of characters in the buffer.
@@ -70,13 +344,31 @@ impl Input {
             .unwrap_or(self.buffer.len())
     }
 
-    /// Insert a character at the current cursor position.
+    /// Insert a character at the current cursor position. Does nothing if
+    /// the value is at its max length.
     fn insert_char(&mut self, c: char) {
+        if self.room() == 0 {
+            return;
+        }
         let byte_pos = self.char_to_byte(self.cursor);
         self.buffer.insert(byte_pos, c);
         self.cursor += 1;
     }
 
+    /// Insert `tab_width` spaces, if set and the filter accepts spaces.
+    fn insert_tab(&mut self) {
+        let Some(width) = self.tab_width else {
+            return;
+        };
+        if !self.accepts(' ') {
+            return;
+        }
+        self.delete_selection();
+        for _ in 0..width {
+            self.insert_char(' ');
+        }
+    }
+
     /// Delete the character before the cursor (backspace).
     fn delete_backward(&mut self) {
         if self.cursor > 0 {
@@ -97,30 +389,88 @@ impl Input {
         }
     }
 
-    /// Delete the word before the cursor (Ctrl+Backspace).
-    fn delete_word_backward(&mut self) {
+    /// Delete the word before the cursor (Ctrl+Backspace / Ctrl+W).
+    /// Returns the removed text.
+    fn delete_word_backward(&mut self) -> String {
         if self.cursor == 0 {
-            return;
+            return String::new();
         }
         let old_cursor = self.cursor;
         self.move_word_backward();
         let new_cursor = self.cursor;
         let start_byte = self.char_to_byte(new_cursor);
         let end_byte = self.char_to_byte(old_cursor);
-        self.buffer.drain(start_byte..end_byte);
+        self.buffer.drain(start_byte..end_byte).collect()
     }
 
-    /// Delete from cursor to start of line (Ctrl+U).
-    fn delete_to_start(&mut self) {
+    /// Delete from cursor to start of line (Ctrl+U). Returns the removed text.
+    fn delete_to_start(&mut self) -> String {
         let byte_pos = self.char_to_byte(self.cursor);
-        self.buffer.drain(..byte_pos);
         self.cursor = 0;
+        self.buffer.drain(..byte_pos).collect()
+    }
+
+    /// Delete from cursor to end of line (Ctrl+K). Returns the removed text.
+    fn delete_to_end(&mut self) -> String {
+        let byte_pos = self.char_to_byte(self.cursor);
+        self.buffer.split_off(byte_pos)
+    }
+
+    /// Store killed text for a later yank.
+    ///
+    /// `previous` is the direction of the kill made by the previous key: if
+    /// it matches, the text joins the kill ring instead of replacing it. An
+    /// empty kill leaves the ring (and the chain) untouched.
+    fn kill(&mut self, text: String, direction: KillDirection, previous: Option<KillDirection>) {
+        if text.is_empty() {
+            self.last_kill = previous;
+            return;
+        }
+        if previous != Some(direction) {
+            self.killed = text;
+        } else if direction == KillDirection::Forward {
+            self.killed.push_str(&text);
+        } else {
+            self.killed.insert_str(0, &text);
+        }
+        self.last_kill = Some(direction);
     }
 
-    /// Delete from cursor to end of line (Ctrl+K).
-    fn delete_to_end(&mut self) {
+    /// Insert the most recently killed text at the cursor (Ctrl+Y), as much
+    /// of it as fits under the max length.
+    fn yank(&mut self) {
+        let text: String = self.killed.chars().take(self.room()).collect();
         let byte_pos = self.char_to_byte(self.cursor);
-        self.buffer.truncate(byte_pos);
+        self.buffer.insert_str(byte_pos, &text);
+        self.cursor += text.chars().count();
+    }
+
+    /// Swap the characters around the cursor and advance (Ctrl+T). At the
+    /// end of the line, swaps the last two characters instead. Does nothing
+    /// at the start of the line or with fewer than two characters.
+    fn transpose_chars(&mut self) {
+        let mut chars: Vec<char> = self.buffer.chars().collect();
+        if chars.len() < 2 || self.cursor == 0 {
+            return;
+        }
+        let at = self.cursor.min(chars.len() - 1);
+        chars.swap(at - 1, at);
+        self.buffer = chars.into_iter().collect();
+        self.cursor = at + 1;
+    }
+
+    /// Move cursor one character left (Left / Ctrl+B).
+    fn move_left(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+        }
+    }
+
+    /// Move cursor one character right (Right / Ctrl+F).
+    fn move_right(&mut self) {
+        if self.cursor < self.char_count() {
+            self.cursor += 1;
+        }
     }
 
     /// Move cursor one word backward (Ctrl+Left).
@@ -129,15 +479,19 @@ impl Input {
             return;
         }
         let chars: Vec<char> = self.buffer.chars().collect();
+        let class = |c| self.word_mode.class(c);
         let mut pos = self.cursor;
 
         // Skip any spaces before the cursor
-        while pos > 0 && chars[pos - 1] == ' ' {
+        while pos > 0 && class(chars[pos - 1]) == CharClass::Space {
             pos -= 1;
         }
-        // Skip non-space characters (the word)
-        while pos > 0 && chars[pos - 1] != ' ' {
-            pos -= 1;
+        // Skip the word: characters of the same class
+        if pos > 0 {
+            let word = class(chars[pos - 1]);
+            while pos > 0 && class(chars[pos - 1]) == word {
+                pos -= 1;
+            }
         }
         self.cursor = pos;
     }
@@ -145,20 +499,23 @@ impl Input {
     /// Move cursor one word forward (Ctrl+Right).
     fn move_word_forward(&mut self) {
         let chars: Vec<char> = self.buffer.chars().collect();
+        let class = |c| self.word_mode.class(c);
         let count = chars.len();
         let mut pos = self.cursor;
 
-        // Skip non-space characters (the word)
-        while pos < count && chars[pos] != ' ' {
-            pos += 1;
+        // Skip the word: characters of the same class
+        if pos < count && class(chars[pos]) != CharClass::Space {
+            let word = class(chars[pos]);
+            while pos < count && class(chars[pos]) == word {
+                pos += 1;
+            }
         }
         // Skip spaces after the word
-        while pos < count && chars[pos] == ' ' {
+        while pos < count && class(chars[pos]) == CharClass::Space {
             pos += 1;
         }
         self.cursor = pos;
     }
-
 }
 
 impl Default for Input {
@@ -169,11 +526,16 @@ impl Default for Input {
 
 impl Component for Input {
     fn render(&self, width: u16) -> Vec<String> {
+        self.render_themed(width, &Theme::default())
+    }
+
+    fn render_themed(&self, width: u16, theme: &Theme) -> Vec<String> {
         let total_width = width as usize;
-        if total_width <= PROMPT_WIDTH {
+        let prompt_width = self.prompt_width;
+        if total_width <= prompt_width {
             return vec![" ".repeat(total_width)];
         }
-        let available = total_width - PROMPT_WIDTH;
+        let available = total_width - prompt_width;
         let chars: Vec<char> = self.buffer.chars().collect();
 
         // Compute scroll offset using column widths
@@ -191,25 +553,53 @@ impl Component for Input {
             vis_cols += w;
             visible_end += 1;
         }
+        // A wide character that only half fits at the right edge is never
+        // split: a space holds its place, so every branch below pads from
+        // the same column count.
+        let edge_placeholder = visible_end < chars.len() && vis_cols < available;
+        if edge_placeholder {
+            vis_cols += 1;
+        }
 
         // Build output line
         let mut line = String::with_capacity(total_width + 20);
-        line.push_str(PROMPT);
+        if self.is_valid() {
+            line.push_str(&self.prompt);
+        } else {
+            line.push_str(&theme.error.paint(&strip_ansi(&self.prompt)));
+        }
 
-        if self.focused {
+        let selection = self.selection().filter(|_| self.focused);
+        if let Some(selection) = selection {
+            // The selection stands in for the cursor: one is always at the
+            // moving edge of the other.
+            let start = selection.start.clamp(scroll, visible_end);
+            let end = selection.end.clamp(scroll, visible_end);
+            line.extend(&chars[scroll..start]);
+            line.push_str(&theme.cursor.on);
+            line.extend(&chars[start..end]);
+            line.push_str(&theme.cursor.off);
+            line.extend(&chars[end..visible_end]);
+            if edge_placeholder {
+                line.push(' ');
+            }
+            let pad = total_width.saturating_sub(prompt_width + vis_cols);
+            line.push_str(&" ".repeat(pad));
+        } else if self.focused {
             // Chars before cursor
             let before: String = chars[scroll..self.cursor].iter().collect();
             line.push_str(&before);
 
-            // Cursor character (inverse video)
-            let cursor_char = if self.cursor < chars.len() {
+            // Cursor character (inverse video). Past the end of the text, or
+            // on a wide char too wide for the whole window, it's a space —
+            // which then also stands in for the edge placeholder.
+            let cursor_fits = self.cursor < visible_end;
+            let cursor_char = if cursor_fits {
                 chars[self.cursor].to_string()
             } else {
-                " ".to_string() // cursor past end of text
+                " ".to_string()
             };
-            line.push_str("\x1b[7m"); // inverse
-            line.push_str(&cursor_char);
-            line.push_str("\x1b[27m"); // reset inverse
+            line.push_str(&theme.cursor.paint(&cursor_char));
 
             // Chars after cursor
             let after_start = (self.cursor + 1).min(visible_end);
@@ -217,10 +607,13 @@ impl Component for Input {
                 let after: String = chars[after_start..visible_end].iter().collect();
                 line.push_str(&after);
             }
+            if edge_placeholder && cursor_fits {
+                line.push(' ');
+            }
 
             // Pad to full width using actual column widths
-            let cursor_extra = if self.cursor >= chars.len() { 1 } else { 0 };
-            let content_cols = PROMPT_WIDTH + vis_cols + cursor_extra;
+            let cursor_extra = usize::from(!cursor_fits && !edge_placeholder);
+            let content_cols = prompt_width + vis_cols + cursor_extra;
             let pad = total_width.saturating_sub(content_cols);
             for _ in 0..pad {
                 line.push(' ');
@@ -229,7 +622,10 @@ impl Component for Input {
             // Not focused: no cursor shown
             let visible_chars: String = chars[scroll..visible_end].iter().collect();
             line.push_str(&visible_chars);
-            let pad = total_width.saturating_sub(PROMPT_WIDTH + vis_cols);
+            if edge_placeholder {
+                line.push(' ');
+            }
+            let pad = total_width.saturating_sub(prompt_width + vis_cols);
             for _ in 0..pad {
                 line.push(' ');
             }
@@ -238,36 +634,101 @@ impl Component for Input {
         vec![line]
     }
 
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn is_single_line(&self) -> bool {
+        true
+    }
+
+    fn form_value(&self) -> Option<String> {
+        Some(self.buffer.clone())
+    }
+
     fn handle_input(&mut self, event: &KeyEvent) {
         let modifiers = event.modifiers;
         let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+        // Any key other than a kill breaks the chain of consecutive kills.
+        let previous_kill = self.last_kill.take();
+        // Shift+movement extends the selection; every other key but a copy
+        // clears it once handled.
+        let extends_selection = modifiers.contains(KeyModifiers::SHIFT)
+            && matches!(
+                event.code,
+                KeyCode::Left | KeyCode::Right | KeyCode::Home | KeyCode::End
+            );
+        if extends_selection && self.selection_anchor.is_none() {
+            self.selection_anchor = Some(self.cursor);
+        }
+        let mut keep_selection = extends_selection;
+        let mut completed = false;
 
         match event.code {
+            // Selection
+            KeyCode::Char('c') if ctrl && self.selection().is_some() => {
+                self.killed = self.selected_text().to_string();
+                keep_selection = true;
+            }
+            KeyCode::Char('x') if ctrl && self.selection().is_some() => {
+                self.killed = self.delete_selection();
+            }
+            KeyCode::Backspace | KeyCode::Delete if self.selection().is_some() => {
+                self.delete_selection();
+            }
+
             // Cursor movement
             KeyCode::Left if ctrl => self.move_word_backward(),
             KeyCode::Right if ctrl => self.move_word_forward(),
-            KeyCode::Left => {
-                if self.cursor > 0 {
-                    self.cursor -= 1;
-                }
-            }
-            KeyCode::Right => {
-                if self.cursor < self.char_count() {
-                    self.cursor += 1;
-                }
-            }
+            KeyCode::Left => self.move_left(),
+            KeyCode::Right => self.move_right(),
             KeyCode::Home => self.cursor = 0,
             KeyCode::End => self.cursor = self.char_count(),
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.char_count(),
+            KeyCode::Char('b') if ctrl => self.move_left(),
+            KeyCode::Char('f') if ctrl => self.move_right(),
 
             // Editing
-            KeyCode::Backspace if ctrl => self.delete_word_backward(),
+            KeyCode::Backspace if ctrl => {
+                let text = self.delete_word_backward();
+                self.kill(text, KillDirection::Backward, previous_kill);
+            }
             KeyCode::Backspace => self.delete_backward(),
             KeyCode::Delete => self.delete_forward(),
-            KeyCode::Char('u') if ctrl => self.delete_to_start(),
-            KeyCode::Char('k') if ctrl => self.delete_to_end(),
+            KeyCode::Char('w') if ctrl => {
+                let text = self.delete_word_backward();
+                self.kill(text, KillDirection::Backward, previous_kill);
+            }
+            KeyCode::Char('u') if ctrl => {
+                let text = self.delete_to_start();
+                self.kill(text, KillDirection::Backward, previous_kill);
+            }
+            KeyCode::Char('k') if ctrl => {
+                let text = self.delete_to_end();
+                self.kill(text, KillDirection::Forward, previous_kill);
+            }
+            KeyCode::Char('y') if ctrl => {
+                self.delete_selection();
+                self.yank();
+            }
+            KeyCode::Char('t') if ctrl => self.transpose_chars(),
 
-            // Character insertion
-            KeyCode::Char(c) if !ctrl => self.insert_char(c),
+            // Character insertion. Some terminals deliver Tab as a literal
+            // '\t'; other control characters would be invisible garbage.
+            KeyCode::Tab | KeyCode::Char('\t') if self.completer.is_some() => {
+                self.complete(false);
+                completed = true;
+            }
+            KeyCode::Tab | KeyCode::Char('\t') => self.insert_tab(),
+            KeyCode::Char(c) if !ctrl && !c.is_control() && self.accepts(c) => {
+                self.delete_selection();
+                self.insert_char(c)
+            }
 
             // Callbacks
             KeyCode::Enter => {
@@ -276,6 +737,7 @@ impl Component for Input {
                     cb(&val);
                 }
             }
+            KeyCode::Esc if !self.completions.is_empty() => self.completions.clear(),
             KeyCode::Esc => {
                 if let Some(ref mut cb) = self.on_escape {
                     cb();
@@ -292,6 +754,13 @@ impl Component for Input {
         if self.cursor > count {
             self.cursor = count;
         }
+        if !keep_selection {
+            self.selection_anchor = None;
+        }
+        // An open completion list follows the word being edited.
+        if !completed && !self.completions.is_empty() {
+            self.complete(true);
+        }
     }
 }
 
@@ -344,7 +813,6 @@ fn compute_scroll(cursor: usize, current_offset: usize, available: usize, chars:
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::visible_width;
 
     fn key(code: KeyCode) -> KeyEvent {
         KeyEvent::new(code, KeyModifiers::NONE)
@@ -415,7 +883,7 @@ mod tests {
     fn render_narrow_width() {
         let input = Input::new();
         let lines = input.render(2);
-        // Width <= PROMPT_WIDTH: just spaces
+        // Width <= prompt width: just spaces
         assert_eq!(lines.len(), 1);
         assert_eq!(lines[0], "  ");
     }
@@ -460,6 +928,215 @@ mod tests {
         assert_eq!(input.value(), "abc");
     }
 
+    #[test]
+    fn control_chars_are_not_inserted() {
+        let mut input = Input::new();
+        for c in ['a', '\x7f', '\x1b', '\u{0}', '\r', '\u{85}', 'b'] {
+            input.handle_input(&char_key(c));
+        }
+        assert_eq!(input.value(), "ab");
+        assert_eq!(input.cursor, 2);
+    }
+
+    #[test]
+    fn tab_ignored_by_default() {
+        let mut input = Input::new();
+        input.handle_input(&key(KeyCode::Tab));
+        input.handle_input(&char_key('\t'));
+        assert_eq!(input.value(), "");
+    }
+
+    #[test]
+    fn tab_inserts_spaces_when_configured() {
+        let mut input = Input::new();
+        input.set_tab_width(Some(4));
+        input.handle_input(&char_key('a'));
+        input.handle_input(&key(KeyCode::Tab));
+        input.handle_input(&char_key('\t'));
+        assert_eq!(input.value(), "a        ");
+
+        input.set_max_length(Some(11));
+        input.handle_input(&key(KeyCode::Tab));
+        assert_eq!(input.value(), "a          ");
+    }
+
+    // === Completion tests ===
+
+    /// Completes against a fixed set of paths by prefix.
+    fn path_completer() -> Completer {
+        const PATHS: [&str; 4] = ["src/", "src/lib.rs", "src/main.rs", "Cargo.toml"];
+        Box::new(|word| {
+            PATHS
+                .iter()
+                .filter(|path| path.starts_with(word))
+                .map(|path| path.to_string())
+                .collect()
+        })
+    }
+
+    #[test]
+    fn tab_lists_matching_completions() {
+        let mut input = Input::new();
+        input.set_completer(Some(path_completer()));
+        input.set_value("cat src/");
+        input.handle_input(&key(KeyCode::Tab));
+        assert_eq!(input.completions(), ["src/", "src/lib.rs", "src/main.rs"]);
+        assert_eq!(input.value(), "cat src/");
+
+        // The list narrows as the word is typed, and Escape closes it.
+        input.handle_input(&char_key('m'));
+        assert_eq!(input.completions(), ["src/main.rs"]);
+        input.handle_input(&key(KeyCode::Esc));
+        assert!(input.completions().is_empty());
+    }
+
+    #[test]
+    fn single_completion_is_accepted_on_tab() {
+        let mut input = Input::new();
+        input.set_completer(Some(path_completer()));
+        input.set_value("cat Ca");
+        input.handle_input(&key(KeyCode::Tab));
+        assert_eq!(input.value(), "cat Cargo.toml");
+        assert!(input.completions().is_empty());
+    }
+
+    #[test]
+    fn accept_completion_replaces_word_before_cursor() {
+        let mut input = Input::new();
+        input.set_completer(Some(path_completer()));
+        input.set_value("cat src/ -n");
+        for _ in 0..3 {
+            input.handle_input(&key(KeyCode::Left));
+        }
+        input.handle_input(&key(KeyCode::Tab));
+        assert_eq!(input.completions().len(), 3);
+
+        input.accept_completion(5);
+        assert_eq!(input.value(), "cat src/ -n");
+        input.accept_completion(1);
+        assert_eq!(input.value(), "cat src/lib.rs -n");
+        assert_eq!(input.cursor, "cat src/lib.rs".len());
+        assert!(input.completions().is_empty());
+    }
+
+    #[test]
+    fn completion_column_is_where_the_word_starts() {
+        let mut input = Input::new();
+        input.set_value("cat sr");
+        input.render(40);
+        assert_eq!(input.completion_column(), 2 + 4);
+    }
+
+    #[test]
+    fn completer_takes_tab_over_from_tab_width() {
+        let mut input = Input::new();
+        input.set_tab_width(Some(4));
+        input.set_completer(Some(Box::new(|_| Vec::new())));
+        input.set_value("x");
+        input.handle_input(&key(KeyCode::Tab));
+        assert_eq!(input.value(), "x");
+    }
+
+    // === Selection tests ===
+
+    fn shift_key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::SHIFT)
+    }
+
+    #[test]
+    fn shift_arrows_select_from_anchor() {
+        let mut input = Input::new();
+        input.set_value("hello world");
+        input.handle_input(&key(KeyCode::Home));
+        for _ in 0..5 {
+            input.handle_input(&shift_key(KeyCode::Right));
+        }
+        assert_eq!(input.selection(), Some(0..5));
+        assert_eq!(input.selected_text(), "hello");
+
+        // A fresh anchor at 6; moving across it flips the range around it.
+        input.handle_input(&key(KeyCode::Right));
+        input.handle_input(&shift_key(KeyCode::Left));
+        input.handle_input(&shift_key(KeyCode::Left));
+        assert_eq!(input.selected_text(), "o ");
+        input.handle_input(&shift_key(KeyCode::End));
+        assert_eq!(input.selected_text(), "world");
+    }
+
+    #[test]
+    fn plain_movement_clears_selection() {
+        let mut input = Input::new();
+        input.set_value("abc");
+        input.handle_input(&shift_key(KeyCode::Home));
+        assert_eq!(input.selection(), Some(0..3));
+        input.handle_input(&key(KeyCode::Right));
+        assert_eq!(input.selection(), None);
+        assert_eq!(input.cursor, 1);
+    }
+
+    #[test]
+    fn typing_replaces_selection() {
+        let mut input = Input::new();
+        input.set_value("hello world");
+        input.handle_input(&key(KeyCode::Home));
+        input.handle_input(&ctrl_key(KeyCode::Right));
+        input.handle_input(&KeyEvent::new(
+            KeyCode::Right,
+            KeyModifiers::SHIFT | KeyModifiers::CONTROL,
+        ));
+        assert_eq!(input.selected_text(), "world");
+        input.handle_input(&KeyEvent::new(KeyCode::Char('T'), KeyModifiers::SHIFT));
+        input.handle_input(&char_key('u'));
+        assert_eq!(input.value(), "hello Tu");
+        assert_eq!(input.selection(), None);
+    }
+
+    #[test]
+    fn backspace_deletes_only_selection() {
+        let mut input = Input::new();
+        input.set_value("abcdef");
+        input.handle_input(&shift_key(KeyCode::Left));
+        input.handle_input(&shift_key(KeyCode::Left));
+        input.handle_input(&key(KeyCode::Backspace));
+        assert_eq!(input.value(), "abcd");
+        assert_eq!(input.cursor, 4);
+    }
+
+    #[test]
+    fn cut_and_copy_use_kill_ring() {
+        let mut input = Input::new();
+        input.set_value("one two");
+        input.handle_input(&shift_key(KeyCode::Home));
+        input.handle_input(&ctrl_key(KeyCode::Char('c')));
+        assert_eq!(input.value(), "one two");
+        assert_eq!(input.selection(), Some(0..7));
+
+        input.handle_input(&key(KeyCode::End));
+        for _ in 0..4 {
+            input.handle_input(&shift_key(KeyCode::Left));
+        }
+        input.handle_input(&ctrl_key(KeyCode::Char('x')));
+        assert_eq!(input.value(), "one");
+        input.handle_input(&key(KeyCode::Home));
+        input.handle_input(&ctrl_key(KeyCode::Char('y')));
+        assert_eq!(input.value(), " twoone");
+    }
+
+    #[test]
+    fn selection_renders_inverse_in_place_of_cursor() {
+        let mut input = Input::new();
+        input.set_value("abcdef");
+        input.handle_input(&key(KeyCode::Left));
+        input.handle_input(&shift_key(KeyCode::Left));
+        input.handle_input(&shift_key(KeyCode::Left));
+        let line = &input.render(12)[0];
+        assert_eq!(line, "> abc\x1b[7mde\x1b[27mf    ");
+        assert_eq!(visible_width(line), 12);
+
+        input.focused = false;
+        assert!(!input.render(12)[0].contains("\x1b[7m"));
+    }
+
     // === Cursor movement tests ===
 
     #[test]
@@ -510,6 +1187,59 @@ mod tests {
         assert_eq!(input.cursor, 11);
     }
 
+    #[test]
+    fn ctrl_a_moves_to_start() {
+        let mut input = Input::new();
+        input.set_value("hello world");
+        input.handle_input(&ctrl_key(KeyCode::Char('a')));
+        assert_eq!(input.cursor, 0);
+        assert_eq!(input.value(), "hello world");
+    }
+
+    #[test]
+    fn ctrl_e_moves_to_end() {
+        let mut input = Input::new();
+        input.set_value("hello world");
+        input.cursor = 3;
+        input.handle_input(&ctrl_key(KeyCode::Char('e')));
+        assert_eq!(input.cursor, 11);
+        assert_eq!(input.value(), "hello world");
+    }
+
+    #[test]
+    fn ctrl_f_moves_right() {
+        let mut input = Input::new();
+        input.set_value("abc");
+        input.cursor = 1;
+        input.handle_input(&ctrl_key(KeyCode::Char('f')));
+        assert_eq!(input.cursor, 2);
+        input.handle_input(&ctrl_key(KeyCode::Char('f')));
+        input.handle_input(&ctrl_key(KeyCode::Char('f')));
+        assert_eq!(input.cursor, 3); // clamped at end
+        assert_eq!(input.value(), "abc");
+    }
+
+    #[test]
+    fn ctrl_b_moves_left() {
+        let mut input = Input::new();
+        input.set_value("abc");
+        input.handle_input(&ctrl_key(KeyCode::Char('b')));
+        assert_eq!(input.cursor, 2);
+        input.cursor = 0;
+        input.handle_input(&ctrl_key(KeyCode::Char('b')));
+        assert_eq!(input.cursor, 0); // clamped at start
+        assert_eq!(input.value(), "abc");
+    }
+
+    #[test]
+    fn plain_a_e_f_b_still_insert() {
+        let mut input = Input::new();
+        for c in ['a', 'e', 'f', 'b'] {
+            input.handle_input(&char_key(c));
+        }
+        assert_eq!(input.value(), "aefb");
+    }
+
     #[test]
     fn cursor_render_shows_at_correct_position() {
         let mut input = Input::new();
@@ -556,6 +1286,42 @@ mod tests {
         assert_eq!(input.cursor, 15); // end
     }
 
+    #[test]
+    fn punctuation_mode_stops_at_each_run() {
+        let mut input = Input::new();
+        input.set_word_boundary(WordMode::Punctuation);
+        input.set_value("foo.bar");
+
+        input.handle_input(&ctrl_key(KeyCode::Left));
+        assert_eq!(input.cursor, 4); // before "bar"
+        input.handle_input(&ctrl_key(KeyCode::Left));
+        assert_eq!(input.cursor, 3); // before "."
+        input.handle_input(&ctrl_key(KeyCode::Left));
+        assert_eq!(input.cursor, 0); // before "foo"
+
+        input.handle_input(&ctrl_key(KeyCode::Right));
+        assert_eq!(input.cursor, 3);
+        input.handle_input(&ctrl_key(KeyCode::Right));
+        assert_eq!(input.cursor, 4);
+    }
+
+    #[test]
+    fn punctuation_mode_applies_to_word_deletion() {
+        let mut input = Input::new();
+        input.set_word_boundary(WordMode::Punctuation);
+        input.set_value("cd src/my_dir");
+        input.handle_input(&ctrl_key(KeyCode::Char('w')));
+        assert_eq!(input.value(), "cd src/");
+        input.handle_input(&ctrl_key(KeyCode::Backspace));
+        assert_eq!(input.value(), "cd src");
+
+        // The default treats the whole path as one word.
+        let mut input = Input::new();
+        input.set_value("cd src/my_dir");
+        input.handle_input(&ctrl_key(KeyCode::Char('w')));
+        assert_eq!(input.value(), "cd ");
+    }
+
     // === Advanced editing tests ===
 
     #[test]
@@ -591,6 +1357,262 @@ mod tests {
         assert_eq!(input.cursor, 5);
     }
 
+    // === Kill ring tests ===
+
+    #[test]
+    fn ctrl_k_then_ctrl_y_yanks_back() {
+        let mut input = Input::new();
+        input.set_value("hello world");
+        input.cursor = 5;
+
+        input.handle_input(&ctrl_key(KeyCode::Char('k')));
+        assert_eq!(input.value(), "hello");
+
+        input.handle_input(&ctrl_key(KeyCode::Char('a')));
+        input.handle_input(&ctrl_key(KeyCode::Char('y')));
+        assert_eq!(input.value(), " worldhello");
+        assert_eq!(input.cursor, 6);
+    }
+
+    #[test]
+    fn ctrl_u_kill_can_be_yanked() {
+        let mut input = Input::new();
+        input.set_value("hello world");
+        input.cursor = 6;
+
+        input.handle_input(&ctrl_key(KeyCode::Char('u')));
+        input.handle_input(&ctrl_key(KeyCode::Char('e')));
+        input.handle_input(&ctrl_key(KeyCode::Char('y')));
+        assert_eq!(input.value(), "worldhello ");
+    }
+
+    #[test]
+    fn consecutive_backward_kills_accumulate() {
+        let mut input = Input::new();
+        input.set_value("one two three");
+
+        input.handle_input(&ctrl_key(KeyCode::Char('w')));
+        input.handle_input(&ctrl_key(KeyCode::Char('w')));
+        assert_eq!(input.value(), "one ");
+
+        input.handle_input(&ctrl_key(KeyCode::Char('y')));
+        assert_eq!(input.value(), "one two three");
+    }
+
+    #[test]
+    fn consecutive_forward_kills_accumulate() {
+        let mut input = Input::new();
+        input.set_value("abc");
+        input.cursor = 1;
+        input.handle_input(&ctrl_key(KeyCode::Char('k'))); // kills "bc"
+
+        input.set_value("xyz");
+        input.cursor = 0;
+        // set_value isn't a key, so the chain is still live
+        input.handle_input(&ctrl_key(KeyCode::Char('k'))); // kills "xyz"
+        input.handle_input(&ctrl_key(KeyCode::Char('y')));
+        assert_eq!(input.value(), "bcxyz");
+    }
+
+    #[test]
+    fn kill_after_other_key_replaces_ring() {
+        let mut input = Input::new();
+        input.set_value("hello world");
+        input.handle_input(&ctrl_key(KeyCode::Char('w'))); // kills "world"
+        input.handle_input(&key(KeyCode::Left));
+        input.handle_input(&ctrl_key(KeyCode::Char('u'))); // kills "hello"
+        input.handle_input(&ctrl_key(KeyCode::Char('y')));
+        assert_eq!(input.value(), "hello ");
+    }
+
+    // === Filter / validator tests ===
+
+    #[test]
+    fn digits_only_filter_drops_letters() {
+        let mut input = Input::new();
+        input.set_filter(Some(Box::new(|c: char| c.is_ascii_digit())));
+        for c in ['1', 'a', '2', 'x', '3'] {
+            input.handle_input(&char_key(c));
+        }
+        assert_eq!(input.value(), "123");
+        assert_eq!(input.cursor, 3);
+
+        input.set_filter(None);
+        input.handle_input(&char_key('z'));
+        assert_eq!(input.value(), "123z");
+    }
+
+    #[test]
+    fn validator_turns_prompt_red_when_invalid() {
+        let mut input = Input::new();
+        input.set_validator(Some(Box::new(|v: &str| v.parse::<u32>().is_ok())));
+
+        input.set_value("42");
+        assert!(input.is_valid());
+        let line = &input.render(20)[0];
+        assert!(line.starts_with("> 42"));
+
+        input.set_value("4x2");
+        assert!(!input.is_valid());
+        let line = &input.render(20)[0];
+        assert!(line.starts_with("\x1b[31m> \x1b[39m4x2"));
+        assert_eq!(visible_width(line), 20);
+    }
+
+    // === Prompt tests ===
+
+    /// Columns before the inverse-video cursor.
+    fn cursor_col(line: &str) -> usize {
+        visible_width(&line[..line.find("\x1b[7m").unwrap()])
+    }
+
+    #[test]
+    fn colored_prompt_keeps_width_and_cursor_column() {
+        let mut input = Input::new();
+        input.set_prompt("\x1b[35m❯ \x1b[39m");
+        input.set_value("abc");
+        let line = &input.render(20)[0];
+        assert!(line.starts_with("\x1b[35m❯ \x1b[39mabc"));
+        assert_eq!(visible_width(line), 20);
+        assert_eq!(cursor_col(line), 5);
+
+        input.handle_input(&KeyEvent::new(KeyCode::Home, KeyModifiers::NONE));
+        assert_eq!(cursor_col(&input.render(20)[0]), 2);
+    }
+
+    #[test]
+    fn wide_and_empty_prompts_pad_to_width() {
+        let mut input = Input::new();
+        input.set_value("0123456789");
+
+        input.set_prompt("你好> ");
+        let line = &input.render(12)[0];
+        assert_eq!(visible_width(line), 12);
+        // 6 prompt columns leave 5 for text and 1 for the cursor.
+        assert_eq!(strip_ansi(line), "你好> 56789 ");
+        assert_eq!(input.render(6)[0], "      ");
+
+        input.set_prompt("");
+        input.set_value("0123456789");
+        let line = &input.render(12)[0];
+        assert_eq!(visible_width(line), 12);
+        assert_eq!(cursor_col(line), 10);
+        input.focused = false;
+        assert_eq!(input.render(12)[0], "0123456789  ");
+    }
+
+    #[test]
+    fn invalid_custom_prompt_renders_red_without_its_styling() {
+        let mut input = Input::new();
+        input.set_prompt("\x1b[35m❯ \x1b[39m");
+        input.set_validator(Some(Box::new(|v: &str| v.is_empty())));
+        input.set_value("x");
+        let line = &input.render(10)[0];
+        assert!(line.starts_with("\x1b[31m❯ \x1b[39mx"));
+        assert_eq!(visible_width(line), 10);
+    }
+
+    // === Transpose tests ===
+
+    #[test]
+    fn ctrl_t_swaps_around_cursor_and_advances() {
+        let mut input = Input::new();
+        input.set_value("abcd");
+        input.cursor = 2;
+        input.handle_input(&ctrl_key(KeyCode::Char('t')));
+        assert_eq!(input.value(), "acbd");
+        assert_eq!(input.cursor, 3);
+    }
+
+    #[test]
+    fn ctrl_t_at_end_swaps_last_two() {
+        let mut input = Input::new();
+        input.set_value("teh");
+        input.handle_input(&ctrl_key(KeyCode::Char('t')));
+        assert_eq!(input.value(), "the");
+        assert_eq!(input.cursor, 3);
+    }
+
+    #[test]
+    fn ctrl_t_handles_multibyte_chars() {
+        let mut input = Input::new();
+        input.set_value("aé你");
+        input.cursor = 1;
+        input.handle_input(&ctrl_key(KeyCode::Char('t')));
+        assert_eq!(input.value(), "éa你");
+        assert_eq!(input.cursor, 2);
+    }
+
+    #[test]
+    fn ctrl_t_noop_at_start_or_short_buffer() {
+        let mut input = Input::new();
+        input.set_value("ab");
+        input.cursor = 0;
+        input.handle_input(&ctrl_key(KeyCode::Char('t')));
+        assert_eq!(input.value(), "ab");
+        assert_eq!(input.cursor, 0);
+
+        input.set_value("x");
+        input.handle_input(&ctrl_key(KeyCode::Char('t')));
+        assert_eq!(input.value(), "x");
+    }
+
+    // === Max length tests ===
+
+    #[test]
+    fn typing_past_max_length_is_ignored() {
+        let mut input = Input::new();
+        input.set_max_length(Some(3));
+        for c in "abcde".chars() {
+            input.handle_input(&char_key(c));
+        }
+        assert_eq!(input.value(), "abc");
+        assert_eq!(input.cursor, 3);
+
+        // Room frees up after deleting
+        input.handle_input(&key(KeyCode::Backspace));
+        input.handle_input(&char_key('z'));
+        assert_eq!(input.value(), "abz");
+    }
+
+    #[test]
+    fn max_length_counts_chars_not_bytes() {
+        let mut input = Input::new();
+        input.set_max_length(Some(2));
+        for c in "你好世".chars() {
+            input.handle_input(&char_key(c));
+        }
+        assert_eq!(input.value(), "你好");
+    }
+
+    #[test]
+    fn set_value_truncates_to_max_length() {
+        let mut input = Input::new();
+        input.set_max_length(Some(4));
+        input.set_value("héllo wörld");
+        assert_eq!(input.value(), "héll");
+        assert_eq!(input.cursor, 4);
+    }
+
+    #[test]
+    fn lowering_max_length_truncates_current_value() {
+        let mut input = Input::new();
+        input.set_value("abcdef");
+        input.set_max_length(Some(2));
+        assert_eq!(input.value(), "ab");
+        assert_eq!(input.cursor, 2);
+    }
+
+    #[test]
+    fn yank_fills_only_remaining_room() {
+        let mut input = Input::new();
+        input.set_value("hello world");
+        input.handle_input(&ctrl_key(KeyCode::Char('w')));
+        input.set_max_length(Some(9));
+        input.handle_input(&ctrl_key(KeyCode::Char('y')));
+        assert_eq!(input.value(), "hello wor");
+    }
+
     // === Horizontal scrolling tests ===
 
     #[test]
@@ -760,6 +1782,35 @@ mod tests {
         assert_eq!(visible_width(&lines[0]), 12);
     }
 
+    #[test]
+    fn wide_char_at_right_edge_gets_placeholder() {
+        // "a你b你c": the first '你' needs columns 1-2 but only column 1 is
+        // left after the prompt at width 4.
+        let mut input = Input::new();
+        input.set_value("a你b你c");
+        input.cursor = 0;
+        assert_eq!(input.render(4)[0], "> \x1b[7ma\x1b[27m ");
+    }
+
+    #[test]
+    fn wide_chars_at_edge_render_exact_width() {
+        let mut input = Input::new();
+        input.set_value("a你b你c");
+        for width in 3..=12u16 {
+            for cursor in 0..=5 {
+                input.cursor = cursor;
+                input.scroll_offset.set(0);
+                input.focused = true;
+                let line = &input.render(width)[0];
+                assert_eq!(visible_width(line), width as usize, "{width}/{cursor}: {line:?}");
+
+                input.focused = false;
+                let line = &input.render(width)[0];
+                assert_eq!(visible_width(line), width as usize, "{width}/{cursor}: {line:?}");
+            }
+        }
+    }
+
     // === Insert in middle ===
 
     #[test]
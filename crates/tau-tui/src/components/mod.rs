@@ -1,13 +1,97 @@
-// Built-in components: Text, Box, Spacer, Input, SelectList.
+// Built-in components: Text, Box, Spacer, Input, SelectList, Tabs, StatusBar,
+// Checkbox, RadioGroup, Form, HBox, Flex, Gauge, Grid, HScrollable.
 
 pub mod box_component;
+pub mod checkbox;
+pub mod flex;
+pub mod form;
+pub mod gauge;
+pub mod grid;
+pub mod hbox;
+pub mod hscroll;
 pub mod input;
+pub mod radio_group;
 pub mod select_list;
 pub mod spacer;
+pub mod status_bar;
+pub mod tabs;
 pub mod text;
 
 pub use box_component::BoxComponent;
-pub use input::Input;
-pub use select_list::{SelectItem, SelectList};
+pub use checkbox::Checkbox;
+pub use flex::{Flex, FlexChild};
+pub use form::Form;
+pub use gauge::Gauge;
+pub use grid::{Grid, Track};
+pub use hbox::HBox;
+pub use hscroll::HScrollable;
+pub use input::{Input, WordMode};
+pub use radio_group::RadioGroup;
+pub use select_list::{ItemRenderer, SelectItem, SelectList, SelectViewState};
 pub use spacer::Spacer;
+pub use status_bar::StatusBar;
+pub use tabs::Tabs;
 pub use text::Text;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::Component;
+
+    /// One of each built-in component, with enough content to draw styling.
+    fn one_of_each() -> Vec<(&'static str, Box<dyn Component>)> {
+        let mut boxed = BoxComponent::new(2, 1);
+        boxed.add_child(Box::new(Text::new("boxed", 0, 0)));
+        let mut flex = Flex::new();
+        flex.add_child(Box::new(Text::new("flex", 0, 0)), FlexChild::weighted(1));
+        flex.set_height(3);
+        let mut form = Form::new();
+        form.add_field("name", "Name", Box::new(Input::new()));
+        let mut gauge = Gauge::new(0.0, 1.0);
+        gauge.set_value(0.5);
+        gauge.set_label(Some(0));
+        let mut grid = Grid::new(vec![Track::Fixed(3), Track::Weight(1)], vec![Track::Auto]);
+        grid.set_cell(0, 0, Box::new(Text::new("cell", 0, 0)));
+        let mut hbox = HBox::new();
+        hbox.add_child(Box::new(Text::new("hbox", 0, 0)));
+        let wide = HScrollable::new(Box::new(Text::new("wide content", 0, 0)), 12);
+        let options = vec!["one".to_string(), "two".to_string()];
+        let mut select = SelectList::new(vec![SelectItem::new("a", "alpha")], 5);
+        select.set_filterable(true);
+        let mut status = StatusBar::new();
+        status.set_left("left");
+        status.set_right("right");
+        status.set_bg("\x1b[44m");
+        let tabs = Tabs::new(vec!["tab".to_string()], vec![Box::new(Text::new("body", 0, 0))]);
+
+        vec![
+            ("BoxComponent", Box::new(boxed)),
+            ("Checkbox", Box::new(Checkbox::new("check"))),
+            ("Flex", Box::new(flex)),
+            ("Form", Box::new(form)),
+            ("Gauge", Box::new(gauge)),
+            ("Grid", Box::new(grid)),
+            ("HBox", Box::new(hbox)),
+            ("HScrollable", Box::new(wide)),
+            ("Input", Box::new(Input::new())),
+            ("RadioGroup", Box::new(RadioGroup::new(options))),
+            ("SelectList", Box::new(select)),
+            ("SelectList (empty)", Box::new(SelectList::new(vec![], 5))),
+            ("Spacer", Box::new(Spacer::new(2))),
+            ("StatusBar", Box::new(status)),
+            ("Tabs", Box::new(tabs)),
+            ("Text", Box::new(Text::new("some text", 1, 1))),
+        ]
+    }
+
+    #[test]
+    fn every_component_renders_blank_at_width_zero() {
+        for (name, component) in one_of_each() {
+            let lines = component.render(0);
+            assert!(
+                lines.iter().all(String::is_empty),
+                "{name} drew something at width 0: {lines:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,218 @@
+// RadioGroup component — pick exactly one of several options.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::component::Component;
+use crate::theme::Theme;
+use crate::utils::{truncate_to_width, visible_width};
+
+/// A vertical list of mutually exclusive options, one per line, rendered as
+/// `(•) label` for the selected option and `( ) label` for the rest.
+///
+/// Up/Down move the cursor (with wrapping); Space or Enter selects the option
+/// under it and invokes `on_change` if the selection changed. While focused,
+/// the cursor's marker is drawn in inverse video. Nothing is selected until
+/// the user picks an option or [`select`](Self::select) is called.
+pub struct RadioGroup {
+    options: Vec<String>,
+    /// Index of the option under the cursor.
+    cursor: usize,
+    /// Index of the selected option, if any.
+    selected: Option<usize>,
+    /// Whether this group currently has focus (highlights the cursor).
+    pub focused: bool,
+    /// Callback invoked with the newly selected option.
+    pub on_change: Option<Box<dyn FnMut(&str)>>,
+}
+
+impl RadioGroup {
+    /// Create a group with the given options and nothing selected.
+    pub fn new(options: Vec<String>) -> Self {
+        RadioGroup {
+            options,
+            cursor: 0,
+            selected: None,
+            focused: true,
+            on_change: None,
+        }
+    }
+
+    /// The selected option, if any.
+    pub fn selected_value(&self) -> Option<&str> {
+        self.selected.map(|idx| self.options[idx].as_str())
+    }
+
+    /// Index of the selected option, if any.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Select the option at `index` (and move the cursor there) without
+    /// invoking `on_change`. Out-of-bounds indices are ignored.
+    pub fn select(&mut self, index: usize) {
+        if index < self.options.len() {
+            self.selected = Some(index);
+            self.cursor = index;
+        }
+    }
+
+    fn move_up(&mut self) {
+        if !self.options.is_empty() {
+            self.cursor = (self.cursor + self.options.len() - 1) % self.options.len();
+        }
+    }
+
+    fn move_down(&mut self) {
+        if !self.options.is_empty() {
+            self.cursor = (self.cursor + 1) % self.options.len();
+        }
+    }
+
+    /// Select the option under the cursor, notifying `on_change` on change.
+    fn select_cursor(&mut self) {
+        if self.cursor >= self.options.len() || self.selected == Some(self.cursor) {
+            return;
+        }
+        self.selected = Some(self.cursor);
+        if let Some(ref mut cb) = self.on_change {
+            cb(&self.options[self.cursor]);
+        }
+    }
+}
+
+impl Component for RadioGroup {
+    fn render(&self, width: u16) -> Vec<String> {
+        self.render_themed(width, &Theme::default())
+    }
+
+    fn render_themed(&self, width: u16, theme: &Theme) -> Vec<String> {
+        if width == 0 {
+            return vec![String::new(); self.options.len()];
+        }
+        let width = width as usize;
+        self.options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| {
+                let mark = if self.selected == Some(i) { "(•)" } else { "( )" };
+                let mut line = if self.focused && i == self.cursor {
+                    format!("{} {}", theme.cursor.paint(mark), option)
+                } else {
+                    format!("{} {}", mark, option)
+                };
+                if visible_width(&line) > width {
+                    line = truncate_to_width(&line, width, "…");
+                }
+                let pad = width.saturating_sub(visible_width(&line));
+                line.push_str(&" ".repeat(pad));
+                line
+            })
+            .collect()
+    }
+
+    fn handle_input(&mut self, event: &KeyEvent) {
+        match event.code {
+            KeyCode::Up => self.move_up(),
+            KeyCode::Down => self.move_down(),
+            KeyCode::Char(' ') | KeyCode::Enter => self.select_cursor(),
+            _ => {}
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// The selected option, or an empty string if nothing is selected.
+    fn form_value(&self) -> Option<String> {
+        Some(self.selected_value().unwrap_or_default().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn themes() -> RadioGroup {
+        RadioGroup::new(vec!["light".into(), "dark".into(), "system".into()])
+    }
+
+    #[test]
+    fn renders_one_line_per_option() {
+        let mut group = themes();
+        group.focused = false;
+        group.select(1);
+        assert_eq!(
+            group.render(12),
+            vec!["( ) light   ", "(•) dark    ", "( ) system  "]
+        );
+    }
+
+    #[test]
+    fn starts_with_nothing_selected() {
+        let group = themes();
+        assert_eq!(group.selected_value(), None);
+        assert!(group.render(20).iter().all(|l| !l.contains('•')));
+    }
+
+    #[test]
+    fn arrows_move_and_space_selects_exactly_one() {
+        let mut group = themes();
+        group.handle_input(&key(KeyCode::Down));
+        group.handle_input(&key(KeyCode::Char(' ')));
+        assert_eq!(group.selected_value(), Some("dark"));
+
+        group.handle_input(&key(KeyCode::Down));
+        group.handle_input(&key(KeyCode::Enter));
+        assert_eq!(group.selected_value(), Some("system"));
+        assert_eq!(group.render(20).iter().filter(|l| l.contains('•')).count(), 1);
+    }
+
+    #[test]
+    fn cursor_wraps_around() {
+        let mut group = themes();
+        group.handle_input(&key(KeyCode::Up));
+        group.handle_input(&key(KeyCode::Enter));
+        assert_eq!(group.selected_value(), Some("system"));
+        group.handle_input(&key(KeyCode::Down));
+        group.handle_input(&key(KeyCode::Enter));
+        assert_eq!(group.selected_value(), Some("light"));
+    }
+
+    #[test]
+    fn on_change_fires_only_when_selection_changes() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let changes_clone = changes.clone();
+        let mut group = themes();
+        group.on_change = Some(Box::new(move |value: &str| {
+            changes_clone.borrow_mut().push(value.to_string());
+        }));
+
+        group.handle_input(&key(KeyCode::Char(' ')));
+        group.handle_input(&key(KeyCode::Char(' '))); // already selected
+        group.handle_input(&key(KeyCode::Down));
+        group.handle_input(&key(KeyCode::Enter));
+        assert_eq!(*changes.borrow(), vec!["light", "dark"]);
+    }
+
+    #[test]
+    fn focused_cursor_is_highlighted() {
+        let mut group = themes();
+        group.handle_input(&key(KeyCode::Down));
+        let lines = group.render(20);
+        assert!(!lines[0].contains("\x1b[7m"));
+        assert!(lines[1].starts_with("\x1b[7m( )\x1b[27m dark"));
+        assert!(lines.iter().all(|l| visible_width(l) == 20));
+    }
+}
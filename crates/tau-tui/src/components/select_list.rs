@@ -1,9 +1,10 @@
 // Selectable list component with arrow-key navigation, filtering, and scrolling.
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use crate::component::Component;
-use crate::utils::visible_width;
+use crate::theme::Theme;
+use crate::utils::{truncate_to_width, visible_width};
 
 /// A single item in a SelectList.
 #[derive(Debug, Clone)]
@@ -38,11 +39,34 @@ impl SelectItem {
     }
 }
 
+/// Where a [`SelectList`] is: its filter, selection and scroll position.
+/// Taken with [`view_state`](SelectList::view_state) and put back with
+/// [`restore_view_state`](SelectList::restore_view_state), so a palette can
+/// reopen where it was left.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelectViewState {
+    /// Index of the selected item within the filtered view.
+    pub selected: usize,
+    /// Index within the filtered view of the first visible item.
+    pub scroll_offset: usize,
+    /// The filter query.
+    pub filter: String,
+}
+
+/// Formats one item of a [`SelectList`]: receives the item, whether it is
+/// selected, and the width available, and returns the line.
+pub type ItemRenderer = Box<dyn Fn(&SelectItem, bool, u16) -> String>;
+
 /// A selectable list with arrow-key navigation, filtering, and scrolling.
 ///
 /// Renders a visible window of items. The selected item has a `→` prefix and
-/// bold/inverse styling. Arrow Up/Down changes selection with wrapping.
+/// the theme's selection style (bold/inverse by default). Arrow Up/Down
+/// changes selection with wrapping.
 /// Enter triggers `on_select`, Escape triggers `on_cancel`.
+///
+/// With [`set_filterable`](Self::set_filterable) the list owns its filter
+/// query: typing narrows it, Backspace widens it again, and the query is
+/// shown as a `/ query` header line above the items.
 pub struct SelectList {
     /// All items (unfiltered).
     items: Vec<SelectItem>,
@@ -52,10 +76,20 @@ pub struct SelectList {
     selected: usize,
     /// Scroll offset (index into filtered_indices of the first visible item).
     scroll_offset: usize,
+    /// Items to keep visible above and below the selection when scrolling.
+    scroll_margin: usize,
     /// Current filter query (empty = show all).
     filter: String,
     /// Indices into `items` that match the current filter.
     filtered_indices: Vec<usize>,
+    /// Whether the filter also matches against item descriptions.
+    match_description: bool,
+    /// Typed characters edit the filter, shown in a header line.
+    filterable: bool,
+    /// Custom item formatting; `None` uses `label - description`.
+    item_renderer: Option<ItemRenderer>,
+    /// The renderer produces whole lines (no cursor column, styling, padding).
+    renderer_owns_line: bool,
     /// Callback invoked on Enter with the selected item.
     pub on_select: Option<Box<dyn FnMut(&SelectItem)>>,
     /// Callback invoked on Escape.
@@ -71,8 +105,13 @@ impl SelectList {
             max_visible: max_visible.max(1),
             selected: 0,
             scroll_offset: 0,
+            scroll_margin: 0,
             filter: String::new(),
             filtered_indices,
+            match_description: false,
+            filterable: false,
+            item_renderer: None,
+            renderer_owns_line: false,
             on_select: None,
             on_cancel: None,
         }
@@ -85,22 +124,127 @@ impl SelectList {
             .map(|&idx| &self.items[idx])
     }
 
+    /// Index of the selected item within the filtered view, or `None` if the
+    /// filter hides everything.
+    pub fn selected_index(&self) -> Option<usize> {
+        (self.selected < self.len()).then_some(self.selected)
+    }
+
+    /// Select the item at `index` in the filtered view, clamped to the last
+    /// item, and scroll it into view. Does nothing if the view is empty.
+    pub fn select(&mut self, index: usize) {
+        if self.is_empty() {
+            return;
+        }
+        self.selected = index.min(self.len() - 1);
+        self.ensure_visible();
+    }
+
+    /// The current filter, selection and scroll position.
+    pub fn view_state(&self) -> SelectViewState {
+        SelectViewState {
+            selected: self.selected,
+            scroll_offset: self.scroll_offset,
+            filter: self.filter.clone(),
+        }
+    }
+
+    /// Go back to a state from [`view_state`](Self::view_state): re-apply
+    /// its filter, then its selection and scroll position, clamped to the
+    /// items that now match (and scrolled so the selection is visible).
+    pub fn restore_view_state(&mut self, state: &SelectViewState) {
+        self.set_filter(&state.filter);
+        if self.is_empty() {
+            return;
+        }
+        self.selected = state.selected.min(self.len() - 1);
+        self.scroll_offset = state.scroll_offset;
+        self.ensure_visible();
+    }
+
+    /// Number of items in the filtered view.
+    pub fn len(&self) -> usize {
+        self.filtered_indices.len()
+    }
+
+    /// Whether the filtered view is empty.
+    pub fn is_empty(&self) -> bool {
+        self.filtered_indices.is_empty()
+    }
+
     /// Filter items by prefix match on label (case-insensitive).
+    /// With [`set_match_description`](Self::set_match_description) enabled,
+    /// items whose description contains the query also match.
     /// Resets selection to 0 and scroll to 0.
     pub fn set_filter(&mut self, query: &str) {
         self.filter = query.to_string();
         let query_lower = query.to_lowercase();
+        let match_description = self.match_description;
         self.filtered_indices = self
             .items
             .iter()
             .enumerate()
-            .filter(|(_, item)| item.label.to_lowercase().starts_with(&query_lower))
+            .filter(|(_, item)| {
+                item.label.to_lowercase().starts_with(&query_lower)
+                    || (match_description
+                        && item
+                            .description
+                            .as_ref()
+                            .is_some_and(|d| d.to_lowercase().contains(&query_lower)))
+            })
             .map(|(i, _)| i)
             .collect();
         self.selected = 0;
         self.scroll_offset = 0;
     }
 
+    /// Also match the filter query against item descriptions (substring,
+    /// case-insensitive). Off by default. Re-applies the current filter.
+    pub fn set_match_description(&mut self, enabled: bool) {
+        self.match_description = enabled;
+        let query = std::mem::take(&mut self.filter);
+        self.set_filter(&query);
+    }
+
+    /// Let typing edit the filter: printable keys append to the query,
+    /// Backspace removes its last character, and a `/ query` header line is
+    /// rendered above the items. Arrow keys still move through the matches.
+    /// Off by default, leaving the filter to [`set_filter`](Self::set_filter).
+    pub fn set_filterable(&mut self, filterable: bool) {
+        self.filterable = filterable;
+    }
+
+    /// The current filter query.
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Format items with `renderer` instead of `label - description`.
+    ///
+    /// By default the list still draws the cursor column (`→ `), the selected
+    /// item's styling, and the padding; the renderer gets the width left
+    /// after the cursor column. See
+    /// [`set_renderer_owns_line`](Self::set_renderer_owns_line) to hand it
+    /// the whole line instead.
+    pub fn set_item_renderer(&mut self, renderer: ItemRenderer) {
+        self.item_renderer = Some(renderer);
+    }
+
+    /// Let the item renderer produce complete lines at the full width: its
+    /// output is used as-is, with no cursor column, styling, or padding.
+    pub fn set_renderer_owns_line(&mut self, owns: bool) {
+        self.renderer_owns_line = owns;
+    }
+
+    /// Keep at least `margin` items visible above and below the selection
+    /// while scrolling, like an editor's "scrolloff". Near either end of the
+    /// list the selection can still reach the edge. Capped at half the window.
+    /// Defaults to 0 (scroll only once the selection would leave the window).
+    pub fn set_scroll_margin(&mut self, margin: usize) {
+        self.scroll_margin = margin;
+        self.ensure_visible();
+    }
+
     /// Move selection up by one, wrapping to bottom.
     fn move_up(&mut self) {
         let count = self.filtered_indices.len();
@@ -125,71 +269,97 @@ impl SelectList {
         self.ensure_visible();
     }
 
-    /// Ensure the selected item is within the visible window.
+    /// Ensure the selected item, plus `scroll_margin` items on either side,
+    /// is within the visible window.
     fn ensure_visible(&mut self) {
-        if self.selected < self.scroll_offset {
-            self.scroll_offset = self.selected;
+        let margin = self.scroll_margin.min((self.max_visible - 1) / 2);
+        if self.selected < self.scroll_offset + margin {
+            self.scroll_offset = self.selected.saturating_sub(margin);
         }
-        if self.selected >= self.scroll_offset + self.max_visible {
-            self.scroll_offset = self.selected - self.max_visible + 1;
+        if self.selected + margin >= self.scroll_offset + self.max_visible {
+            self.scroll_offset = self.selected + margin + 1 - self.max_visible;
         }
-    }
-
-    /// Number of filtered items.
-    fn filtered_count(&self) -> usize {
-        self.filtered_indices.len()
+        let max_offset = self.len().saturating_sub(self.max_visible);
+        self.scroll_offset = self.scroll_offset.min(max_offset);
     }
 }
 
 impl Component for SelectList {
     fn render(&self, width: u16) -> Vec<String> {
+        self.render_themed(width, &Theme::default())
+    }
+
+    fn render_themed(&self, width: u16, theme: &Theme) -> Vec<String> {
         let total_width = width as usize;
-        let count = self.filtered_count();
+        let count = self.len();
+
+        if total_width == 0 {
+            // Nothing fits; keep the rows so the layout doesn't jump.
+            let items = if count == 0 {
+                1
+            } else {
+                count.min(self.max_visible) + usize::from(count > self.max_visible)
+            };
+            return vec![String::new(); usize::from(self.filterable) + items];
+        }
+
+        let mut lines = Vec::new();
+        if self.filterable {
+            let header = format!("/ {}", self.filter);
+            let pad = total_width.saturating_sub(visible_width(&header));
+            let mut line = truncate_to_width(&header, total_width, "…");
+            line.extend(std::iter::repeat(' ').take(pad));
+            lines.push(line);
+        }
 
         if count == 0 {
-            // Show "(no items)" placeholder
-            let msg = "(no items)";
-            let pad = total_width.saturating_sub(visible_width(msg));
-            let mut line = msg.to_string();
+            // Show a dim "(no items)" placeholder
+            let msg = truncate_to_width("(no items)", total_width, "");
+            let pad = total_width.saturating_sub(visible_width(&msg));
+            let mut line = theme.dim.paint(&msg);
             line.extend(std::iter::repeat(' ').take(pad));
-            return vec![line];
+            lines.push(line);
+            return lines;
         }
 
         let visible_count = count.min(self.max_visible);
         let visible_end = (self.scroll_offset + visible_count).min(count);
 
-        let mut lines = Vec::with_capacity(visible_count + 1);
-
         for i in self.scroll_offset..visible_end {
             let item_idx = self.filtered_indices[i];
             let item = &self.items[item_idx];
             let is_selected = i == self.selected;
 
-            let mut line = String::new();
+            if let (Some(renderer), true) = (&self.item_renderer, self.renderer_owns_line) {
+                lines.push(renderer(item, is_selected, width));
+                continue;
+            }
 
-            if is_selected {
-                // Selected: "→ " prefix with bold/inverse styling
-                line.push_str("\x1b[1;7m→ ");
-                line.push_str(&item.label);
-                if let Some(ref desc) = item.description {
-                    line.push_str(" - ");
-                    line.push_str(desc);
-                }
-                let content_width = visible_width(&line);
-                let pad = total_width.saturating_sub(content_width);
-                line.extend(std::iter::repeat(' ').take(pad));
-                line.push_str("\x1b[0m");
+            // Selected: "→ " prefix in the theme's selection style.
+            // Unselected: "  " prefix (same width as "→ ").
+            let mut line = if is_selected {
+                format!("{}→ ", theme.selection.on)
             } else {
-                // Unselected: "  " prefix (same width as "→ ")
-                line.push_str("  ");
-                line.push_str(&item.label);
-                if let Some(ref desc) = item.description {
-                    line.push_str(" - ");
-                    line.push_str(desc);
+                String::from("  ")
+            };
+            match &self.item_renderer {
+                Some(renderer) => {
+                    let content_width = total_width.saturating_sub(2) as u16;
+                    line.push_str(&renderer(item, is_selected, content_width));
+                }
+                None => {
+                    line.push_str(&item.label);
+                    if let Some(ref desc) = item.description {
+                        line.push_str(" - ");
+                        line.push_str(desc);
+                    }
                 }
-                let content_width = visible_width(&line);
-                let pad = total_width.saturating_sub(content_width);
-                line.extend(std::iter::repeat(' ').take(pad));
+            }
+            let content_width = visible_width(&line);
+            let pad = total_width.saturating_sub(content_width);
+            line.extend(std::iter::repeat(' ').take(pad));
+            if is_selected {
+                line.push_str(&theme.selection.off);
             }
 
             lines.push(line);
@@ -224,9 +394,37 @@ impl Component for SelectList {
                     cb();
                 }
             }
+            KeyCode::Char(c)
+                if self.filterable
+                    && !event
+                        .modifiers
+                        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                let mut query = std::mem::take(&mut self.filter);
+                query.push(c);
+                self.set_filter(&query);
+            }
+            KeyCode::Backspace if self.filterable => {
+                let mut query = std::mem::take(&mut self.filter);
+                query.pop();
+                self.set_filter(&query);
+            }
             _ => {}
         }
     }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    /// The selected item's value, or an empty string if the filter hides all.
+    fn form_value(&self) -> Option<String> {
+        Some(
+            self.selected_item()
+                .map(|item| item.value.clone())
+                .unwrap_or_default(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -398,6 +596,88 @@ mod tests {
         assert_eq!(sl.scroll_offset, 1);
     }
 
+    #[test]
+    fn scroll_margin_keeps_item_below_selection() {
+        let items = make_items(&["a", "b", "c", "d", "e", "f"]);
+        let mut sl = SelectList::new(items, 3);
+        sl.set_scroll_margin(1);
+
+        // (selected, scroll_offset) after each Down: one item stays visible
+        // below the selection until the last item is shown.
+        let mut positions = Vec::new();
+        for _ in 0..5 {
+            sl.handle_input(&key(KeyCode::Down));
+            positions.push((sl.selected, sl.scroll_offset));
+        }
+        assert_eq!(positions, vec![(1, 0), (2, 1), (3, 2), (4, 3), (5, 3)]);
+    }
+
+    #[test]
+    fn scroll_margin_keeps_item_above_selection() {
+        let items = make_items(&["a", "b", "c", "d", "e", "f"]);
+        let mut sl = SelectList::new(items, 3);
+        sl.set_scroll_margin(1);
+        sl.handle_input(&key(KeyCode::Up)); // wrap to last item
+        assert_eq!((sl.selected, sl.scroll_offset), (5, 3));
+
+        let mut positions = Vec::new();
+        for _ in 0..5 {
+            sl.handle_input(&key(KeyCode::Up));
+            positions.push((sl.selected, sl.scroll_offset));
+        }
+        assert_eq!(positions, vec![(4, 3), (3, 2), (2, 1), (1, 0), (0, 0)]);
+    }
+
+    #[test]
+    fn scroll_margin_capped_at_half_window() {
+        let items = make_items(&["a", "b", "c", "d", "e", "f"]);
+        let mut sl = SelectList::new(items, 3);
+        sl.set_scroll_margin(10);
+        sl.handle_input(&key(KeyCode::Down));
+        sl.handle_input(&key(KeyCode::Down));
+        // Selection stays centered in the 3-item window
+        assert_eq!((sl.selected, sl.scroll_offset), (2, 1));
+    }
+
+    // === Item renderer tests ===
+
+    #[test]
+    fn item_renderer_prefixes_icon() {
+        let items = make_items(&["main.rs", "docs"]);
+        let mut sl = SelectList::new(items, 5);
+        sl.set_item_renderer(Box::new(|item, _selected, _width| {
+            let icon = if item.label.contains('.') { "📄" } else { "📁" };
+            format!("{} {}", icon, item.label)
+        }));
+        let lines = sl.render(20);
+        assert!(lines[0].starts_with("\x1b[1;7m→ 📄 main.rs "));
+        assert!(lines[1].starts_with("  📁 docs "));
+        assert!(lines.iter().all(|l| visible_width(l) == 20));
+    }
+
+    #[test]
+    fn item_renderer_sees_selection_and_content_width() {
+        let items = make_items(&["a", "b"]);
+        let mut sl = SelectList::new(items, 5);
+        sl.set_item_renderer(Box::new(|item, selected, width| {
+            format!("{}{}:{}", item.label, if selected { "*" } else { "" }, width)
+        }));
+        let lines = sl.render(12);
+        assert!(lines[0].contains("a*:10"));
+        assert!(lines[1].contains("b:10"));
+    }
+
+    #[test]
+    fn item_renderer_can_own_the_line() {
+        let items = make_items(&["a", "b"]);
+        let mut sl = SelectList::new(items, 5);
+        sl.set_item_renderer(Box::new(|item, selected, width| {
+            format!("[{}] {} ({})", if selected { "x" } else { " " }, item.label, width)
+        }));
+        sl.set_renderer_owns_line(true);
+        assert_eq!(sl.render(30), vec!["[x] a (30)", "[ ] b (30)"]);
+    }
+
     #[test]
     fn scroll_indicator_shows_position() {
         let items = make_items(&["a", "b", "c", "d", "e"]);
@@ -427,6 +707,40 @@ mod tests {
         assert!(last.contains("(2/5)"), "expected (2/5), got: {}", last);
     }
 
+    // === Programmatic selection ===
+
+    #[test]
+    fn select_sets_index_and_scrolls_into_view() {
+        let items = make_items(&["a", "b", "c", "d", "e"]);
+        let mut sl = SelectList::new(items, 3);
+        assert_eq!(sl.selected_index(), Some(0));
+        sl.select(3);
+        assert_eq!(sl.selected_index(), Some(3));
+        assert_eq!(sl.selected_item().unwrap().value, "d");
+        assert_eq!(sl.scroll_offset, 1);
+    }
+
+    #[test]
+    fn select_clamps_to_filtered_range() {
+        let items = make_items(&["apple", "banana", "apricot", "blueberry"]);
+        let mut sl = SelectList::new(items, 5);
+        sl.set_filter("b");
+        assert_eq!(sl.len(), 2);
+        sl.select(10);
+        assert_eq!(sl.selected_index(), Some(1));
+        assert_eq!(sl.selected_item().unwrap().value, "blueberry");
+    }
+
+    #[test]
+    fn empty_view_has_no_selected_index() {
+        let items = make_items(&["apple"]);
+        let mut sl = SelectList::new(items, 5);
+        sl.set_filter("z");
+        assert!(sl.is_empty());
+        sl.select(0);
+        assert_eq!(sl.selected_index(), None);
+    }
+
     // === Filter tests ===
 
     #[test]
@@ -435,7 +749,7 @@ mod tests {
         let mut sl = SelectList::new(items, 5);
 
         sl.set_filter("ap");
-        assert_eq!(sl.filtered_count(), 2);
+        assert_eq!(sl.len(), 2);
 
         let lines = sl.render(30);
         assert!(lines[0].contains("apple"));
@@ -448,7 +762,7 @@ mod tests {
         let mut sl = SelectList::new(items, 5);
 
         sl.set_filter("ap");
-        assert_eq!(sl.filtered_count(), 2);
+        assert_eq!(sl.len(), 2);
     }
 
     #[test]
@@ -466,10 +780,33 @@ mod tests {
         let items = make_items(&["apple", "banana"]);
         let mut sl = SelectList::new(items, 5);
         sl.set_filter("ap");
-        assert_eq!(sl.filtered_count(), 1);
+        assert_eq!(sl.len(), 1);
 
         sl.set_filter("");
-        assert_eq!(sl.filtered_count(), 2);
+        assert_eq!(sl.len(), 2);
+    }
+
+    #[test]
+    fn filter_matches_description_only_when_enabled() {
+        let items = vec![
+            SelectItem::with_description("go", "go", "Google's language"),
+            SelectItem::with_description("rs", "rust", "Systems language"),
+            SelectItem::new("py", "python"),
+        ];
+        let mut sl = SelectList::new(items, 5);
+
+        sl.set_filter("google");
+        assert_eq!(sl.len(), 0);
+
+        sl.set_match_description(true);
+        assert_eq!(sl.len(), 1);
+        assert_eq!(sl.selected_item().unwrap().value, "go");
+
+        sl.set_filter("lang");
+        assert_eq!(sl.len(), 2);
+
+        sl.set_match_description(false);
+        assert_eq!(sl.len(), 0);
     }
 
     #[test]
@@ -477,11 +814,132 @@ mod tests {
         let items = make_items(&["apple", "banana"]);
         let mut sl = SelectList::new(items, 5);
         sl.set_filter("xyz");
-        assert_eq!(sl.filtered_count(), 0);
+        assert_eq!(sl.len(), 0);
 
         let lines = sl.render(30);
         assert_eq!(lines.len(), 1);
-        assert!(lines[0].contains("(no items)"));
+        assert!(lines[0].starts_with("\x1b[2m(no items)\x1b[22m"));
+        assert_eq!(visible_width(&lines[0]), 30);
+    }
+
+    // === View state tests ===
+
+    #[test]
+    fn view_state_round_trips_onto_fresh_list() {
+        let labels = ["apple", "apricot", "avocado", "banana", "acorn", "almond", "anise"];
+        let mut sl = SelectList::new(make_items(&labels), 3);
+        sl.set_filter("a");
+        for _ in 0..4 {
+            sl.handle_input(&key(KeyCode::Down));
+        }
+        let state = sl.view_state();
+        assert_eq!(state.filter, "a");
+        assert_eq!(state.selected, 4);
+
+        let mut fresh = SelectList::new(make_items(&labels), 3);
+        fresh.restore_view_state(&state);
+        assert_eq!(fresh.render(30), sl.render(30));
+        assert_eq!(fresh.view_state(), state);
+        assert_eq!(fresh.selected_item().unwrap().value, "almond");
+    }
+
+    #[test]
+    fn restore_view_state_clamps_to_fewer_items() {
+        let mut sl = SelectList::new(make_items(&["alpha", "beta", "gamma"]), 2);
+        sl.restore_view_state(&SelectViewState {
+            selected: 10,
+            scroll_offset: 10,
+            filter: String::new(),
+        });
+        assert_eq!(sl.selected_index(), Some(2));
+        assert_eq!(sl.view_state().scroll_offset, 1);
+
+        sl.restore_view_state(&SelectViewState {
+            selected: 1,
+            scroll_offset: 0,
+            filter: "zzz".to_string(),
+        });
+        assert!(sl.is_empty());
+        assert_eq!(sl.selected_index(), None);
+    }
+
+    // === Theme tests ===
+
+    #[test]
+    fn selection_uses_theme_style() {
+        use crate::theme::Style;
+
+        let items = make_items(&["alpha", "beta"]);
+        let sl = SelectList::new(items, 5);
+        let theme = Theme {
+            selection: Style::new("\x1b[30;46m", "\x1b[0m"),
+            ..Theme::default()
+        };
+        let lines = sl.render_themed(20, &theme);
+        assert!(lines[0].starts_with("\x1b[30;46m→ alpha"));
+        assert!(lines[0].ends_with("\x1b[0m"));
+        assert!(!lines[0].contains("\x1b[1;7m"));
+        assert_eq!(lines[1], format!("{:<20}", "  beta"));
+        // Plain render() keeps the default look.
+        assert!(sl.render(20)[0].starts_with("\x1b[1;7m→ alpha"));
+    }
+
+    // === Type-to-filter tests ===
+
+    #[test]
+    fn typing_narrows_and_backspace_widens() {
+        let items = make_items(&["git commit", "git push", "grep", "cargo"]);
+        let mut sl = SelectList::new(items, 5);
+        sl.set_filterable(true);
+        assert_eq!(sl.len(), 4);
+
+        sl.handle_input(&key(KeyCode::Char('g')));
+        assert_eq!(sl.len(), 3);
+        sl.handle_input(&key(KeyCode::Char('i')));
+        assert_eq!(sl.len(), 2);
+        sl.handle_input(&key(KeyCode::Char('t')));
+        assert_eq!(sl.filter(), "git");
+        assert_eq!(sl.len(), 2);
+
+        sl.handle_input(&key(KeyCode::Down));
+        assert_eq!(sl.selected_item().unwrap().value, "git push");
+
+        sl.handle_input(&key(KeyCode::Backspace));
+        sl.handle_input(&key(KeyCode::Backspace));
+        assert_eq!(sl.filter(), "g");
+        assert_eq!(sl.len(), 3);
+        sl.handle_input(&key(KeyCode::Backspace));
+        assert_eq!(sl.len(), 4);
+        // Backspace on an empty query is harmless.
+        sl.handle_input(&key(KeyCode::Backspace));
+        assert_eq!(sl.len(), 4);
+    }
+
+    #[test]
+    fn filterable_renders_query_header() {
+        let items = make_items(&["git commit", "cargo"]);
+        let mut sl = SelectList::new(items, 5);
+        sl.set_filterable(true);
+        sl.handle_input(&key(KeyCode::Char('g')));
+        sl.handle_input(&key(KeyCode::Char('i')));
+        let lines = sl.render(20);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], format!("{:<20}", "/ gi"));
+        assert!(lines[1].contains("→ git commit"));
+
+        sl.handle_input(&key(KeyCode::Char('x')));
+        let lines = sl.render(20);
+        assert_eq!(lines[0].trim_end(), "/ gix");
+        assert!(crate::utils::strip_ansi(&lines[1]).starts_with("(no items)"));
+    }
+
+    #[test]
+    fn typing_ignored_unless_filterable() {
+        let items = make_items(&["alpha", "beta"]);
+        let mut sl = SelectList::new(items, 5);
+        sl.handle_input(&key(KeyCode::Char('b')));
+        assert_eq!(sl.len(), 2);
+        assert_eq!(sl.render(20).len(), 2);
     }
 
     // === Callback tests ===
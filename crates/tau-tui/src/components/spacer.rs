@@ -1,33 +1,67 @@
-// Spacer component — renders N empty lines for vertical spacing.
+// Spacer component — empty lines for vertical spacing, or blank columns for
+// horizontal layouts.
 
 use crate::component::Component;
 
-/// A component that renders empty lines for vertical spacing.
+/// What kind of space a Spacer takes up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpacerKind {
+    /// N empty lines.
+    Vertical(usize),
+    /// One line of N spaces.
+    Horizontal(usize),
+}
+
+/// A component that renders empty space.
+///
+/// `new()` gives vertical spacing (empty lines). In an `HBox`,
+/// `horizontal()` gives a fixed gap of columns; added with
+/// `HBox::add_flex_child`, any spacer takes a share of the leftover width
+/// to push its siblings apart.
 pub struct Spacer {
-    lines: usize,
+    kind: SpacerKind,
 }
 
 impl Spacer {
     /// Create a new Spacer that renders `lines` empty lines.
     pub fn new(lines: usize) -> Self {
-        Spacer { lines }
+        Spacer {
+            kind: SpacerKind::Vertical(lines),
+        }
+    }
+
+    /// Create a Spacer that renders a single line of `cols` spaces.
+    pub fn horizontal(cols: usize) -> Self {
+        Spacer {
+            kind: SpacerKind::Horizontal(cols),
+        }
     }
 
-    /// Update the number of empty lines.
+    /// Update the number of empty lines. Turns the spacer vertical.
     pub fn set_lines(&mut self, lines: usize) {
-        self.lines = lines;
+        self.kind = SpacerKind::Vertical(lines);
     }
 }
 
 impl Default for Spacer {
     fn default() -> Self {
-        Spacer { lines: 1 }
+        Spacer::new(1)
     }
 }
 
 impl Component for Spacer {
-    fn render(&self, _width: u16) -> Vec<String> {
-        vec![String::new(); self.lines]
+    fn render(&self, width: u16) -> Vec<String> {
+        match self.kind {
+            SpacerKind::Vertical(lines) => vec![String::new(); lines],
+            SpacerKind::Horizontal(cols) => vec![" ".repeat(cols.min(width as usize))],
+        }
+    }
+
+    fn desired_height(&self, _width: u16) -> Option<usize> {
+        match self.kind {
+            SpacerKind::Vertical(lines) => Some(lines),
+            SpacerKind::Horizontal(_) => Some(1),
+        }
     }
 }
 
@@ -73,9 +107,23 @@ mod tests {
         assert_eq!(narrow, wide);
     }
 
+    #[test]
+    fn horizontal_spacer_renders_one_line_of_spaces() {
+        let spacer = Spacer::horizontal(4);
+        assert_eq!(spacer.render(80), vec!["    "]);
+        assert_eq!(spacer.render(2), vec!["  "]);
+    }
+
     #[test]
     fn spacer_is_valid_component() {
         // Verify Spacer can be boxed as dyn Component
         let _boxed: Box<dyn Component> = Box::new(Spacer::new(1));
     }
+
+    #[test]
+    fn desired_height_matches_rendered_lines() {
+        for spacer in [Spacer::new(0), Spacer::new(3), Spacer::horizontal(4)] {
+            assert_eq!(spacer.desired_height(20), Some(spacer.render(20).len()));
+        }
+    }
 }
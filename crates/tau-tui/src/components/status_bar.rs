@@ -0,0 +1,169 @@
+// StatusBar component — a single line with left, center, and right segments.
+
+use crate::component::Component;
+use crate::utils::{truncate_to_width, visible_width};
+
+/// A one-line status bar: left segment flush left, right segment flush right,
+/// center segment centered between them.
+///
+/// Segments may contain ANSI codes. When the segments don't fit, the center
+/// is truncated first, then the right segment, then the left. The line always
+/// spans the full width, with an optional background color (raw ANSI code)
+/// applied across it.
+pub struct StatusBar {
+    left: String,
+    center: String,
+    right: String,
+    bg: Option<String>,
+}
+
+impl StatusBar {
+    /// Create an empty status bar.
+    pub fn new() -> Self {
+        StatusBar {
+            left: String::new(),
+            center: String::new(),
+            right: String::new(),
+            bg: None,
+        }
+    }
+
+    /// Set the left-aligned segment.
+    pub fn set_left(&mut self, text: &str) {
+        self.left = text.to_string();
+    }
+
+    /// Set the centered segment.
+    pub fn set_center(&mut self, text: &str) {
+        self.center = text.to_string();
+    }
+
+    /// Set the right-aligned segment.
+    pub fn set_right(&mut self, text: &str) {
+        self.right = text.to_string();
+    }
+
+    /// Set the background color as a raw ANSI code (e.g., `"\x1b[48;5;236m"`).
+    pub fn set_bg(&mut self, ansi_code: &str) {
+        self.bg = Some(ansi_code.to_string());
+    }
+}
+
+impl Default for StatusBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for StatusBar {
+    fn render(&self, width: u16) -> Vec<String> {
+        if width == 0 {
+            return vec![String::new()];
+        }
+        let width = width as usize;
+
+        // Left and right keep priority; each gets what the other leaves.
+        let left = truncate_to_width(&self.left, width, "…");
+        let left_w = visible_width(&left);
+        let right_budget = width.saturating_sub(left_w + usize::from(left_w > 0));
+        let right = truncate_to_width(&self.right, right_budget, "…");
+        let right_w = visible_width(&right);
+
+        // The center needs a one-column gap from each non-empty neighbor.
+        let gap_l = usize::from(left_w > 0);
+        let gap_r = usize::from(right_w > 0);
+        let center_budget = width.saturating_sub(left_w + gap_l + gap_r + right_w);
+        let center = truncate_to_width(&self.center, center_budget, "…");
+        let center_w = visible_width(&center);
+
+        let mut line = String::new();
+        if let Some(bg) = &self.bg {
+            line.push_str(bg);
+        }
+        line.push_str(&left);
+        if center_w > 0 {
+            let earliest = left_w + gap_l;
+            let latest = width - right_w - gap_r - center_w;
+            let start = (width.saturating_sub(center_w) / 2).clamp(earliest, latest);
+            line.push_str(&" ".repeat(start - left_w));
+            line.push_str(&center);
+            line.push_str(&" ".repeat(width - start - center_w - right_w));
+        } else {
+            line.push_str(&" ".repeat(width - left_w - right_w));
+        }
+        line.push_str(&right);
+        if self.bg.is_some() {
+            line.push_str("\x1b[0m");
+        }
+
+        vec![line]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::strip_ansi;
+
+    fn bar(left: &str, center: &str, right: &str) -> StatusBar {
+        let mut bar = StatusBar::new();
+        bar.set_left(left);
+        bar.set_center(center);
+        bar.set_right(right);
+        bar
+    }
+
+    #[test]
+    fn segments_placed_left_center_right() {
+        let lines = bar("NORMAL", "main.rs", "1:1").render(31);
+        assert_eq!(lines.len(), 1);
+        // Centered on the full width, not between the segments
+        assert_eq!(lines[0], "NORMAL      main.rs         1:1");
+        assert_eq!(visible_width(&lines[0]), 31);
+    }
+
+    #[test]
+    fn width_always_matches_terminal_width() {
+        for width in [0u16, 1, 5, 12, 20, 80] {
+            let line = &bar("mode", "\x1b[1mfile.txt\x1b[0m", "connected").render(width)[0];
+            assert_eq!(visible_width(line), width as usize, "width {}", width);
+        }
+    }
+
+    #[test]
+    fn center_shifts_to_avoid_long_left_segment() {
+        let line = &bar("a long left segment", "mid", "r").render(30)[0];
+        assert_eq!(line, "a long left segment mid      r");
+    }
+
+    #[test]
+    fn overflow_truncates_center_first() {
+        let line = &bar("left", "a very long center segment", "right").render(20)[0];
+        assert!(line.starts_with("left "));
+        assert!(line.ends_with(" right"));
+        assert!(line.contains('…'));
+        assert_eq!(visible_width(line), 20);
+    }
+
+    #[test]
+    fn right_truncated_once_center_is_gone() {
+        let line = &bar("left side", "center", "right side").render(14)[0];
+        assert_eq!(line, "left side rig…");
+    }
+
+    #[test]
+    fn only_left_segment() {
+        let line = &bar("ready", "", "").render(10)[0];
+        assert_eq!(line, "ready     ");
+    }
+
+    #[test]
+    fn background_spans_the_line() {
+        let mut status = bar("L", "C", "R");
+        status.set_bg("\x1b[44m");
+        let line = &status.render(9)[0];
+        assert!(line.starts_with("\x1b[44m"));
+        assert!(line.ends_with("\x1b[0m"));
+        assert_eq!(strip_ansi(line), "L   C   R");
+    }
+}
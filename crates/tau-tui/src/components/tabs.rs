@@ -0,0 +1,309 @@
+// Tabs component — a tab bar over a body that swaps with the active tab.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::component::Component;
+use crate::theme::Theme;
+use crate::utils::{truncate_to_width, visible_width};
+
+/// A tab bar with one body component per tab; only the active body renders.
+///
+/// The first line is the bar: each label padded with a space on both sides,
+/// the active one in bold/inverse. Below it, the active body renders at full
+/// width. Left/Right (or Ctrl+PageUp/PageDown) switch tabs with wrapping; all
+/// other keys are forwarded to the active body, as is focus.
+pub struct Tabs {
+    labels: Vec<String>,
+    bodies: Vec<Box<dyn Component>>,
+    active: usize,
+    /// Whether the tabs have focus, passed on to the active body. Set until
+    /// told otherwise, like `Input`.
+    focused: bool,
+}
+
+impl Tabs {
+    /// Create tabs from parallel lists of labels and bodies. The first tab is
+    /// active. Panics if the lists differ in length.
+    pub fn new(labels: Vec<String>, bodies: Vec<Box<dyn Component>>) -> Self {
+        assert_eq!(
+            labels.len(),
+            bodies.len(),
+            "Tabs needs exactly one body per label"
+        );
+        Tabs {
+            labels,
+            bodies,
+            active: 0,
+            focused: true,
+        }
+    }
+
+    /// Index of the active tab.
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    /// Switch to the tab at `index`. Out-of-bounds indices are ignored.
+    pub fn set_active(&mut self, index: usize) {
+        if index < self.bodies.len() {
+            self.bodies[self.active].set_focused(false);
+            self.active = index;
+            self.bodies[index].set_focused(self.focused);
+        }
+    }
+
+    /// Get a mutable reference to the body of the tab at `index`.
+    pub fn body_mut(&mut self, index: usize) -> Option<&mut Box<dyn Component>> {
+        self.bodies.get_mut(index)
+    }
+
+    /// Number of tabs.
+    pub fn len(&self) -> usize {
+        self.bodies.len()
+    }
+
+    /// Whether there are no tabs.
+    pub fn is_empty(&self) -> bool {
+        self.bodies.is_empty()
+    }
+
+    fn next(&mut self) {
+        if !self.bodies.is_empty() {
+            self.set_active((self.active + 1) % self.bodies.len());
+        }
+    }
+
+    fn previous(&mut self) {
+        if !self.bodies.is_empty() {
+            self.set_active((self.active + self.bodies.len() - 1) % self.bodies.len());
+        }
+    }
+
+    fn render_bar(&self, width: usize, theme: &Theme) -> String {
+        if width == 0 {
+            return String::new();
+        }
+        let mut bar = String::new();
+        for (i, label) in self.labels.iter().enumerate() {
+            if i == self.active {
+                bar.push_str(&theme.selection.paint(&format!(" {} ", label)));
+            } else {
+                bar.push(' ');
+                bar.push_str(label);
+                bar.push(' ');
+            }
+        }
+        let mut bar = truncate_to_width(&bar, width, "");
+        let pad = width.saturating_sub(visible_width(&bar));
+        bar.push_str(&" ".repeat(pad));
+        bar
+    }
+}
+
+impl Component for Tabs {
+    fn render(&self, width: u16) -> Vec<String> {
+        self.render_themed(width, &Theme::default())
+    }
+
+    fn render_themed(&self, width: u16, theme: &Theme) -> Vec<String> {
+        if self.bodies.is_empty() {
+            return vec![];
+        }
+        let mut lines = vec![self.render_bar(width as usize, theme)];
+        lines.extend(self.bodies[self.active].render_themed(width, theme));
+        lines
+    }
+
+    fn handle_input(&mut self, event: &KeyEvent) {
+        let ctrl = event.modifiers.contains(KeyModifiers::CONTROL);
+        match event.code {
+            KeyCode::Right => self.next(),
+            KeyCode::Left => self.previous(),
+            KeyCode::PageDown if ctrl => self.next(),
+            KeyCode::PageUp if ctrl => self.previous(),
+            _ => {
+                if let Some(body) = self.bodies.get_mut(self.active) {
+                    body.handle_input(event);
+                }
+            }
+        }
+    }
+
+    fn invalidate(&mut self) {
+        for body in &mut self.bodies {
+            body.invalidate();
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        if let Some(body) = self.bodies.get_mut(self.active) {
+            body.set_focused(focused);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{BoxComponent, Form, Input};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Mock body returning fixed lines and recording received keys.
+    struct MockBody {
+        lines: Vec<String>,
+        keys: Rc<RefCell<Vec<KeyCode>>>,
+    }
+
+    impl MockBody {
+        fn new(lines: Vec<&str>) -> Self {
+            MockBody {
+                lines: lines.into_iter().map(String::from).collect(),
+                keys: Rc::new(RefCell::new(Vec::new())),
+            }
+        }
+    }
+
+    impl Component for MockBody {
+        fn render(&self, _width: u16) -> Vec<String> {
+            self.lines.clone()
+        }
+
+        fn handle_input(&mut self, event: &KeyEvent) {
+            self.keys.borrow_mut().push(event.code);
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn three_tabs() -> Tabs {
+        Tabs::new(
+            vec!["logs".into(), "stats".into(), "settings".into()],
+            vec![
+                Box::new(MockBody::new(vec!["log line 1", "log line 2"])),
+                Box::new(MockBody::new(vec!["cpu 5%"])),
+                Box::new(MockBody::new(vec!["theme: dark"])),
+            ],
+        )
+    }
+
+    #[test]
+    fn renders_bar_then_only_active_body() {
+        let tabs = three_tabs();
+        let lines = tabs.render(40);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(&lines[1..], &["log line 1", "log line 2"]);
+        assert!(!lines.iter().any(|l| l.contains("cpu") || l.contains("theme")));
+    }
+
+    #[test]
+    fn bar_highlights_active_tab_and_fills_width() {
+        let tabs = three_tabs();
+        let bar = &tabs.render(40)[0];
+        assert!(bar.starts_with("\x1b[1;7m logs \x1b[0m stats  settings "));
+        assert_eq!(visible_width(bar), 40);
+    }
+
+    #[test]
+    fn bar_truncates_to_narrow_width() {
+        let tabs = three_tabs();
+        assert_eq!(visible_width(&tabs.render(8)[0]), 8);
+    }
+
+    #[test]
+    fn right_and_left_switch_with_wrapping() {
+        let mut tabs = three_tabs();
+        tabs.handle_input(&key(KeyCode::Right));
+        assert_eq!(tabs.active_index(), 1);
+        assert_eq!(&tabs.render(40)[1..], &["cpu 5%"]);
+
+        tabs.handle_input(&key(KeyCode::Right));
+        tabs.handle_input(&key(KeyCode::Right));
+        assert_eq!(tabs.active_index(), 0);
+
+        tabs.handle_input(&key(KeyCode::Left));
+        assert_eq!(tabs.active_index(), 2);
+        assert_eq!(&tabs.render(40)[1..], &["theme: dark"]);
+    }
+
+    #[test]
+    fn ctrl_page_keys_switch_tabs() {
+        let mut tabs = three_tabs();
+        tabs.handle_input(&KeyEvent::new(KeyCode::PageDown, KeyModifiers::CONTROL));
+        assert_eq!(tabs.active_index(), 1);
+        tabs.handle_input(&KeyEvent::new(KeyCode::PageUp, KeyModifiers::CONTROL));
+        assert_eq!(tabs.active_index(), 0);
+    }
+
+    #[test]
+    fn set_active_ignores_out_of_bounds() {
+        let mut tabs = three_tabs();
+        tabs.set_active(2);
+        assert_eq!(tabs.active_index(), 2);
+        tabs.set_active(7);
+        assert_eq!(tabs.active_index(), 2);
+    }
+
+    #[test]
+    fn other_keys_go_to_active_body() {
+        let first = MockBody::new(vec!["a"]);
+        let second = MockBody::new(vec!["b"]);
+        let first_keys = first.keys.clone();
+        let second_keys = second.keys.clone();
+        let mut tabs = Tabs::new(
+            vec!["one".into(), "two".into()],
+            vec![Box::new(first), Box::new(second)],
+        );
+
+        tabs.set_active(1);
+        tabs.handle_input(&key(KeyCode::Char('x')));
+        tabs.handle_input(&key(KeyCode::Right)); // switches, not forwarded
+
+        assert!(first_keys.borrow().is_empty());
+        assert_eq!(*second_keys.borrow(), vec![KeyCode::Char('x')]);
+    }
+
+    #[test]
+    fn empty_tabs_render_nothing() {
+        let tabs = Tabs::new(vec![], vec![]);
+        assert!(tabs.is_empty());
+        assert!(tabs.render(40).is_empty());
+    }
+
+    #[test]
+    fn focus_follows_the_active_body() {
+        let mut tabs = Tabs::new(
+            vec!["one".into(), "two".into()],
+            vec![Box::new(Input::new()), Box::new(Input::new())],
+        );
+        let has_cursor = |tabs: &Tabs| tabs.render(20)[1].contains("\x1b[7m");
+        tabs.set_focused(false);
+        assert!(!has_cursor(&tabs));
+        tabs.set_focused(true);
+        assert!(has_cursor(&tabs));
+        tabs.handle_input(&key(KeyCode::Right));
+        assert!(has_cursor(&tabs));
+        assert!(!tabs.body_mut(0).unwrap().render(20)[0].contains("\x1b[7m"));
+    }
+
+    #[test]
+    fn tabs_inside_box_and_form_switch_on_arrows() {
+        let mut boxed = BoxComponent::new(0, 0);
+        boxed.add_child(Box::new(three_tabs()));
+        assert!(boxed.focusable());
+        boxed.handle_input(&key(KeyCode::Right));
+        assert_eq!(boxed.render(40)[1].trim_end(), "cpu 5%");
+
+        let mut form = Form::new();
+        form.add_field("view", "", Box::new(three_tabs()));
+        form.handle_input(&key(KeyCode::Left));
+        assert_eq!(form.render(40)[1].trim_end(), "theme: dark");
+    }
+}
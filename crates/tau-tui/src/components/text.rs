@@ -3,11 +3,12 @@
 use std::cell::RefCell;
 
 use crate::component::Component;
-use crate::utils::{visible_width, wrap_text_with_ansi};
+use crate::utils::{truncate_to_width, visible_width, wrap_text_with_ansi, wrapped_height};
 
-/// Cached render result for a given width.
+/// Cached render result for a given width and line cap.
 struct CachedRender {
     width: u16,
+    max_lines: Option<usize>,
     lines: Vec<String>,
 }
 
@@ -19,6 +20,7 @@ pub struct Text {
     text: String,
     padding_x: u16,
     padding_y: u16,
+    max_lines: Option<usize>,
     cache: RefCell<Option<CachedRender>>,
 }
 
@@ -33,10 +35,18 @@ impl Text {
             text: text.to_string(),
             padding_x,
             padding_y,
+            max_lines: None,
             cache: RefCell::new(None),
         }
     }
 
+    /// Cap the wrapped content at `max_lines` rows (`None`, the default, for
+    /// no cap). When the text wraps to more, the last row shown ends in `…`
+    /// to mark the cut. Vertical padding is not counted.
+    pub fn set_max_lines(&mut self, max_lines: Option<usize>) {
+        self.max_lines = max_lines;
+    }
+
     /// Update the text content. Invalidates the render cache.
     pub fn set_text(&mut self, text: &str) {
         if self.text != text {
@@ -50,7 +60,7 @@ impl Component for Text {
     fn render(&self, width: u16) -> Vec<String> {
         // Check cache
         if let Some(ref cached) = *self.cache.borrow() {
-            if cached.width == width {
+            if cached.width == width && cached.max_lines == self.max_lines {
                 return cached.lines.clone();
             }
         }
@@ -60,6 +70,7 @@ impl Component for Text {
         // Store in cache
         *self.cache.borrow_mut() = Some(CachedRender {
             width,
+            max_lines: self.max_lines,
             lines: lines.clone(),
         });
 
@@ -69,6 +80,21 @@ impl Component for Text {
     fn invalidate(&mut self) {
         self.cache.borrow_mut().take();
     }
+
+    fn desired_height(&self, width: u16) -> Option<usize> {
+        if let Some(ref cached) = *self.cache.borrow() {
+            if cached.width == width && cached.max_lines == self.max_lines {
+                return Some(cached.lines.len());
+            }
+        }
+        let inner_width = (width as usize).saturating_sub(2 * self.padding_x as usize);
+        let content =
+            wrapped_height(&self.text, inner_width).min(self.max_lines.unwrap_or(usize::MAX));
+        if content == 0 {
+            return Some(0);
+        }
+        Some(content + 2 * self.padding_y as usize)
+    }
 }
 
 impl Text {
@@ -85,7 +111,14 @@ impl Text {
             return vec![];
         }
 
-        let wrapped = wrap_text_with_ansi(&self.text, inner_width);
+        let mut wrapped = wrap_text_with_ansi(&self.text, inner_width);
+        if let Some(max_lines) = self.max_lines.filter(|&max| wrapped.len() > max) {
+            wrapped.truncate(max_lines);
+            if let Some(last) = wrapped.last_mut() {
+                // Append the ellipsis, or cut the row to make room for it.
+                *last = truncate_to_width(&format!("{}…", last), inner_width, "…");
+            }
+        }
         let pad_left = " ".repeat(self.padding_x as usize);
 
         let mut lines = Vec::new();
@@ -267,4 +300,60 @@ mod tests {
         let lines = text.render(4);
         assert!(lines.is_empty());
     }
+
+    #[test]
+    fn desired_height_matches_rendered_lines() {
+        let texts = [
+            Text::new("the quick brown fox jumps over the lazy dog", 0, 0),
+            Text::new("the quick brown fox jumps over the lazy dog", 2, 1),
+            Text::new("\x1b[1mbold\x1b[0m words\n\nafter a blank line", 1, 0),
+            Text::new("", 0, 3),
+            Text::new("hello", 3, 1),
+        ];
+        for text in &texts {
+            for width in [4u16, 10, 17, 80] {
+                assert_eq!(
+                    text.desired_height(width),
+                    Some(text.render(width).len()),
+                    "{:?} at width {}",
+                    text.text,
+                    width
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn max_lines_caps_wrap_with_ellipsis() {
+        let mut text = Text::new("one two three four five", 0, 0);
+        assert_eq!(text.render(5).len(), 5);
+
+        text.set_max_lines(Some(3));
+        let lines = text.render(5);
+        assert_eq!(lines, vec!["one  ", "two  ", "thre…"]);
+        assert_eq!(text.desired_height(5), Some(3));
+
+        // Short rows take the ellipsis without losing text.
+        assert_eq!(text.render(6), vec!["one   ", "two   ", "three…"]);
+
+        text.set_max_lines(None);
+        assert_eq!(text.render(5).len(), 5);
+    }
+
+    #[test]
+    fn max_lines_ellipsis_keeps_ansi_codes() {
+        let mut text = Text::new("\x1b[31maaa bbb ccc\x1b[0m", 0, 0);
+        text.set_max_lines(Some(1));
+        let lines = text.render(3);
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].starts_with("\x1b[31m"));
+        assert_eq!(crate::utils::strip_ansi(&lines[0]), "aa…");
+    }
+
+    #[test]
+    fn max_lines_not_reached_renders_unchanged() {
+        let mut text = Text::new("hello world", 0, 0);
+        text.set_max_lines(Some(2));
+        assert_eq!(text.render(7), vec!["hello  ", "world  "]);
+    }
 }
@@ -3,5 +3,6 @@
 pub mod terminal;
 pub mod utils;
 pub mod component;
+pub mod theme;
 pub mod tui;
 pub mod components;
@@ -8,6 +8,8 @@
 use std::any::Any;
 use std::io::{self, Write as IoWrite};
 
+use tokio::sync::mpsc::UnboundedSender;
+
 /// Abstraction over terminal I/O for rendering.
 pub trait Terminal {
     /// Enable raw mode and hide cursor.
@@ -101,6 +103,8 @@ pub struct MockTerminal {
     pub stopped: bool,
     /// Cursor visibility state.
     pub cursor_visible: bool,
+    /// Where `resize()` sends its `Resize` event, if connected.
+    event_tx: Option<UnboundedSender<crossterm::event::Event>>,
 }
 
 impl MockTerminal {
@@ -111,6 +115,7 @@ impl MockTerminal {
             started: false,
             stopped: false,
             cursor_visible: true,
+            event_tx: None,
         }
     }
 
@@ -123,6 +128,32 @@ impl MockTerminal {
     pub fn set_size(&mut self, cols: u16, rows: u16) {
         self.size = (cols, rows);
     }
+
+    /// Connect to a TUI's event channel (see `TUI::crossterm_event_tx()`) so
+    /// that `resize()` also delivers a `Resize` event to the run loop.
+    pub fn set_event_tx(&mut self, tx: UnboundedSender<crossterm::event::Event>) {
+        self.event_tx = Some(tx);
+    }
+
+    /// Simulate a terminal resize: update the size and, if connected, send a
+    /// `Resize` event like a real terminal would.
+    pub fn resize(&mut self, cols: u16, rows: u16) {
+        self.set_size(cols, rows);
+        if let Some(tx) = &self.event_tx {
+            let _ = tx.send(crossterm::event::Event::Resize(cols, rows));
+        }
+    }
+
+    /// The payload of each `write()` call, in order. A TUI render writes
+    /// exactly once, so each entry is one frame.
+    pub fn frames(&self) -> Vec<String> {
+        self.writes.clone()
+    }
+
+    /// The payload of the most recent `write()` call.
+    pub fn last_frame(&self) -> Option<&str> {
+        self.writes.last().map(String::as_str)
+    }
 }
 
 impl Terminal for MockTerminal {
@@ -234,6 +265,33 @@ mod tests {
         assert_eq!(term.size(), (120, 40));
     }
 
+    #[test]
+    fn mock_terminal_frames() {
+        let mut term = MockTerminal::new(80, 24);
+        assert_eq!(term.last_frame(), None);
+        term.write("first");
+        term.write("second");
+        assert_eq!(term.frames(), vec!["first", "second"]);
+        assert_eq!(term.last_frame(), Some("second"));
+    }
+
+    #[test]
+    fn mock_terminal_resize_without_channel() {
+        let mut term = MockTerminal::new(80, 24);
+        term.resize(100, 30);
+        assert_eq!(term.size(), (100, 30));
+    }
+
+    #[test]
+    fn mock_terminal_resize_sends_event() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut term = MockTerminal::new(80, 24);
+        term.set_event_tx(tx);
+        term.resize(60, 20);
+        assert_eq!(term.size(), (60, 20));
+        assert_eq!(rx.try_recv().unwrap(), crossterm::event::Event::Resize(60, 20));
+    }
+
     #[test]
     fn mock_terminal_flush_is_noop() {
         let mut term = MockTerminal::new(80, 24);
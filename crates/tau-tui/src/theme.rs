@@ -0,0 +1,74 @@
+// Theme: the styles built-in components draw highlights with.
+
+/// A text style, as the escape sequences that switch it on and off.
+///
+/// `off` should undo only what `on` set (`\x1b[27m` after `\x1b[7m`, say)
+/// where it can, so styled text inside the span keeps its other attributes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Style {
+    pub on: String,
+    pub off: String,
+}
+
+impl Style {
+    pub fn new(on: impl Into<String>, off: impl Into<String>) -> Self {
+        Style {
+            on: on.into(),
+            off: off.into(),
+        }
+    }
+
+    /// `text` wrapped in the style.
+    pub fn paint(&self, text: &str) -> String {
+        format!("{}{}{}", self.on, text, self.off)
+    }
+}
+
+/// The styles built-in components use for their highlights, so an app can
+/// restyle them all at once (light vs dark terminals, an accent color).
+///
+/// Set one with `TUI::set_theme`; it reaches components through
+/// `Component::render_themed`. The default reproduces the components'
+/// classic look.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// The selected row of a `SelectList` and the active tab of `Tabs`.
+    /// Default: bold inverse.
+    pub selection: Style,
+    /// The `Input` cursor and selected text, and the focus marker of
+    /// `Checkbox` and `RadioGroup`. Default: inverse.
+    pub cursor: Style,
+    /// De-emphasized text, such as the "(no items)" placeholder of an empty
+    /// `SelectList`. Default: faint.
+    pub dim: Style,
+    /// Emphasis, such as the focused field's label in a `Form`. Default: bold.
+    pub accent: Style,
+    /// Errors, such as the prompt of an `Input` whose value is invalid.
+    /// Default: red.
+    pub error: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            selection: Style::new("\x1b[1;7m", "\x1b[0m"),
+            cursor: Style::new("\x1b[7m", "\x1b[27m"),
+            dim: Style::new("\x1b[2m", "\x1b[22m"),
+            accent: Style::new("\x1b[1m", "\x1b[22m"),
+            error: Style::new("\x1b[31m", "\x1b[39m"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paint_wraps_text_in_on_and_off() {
+        let theme = Theme::default();
+        assert_eq!(theme.error.paint("> "), "\x1b[31m> \x1b[39m");
+        let accent = Style::new("\x1b[38;5;208m", "\x1b[39m");
+        assert_eq!(accent.paint("x"), "\x1b[38;5;208mx\x1b[39m");
+    }
+}
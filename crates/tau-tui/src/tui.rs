@@ -3,12 +3,20 @@
 use std::cell::Cell;
 use std::fmt::Write;
 use std::rc::Rc;
+use std::time::Duration;
 use futures::StreamExt;
 use tokio::sync::mpsc::{self, UnboundedSender, UnboundedReceiver};
+use tokio::time::Instant;
 
 use crate::component::{Component, Container};
 use crate::terminal::Terminal;
-use crate::utils::{visible_width, truncate_to_width, slice_from_column};
+use crate::theme::Theme;
+use crate::utils::{
+    visible_width, truncate_to_width, slice_from_column, downsample_truecolor, ColorDepth,
+};
+
+/// How long `flash()` keeps the screen inverted inside `run()`.
+const FLASH_DURATION: Duration = Duration::from_millis(100);
 
 /// Events delivered to the TUI handler.
 #[derive(Debug)]
@@ -71,6 +79,53 @@ struct OverlayEntry {
     saved_focus: Option<usize>,
 }
 
+/// Optional terminal modes that `start()` enables and `stop()` disables.
+#[derive(Debug, Clone, Copy, Default)]
+struct TerminalModes {
+    alt_screen: bool,
+    mouse_capture: bool,
+    bracketed_paste: bool,
+}
+
+impl TerminalModes {
+    /// Escape sequences enabling these modes.
+    fn enable_sequence(&self) -> String {
+        let mut seq = String::new();
+        if self.alt_screen {
+            seq.push_str("\x1b[?1049h");
+        }
+        if self.mouse_capture {
+            // Button/drag tracking, reported in SGR format.
+            seq.push_str("\x1b[?1002h\x1b[?1006h");
+        }
+        if self.bracketed_paste {
+            seq.push_str("\x1b[?2004h");
+        }
+        seq
+    }
+
+    /// Escape sequences disabling these modes, in reverse order.
+    fn disable_sequence(&self) -> String {
+        let mut seq = String::new();
+        if self.bracketed_paste {
+            seq.push_str("\x1b[?2004l");
+        }
+        if self.mouse_capture {
+            seq.push_str("\x1b[?1006l\x1b[?1002l");
+        }
+        if self.alt_screen {
+            seq.push_str("\x1b[?1049l");
+        }
+        seq
+    }
+}
+
+/// A transient message shown in the top-right corner until its timer fires.
+struct Toast {
+    id: u64,
+    message: String,
+}
+
 /// The main TUI engine. Renders a component tree to a terminal.
 ///
 /// Generic over user event type `E`, providing an `mpsc::UnboundedSender<E>`
@@ -85,14 +140,17 @@ pub struct TUI<E: Send + 'static> {
     previous_width: u16,
     /// Logical cursor position: number of content lines from the last render.
     cursor_row: usize,
-    /// Actual terminal cursor row position (may differ from cursor_row after differential render).
+    /// Actual terminal cursor row position (may differ from cursor_row after differential render),
+    /// counted from `viewport_top`, so it never exceeds the terminal height.
     hardware_cursor_row: usize,
+    /// First content row still on screen. Non-zero once the content has
+    /// outgrown the terminal height and the rows above scrolled into the
+    /// scrollback, where the cursor can't reach to redraw them.
+    viewport_top: usize,
     event_tx: UnboundedSender<E>,
     event_rx: Option<UnboundedReceiver<E>>,
     /// Whether the run loop should exit.
     should_quit: bool,
-    /// Index of the focused child component in root (receives key input).
-    focused: Option<usize>,
     /// Sender for injecting terminal (crossterm) events into the run loop.
     /// In production, a spawned task bridges crossterm's EventStream here.
     /// In tests, events are injected directly.
@@ -100,6 +158,32 @@ pub struct TUI<E: Send + 'static> {
     crossterm_rx: Option<UnboundedReceiver<crossterm::event::Event>>,
     /// Stack of overlay entries (topmost is last).
     overlays: Vec<OverlayEntry>,
+    /// Colors the terminal can display; truecolor content is downsampled to fit.
+    color_depth: ColorDepth,
+    /// Highlight styles passed to every component's `render_themed()`.
+    theme: Theme,
+    /// Active toasts, oldest first (rendered top to bottom).
+    toasts: Vec<Toast>,
+    next_toast_id: u64,
+    /// Internal channel on which toast timers report the id to dismiss.
+    /// Kept separate from `event_tx` so dismissal doesn't depend on `E`.
+    toast_tx: UnboundedSender<u64>,
+    toast_rx: Option<UnboundedReceiver<u64>>,
+    /// Minimum time between renders in `run()`; `None` renders after every event.
+    frame_budget: Option<Duration>,
+    /// When `run()` last rendered.
+    last_render: Option<Instant>,
+    /// A render was skipped for the frame budget and is owed when it expires.
+    render_pending: bool,
+    /// Modes `start()` will enable.
+    modes: TerminalModes,
+    /// Modes the last `start()` enabled and `stop()` has yet to disable.
+    active_modes: TerminalModes,
+    /// The next render draws the screen inverted (see `flash()`).
+    flash_pending: bool,
+    /// When the last render drew a flash frame. `run()` holds further renders
+    /// back until `FLASH_DURATION` has passed, then restores the screen.
+    flash_shown: Option<Instant>,
 }
 
 impl<E: Send + 'static> TUI<E> {
@@ -107,6 +191,7 @@ impl<E: Send + 'static> TUI<E> {
     pub fn new(terminal: Box<dyn Terminal>) -> Self {
         let (event_tx, event_rx) = mpsc::unbounded_channel();
         let (crossterm_tx, crossterm_rx) = mpsc::unbounded_channel();
+        let (toast_tx, toast_rx) = mpsc::unbounded_channel();
         TUI {
             terminal,
             root: Container::new(),
@@ -114,16 +199,64 @@ impl<E: Send + 'static> TUI<E> {
             previous_width: 0,
             cursor_row: 0,
             hardware_cursor_row: 0,
+            viewport_top: 0,
             event_tx,
             event_rx: Some(event_rx),
             should_quit: false,
-            focused: None,
             crossterm_tx,
             crossterm_rx: Some(crossterm_rx),
             overlays: Vec::new(),
+            color_depth: ColorDepth::TrueColor,
+            theme: Theme::default(),
+            toasts: Vec::new(),
+            next_toast_id: 0,
+            toast_tx,
+            toast_rx: Some(toast_rx),
+            frame_budget: None,
+            last_render: None,
+            render_pending: false,
+            modes: TerminalModes::default(),
+            active_modes: TerminalModes::default(),
+            flash_pending: false,
+            flash_shown: None,
         }
     }
 
+    /// Set the terminal's color depth. Below `TrueColor`, 24-bit colors in
+    /// rendered lines are rewritten to the nearest available color.
+    /// Defaults to `TrueColor` (no rewriting); see `utils::detect_color_depth()`.
+    pub fn set_color_depth(&mut self, depth: ColorDepth) {
+        self.color_depth = depth;
+    }
+
+    /// Style the built-in components' highlights (selection, cursor, errors,
+    /// ...) with `theme`, from the next render on.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// Cap how often `run()` renders. Events are still handled as they
+    /// arrive, but renders within `1/fps` of the previous one are collapsed
+    /// into a single render when the frame is due. `0` removes the cap.
+    pub fn set_max_fps(&mut self, fps: u32) {
+        self.frame_budget = (fps > 0).then(|| Duration::from_secs(1) / fps);
+    }
+
+    /// Switch to the alternate screen while running. Takes effect at `start()`.
+    pub fn set_alt_screen(&mut self, enabled: bool) {
+        self.modes.alt_screen = enabled;
+    }
+
+    /// Enable mouse reporting while running. Takes effect at `start()`.
+    pub fn set_mouse_capture(&mut self, enabled: bool) {
+        self.modes.mouse_capture = enabled;
+    }
+
+    /// Enable bracketed paste while running. Takes effect at `start()`.
+    pub fn set_bracketed_paste(&mut self, enabled: bool) {
+        self.modes.bracketed_paste = enabled;
+    }
+
     /// Access the root container for adding/removing child components.
     pub fn root(&mut self) -> &mut Container {
         &mut self.root
@@ -147,29 +280,52 @@ impl<E: Send + 'static> TUI<E> {
     }
 
     /// Set which child component in root has focus (receives key input).
-    /// Pass `None` to clear focus.
+    /// Pass `None` to clear focus. Focusing a hidden child — or hiding the
+    /// focused one, noticed on the next key — moves focus on to the next
+    /// visible, focusable child; with none, the hidden child keeps focus but
+    /// receives no input until it is made visible again.
+    ///
+    /// The previously focused child is told it lost focus and the new one
+    /// that it gained it (`Component::set_focused`), so an `Input` shows its
+    /// cursor only while focused without the app syncing its flag. Focus is
+    /// the root container's (`Container::set_focus`), so out-of-bounds
+    /// indices clear it and removing the focused child moves it on.
     pub fn set_focus(&mut self, index: Option<usize>) {
-        self.focused = index;
+        let index = index.map(|idx| self.visible_focus(idx));
+        self.root.set_focus(index);
+    }
+
+    /// `index`, or the next visible, focusable root child if `index` is a
+    /// hidden child.
+    fn visible_focus(&self, index: usize) -> usize {
+        if index >= self.root.len() || self.root.is_child_visible(index) {
+            return index;
+        }
+        self.root.next_focusable(index, true).unwrap_or(index)
     }
 
     /// Returns the index of the currently focused child, if any.
     pub fn focused(&self) -> Option<usize> {
-        self.focused
+        self.root.focused()
     }
 
     /// Show an overlay component on top of the base content.
     ///
-    /// Saves the current focus state. Returns an `OverlayHandle` for
+    /// Saves the current focus state and moves focus to the overlay: the
+    /// component that had it is told it lost it (`Component::set_focused`)
+    /// until `hide_overlay()`. Returns an `OverlayHandle` for
     /// controlling the overlay's visibility. Multiple overlays form a stack;
     /// the topmost visible overlay receives key input.
     pub fn show_overlay(
         &mut self,
-        component: Box<dyn Component>,
+        mut component: Box<dyn Component>,
         options: OverlayOptions,
     ) -> OverlayHandle {
         let hidden = Rc::new(Cell::new(false));
         let handle = OverlayHandle { hidden: hidden.clone() };
-        let saved_focus = self.focused;
+        let saved_focus = self.root.focused();
+        self.input_target().set_focused(false);
+        component.set_focused(true);
         self.overlays.push(OverlayEntry {
             component,
             options,
@@ -179,10 +335,29 @@ impl<E: Send + 'static> TUI<E> {
         handle
     }
 
-    /// Remove the topmost overlay and restore its saved focus state.
+    /// Remove the topmost overlay and restore its saved focus state, giving
+    /// focus back to the overlay below or the base content.
     pub fn hide_overlay(&mut self) {
         if let Some(entry) = self.overlays.pop() {
-            self.focused = entry.saved_focus;
+            self.input_target().set_focused(true);
+            self.set_focus(entry.saved_focus);
+        }
+    }
+
+    /// The topmost visible overlay, or the root if there is none.
+    fn input_target(&mut self) -> &mut dyn Component {
+        match self.overlays.iter_mut().rev().find(|e| !e.hidden.get()) {
+            Some(entry) => entry.component.as_mut(),
+            None => &mut self.root,
+        }
+    }
+
+    /// Set which child of the topmost visible overlay receives key input,
+    /// for overlays that route input among children (e.g. a `Container`;
+    /// see `Component::focus_child`). Does nothing if no overlay is visible.
+    pub fn overlay_set_focus(&mut self, index: Option<usize>) {
+        if let Some(entry) = self.overlays.iter_mut().rev().find(|e| !e.hidden.get()) {
+            entry.component.focus_child(index);
         }
     }
 
@@ -191,21 +366,82 @@ impl<E: Send + 'static> TUI<E> {
         self.overlays.iter().any(|e| !e.hidden.get())
     }
 
-    /// Start the terminal (enable raw mode, hide cursor).
+    /// Ring the terminal bell. Written immediately, outside the render cycle.
+    pub fn bell(&mut self) {
+        self.terminal.write("\x07");
+        self.terminal.flush();
+    }
+
+    /// Visual bell: render one frame with every line in inverse video, then
+    /// restore the screen on the next render. Inside `run()` the inverted
+    /// frame stays up for a short delay — renders requested meanwhile wait
+    /// for it — and is then restored. Only the rows the TUI draws are
+    /// inverted.
+    pub fn flash(&mut self) {
+        self.flash_pending = true;
+        self.flush_render();
+    }
+
+    /// Show a bordered one-line message in the top-right corner for `duration`.
+    ///
+    /// Toasts stack downward in the order they were shown and never take
+    /// input. Dismissal is handled by the run loop, so this must be called
+    /// from within the tokio runtime driving `run()` (e.g. from the handler).
+    pub fn toast(&mut self, message: &str, duration: Duration) {
+        let id = self.next_toast_id;
+        self.next_toast_id += 1;
+        self.toasts.push(Toast {
+            id,
+            message: message.to_string(),
+        });
+
+        let tx = self.toast_tx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(duration).await;
+            let _ = tx.send(id);
+        });
+    }
+
+    /// Start the terminal (enable raw mode, hide cursor), then enable the
+    /// alt-screen / mouse / bracketed-paste modes that were requested.
     pub fn start(&mut self) {
         self.terminal.start();
+        let enable = self.modes.enable_sequence();
+        if !enable.is_empty() {
+            self.terminal.write(&enable);
+            self.terminal.flush();
+        }
+        self.active_modes = self.modes;
     }
 
-    /// Stop the terminal (show cursor, disable raw mode).
-    /// Moves cursor from `hardware_cursor_row` to `cursor_row` (end of content)
-    /// so the shell prompt appears below all TUI output, not mid-content.
+    /// Stop the terminal (show cursor, disable raw mode), leaving it as it
+    /// was before `start()`:
+    /// - overlays and toasts are hidden, and the content beneath redrawn;
+    /// - the cursor moves from `hardware_cursor_row` to `cursor_row` (end of
+    ///   content) so the shell prompt appears below all TUI output;
+    /// - SGR attributes are reset and every mode `start()` enabled is disabled.
     pub fn stop(&mut self) {
-        if self.hardware_cursor_row < self.cursor_row {
-            let n = self.cursor_row - self.hardware_cursor_row;
-            self.terminal.write(&format!("\x1b[{}B", n));
-            self.terminal.flush();
-            self.hardware_cursor_row = self.cursor_row;
+        let mut covered = !self.toasts.is_empty();
+        for entry in &self.overlays {
+            covered |= !entry.hidden.replace(true);
         }
+        self.toasts.clear();
+        if covered && !self.previous_lines.is_empty() {
+            self.render();
+        }
+
+        let mut output = String::new();
+        let end_row = self.cursor_row.saturating_sub(self.viewport_top);
+        if self.hardware_cursor_row < end_row {
+            let n = end_row - self.hardware_cursor_row;
+            let _ = write!(output, "\x1b[{}B", n);
+            self.hardware_cursor_row = end_row;
+        }
+        output.push_str("\x1b[0m");
+        output.push_str(&self.active_modes.disable_sequence());
+        self.active_modes = TerminalModes::default();
+        self.terminal.write(&output);
+        self.terminal.flush();
         self.terminal.stop();
     }
 
@@ -218,19 +454,21 @@ impl<E: Send + 'static> TUI<E> {
     /// on exit.
     ///
     /// Key events are automatically forwarded to the focused component
-    /// (if any) before the handler is called.
+    /// (if any) before the handler is called. With `set_max_fps()`, renders
+    /// are coalesced to the frame rate.
     pub async fn run<F>(&mut self, mut handler: F)
     where
         F: FnMut(Event<E>, &mut TUI<E>),
     {
         self.start();
-        self.render();
+        self.flush_render();
 
         let mut user_rx = self.event_rx.take().expect("run() can only be called once");
         let mut crossterm_rx = self
             .crossterm_rx
             .take()
             .expect("run() can only be called once");
+        let mut toast_rx = self.toast_rx.take().expect("run() can only be called once");
 
         // Spawn a task that bridges crossterm's EventStream to our channel.
         let ct_tx = self.crossterm_tx.clone();
@@ -248,19 +486,49 @@ impl<E: Send + 'static> TUI<E> {
             }
         });
 
+        // A closed user channel only stops user events; the loop runs on
+        // until `quit()` or until terminal input ends (every crossterm
+        // sender, including our own, is gone).
+        let mut user_open = true;
         loop {
+            // A flash on screen holds the next frame back until it is over;
+            // that frame restores the screen even if nothing else changed.
+            let render_due = self.render_pending || self.flash_shown.is_some();
+            let next_frame = match (self.last_render, self.frame_budget) {
+                (Some(last), Some(budget)) => last + budget,
+                _ => Instant::now(),
+            };
+            let next_frame = match self.flash_end() {
+                Some(end) => next_frame.max(end),
+                None => next_frame,
+            };
             let event = tokio::select! {
-                Some(ct_event) = crossterm_rx.recv() => {
+                ct_event = crossterm_rx.recv() => {
                     match ct_event {
-                        crossterm::event::Event::Key(key) => Some(Event::Key(key)),
-                        crossterm::event::Event::Resize(w, h) => Some(Event::Resize(w, h)),
-                        _ => None,
+                        Some(crossterm::event::Event::Key(key)) => Some(Event::Key(key)),
+                        Some(crossterm::event::Event::Resize(w, h)) => Some(Event::Resize(w, h)),
+                        Some(_) => None,
+                        None => break, // terminal input ended
+                    }
+                }
+                user_event = user_rx.recv(), if user_open => {
+                    match user_event {
+                        Some(user_event) => Some(Event::User(user_event)),
+                        None => {
+                            user_open = false;
+                            None
+                        }
                     }
                 }
-                Some(user_event) = user_rx.recv() => {
-                    Some(Event::User(user_event))
+                Some(toast_id) = toast_rx.recv() => {
+                    self.toasts.retain(|toast| toast.id != toast_id);
+                    self.request_render();
+                    None
+                }
+                _ = tokio::time::sleep_until(next_frame), if render_due => {
+                    self.flush_render();
+                    None
                 }
-                else => break,
             };
 
             if let Some(event) = event {
@@ -275,16 +543,18 @@ impl<E: Send + 'static> TUI<E> {
                         }
                     }
                     if !forwarded {
-                        if let Some(idx) = self.focused {
-                            if let Some(child) = self.root.child_mut(idx) {
-                                child.handle_input(key);
+                        if let Some(idx) = self.root.focused() {
+                            let next = self.visible_focus(idx);
+                            if next != idx {
+                                self.set_focus(Some(next));
                             }
                         }
+                        self.root.handle_input(key);
                     }
                 }
 
                 handler(event, self);
-                self.render();
+                self.request_render();
             }
 
             if self.should_quit {
@@ -292,10 +562,41 @@ impl<E: Send + 'static> TUI<E> {
             }
         }
 
+        if self.render_pending {
+            self.flush_render();
+        }
         reader_handle.abort();
         self.stop();
     }
 
+    /// When the flash on screen, if any, is due to be restored.
+    fn flash_end(&self) -> Option<Instant> {
+        self.flash_shown.map(|at| at + FLASH_DURATION)
+    }
+
+    /// Render now, unless the previous render was less than a frame ago or
+    /// a flash is still on screen — then mark the render as owed for the run
+    /// loop's frame timer.
+    fn request_render(&mut self) {
+        if self.flash_end().is_some_and(|end| Instant::now() < end) {
+            self.render_pending = true;
+            return;
+        }
+        match (self.last_render, self.frame_budget) {
+            (Some(last), Some(budget)) if last.elapsed() < budget => {
+                self.render_pending = true;
+            }
+            _ => self.flush_render(),
+        }
+    }
+
+    /// Render and restart the frame budget.
+    fn flush_render(&mut self) {
+        self.render();
+        self.render_pending = false;
+        self.last_render = Some(Instant::now());
+    }
+
     /// Render the component tree to the terminal with differential rendering.
     ///
     /// Compares new lines vs `previous_lines` to minimize terminal output:
@@ -303,12 +604,24 @@ impl<E: Send + 'static> TUI<E> {
     /// - Width changed: full re-render with screen clear
     /// - Otherwise: only rewrites changed lines using cursor movement
     ///
+    /// Content taller than the terminal scrolls its top rows into the
+    /// scrollback. From then on the viewport is clamped: changes to those
+    /// rows can't be redrawn and are skipped, and cursor movement stays
+    /// within the screen.
+    ///
     /// Builds a single `String` buffer, wraps in synchronized output markers,
     /// then calls `terminal.write()` + `terminal.flush()` once.
     /// If nothing changed, no output is written at all.
+    ///
+    /// A terminal reporting no columns or rows (mid-resize, or detached) is
+    /// skipped entirely; the next render at a real size picks up from the
+    /// last frame drawn.
     pub fn render(&mut self) {
-        let (width, _height) = self.terminal.size();
-        let mut lines = self.root.render(width);
+        let (width, height) = self.terminal.size();
+        if width == 0 || height == 0 {
+            return;
+        }
+        let mut lines = self.root.render_themed(width, &self.theme);
 
         // Composite visible overlays onto base content
         for overlay in &self.overlays {
@@ -319,7 +632,7 @@ impl<E: Send + 'static> TUI<E> {
             if ov_width == 0 {
                 continue;
             }
-            let mut ov_lines = overlay.component.render(ov_width as u16);
+            let mut ov_lines = overlay.component.render_themed(ov_width as u16, &self.theme);
             if let Some(max_h) = overlay.options.max_height {
                 ov_lines.truncate(max_h as usize);
             }
@@ -348,6 +661,38 @@ impl<E: Send + 'static> TUI<E> {
             }
         }
 
+        // Toasts go on top of everything, stacked down from the top-right
+        let mut row = 0;
+        for toast in &self.toasts {
+            let toast_lines = render_toast(&toast.message, width as usize);
+            let Some(toast_width) = toast_lines.first().map(|l| visible_width(l)) else {
+                continue;
+            };
+            let col = (width as usize).saturating_sub(toast_width);
+            while lines.len() < row + toast_lines.len() {
+                lines.push(String::new());
+            }
+            for toast_line in &toast_lines {
+                lines[row] = splice_overlay_into_line(&lines[row], col, toast_line, toast_width);
+                row += 1;
+            }
+        }
+
+        if std::mem::take(&mut self.flash_pending) {
+            for line in &mut lines {
+                *line = invert_line(line, width as usize);
+            }
+            self.flash_shown = Some(Instant::now());
+        } else {
+            self.flash_shown = None;
+        }
+
+        if self.color_depth != ColorDepth::TrueColor {
+            for line in &mut lines {
+                *line = downsample_truecolor(line, self.color_depth);
+            }
+        }
+
         let mut buffer = String::new();
         let is_first_render = self.previous_width == 0;
 
@@ -357,7 +702,8 @@ impl<E: Send + 'static> TUI<E> {
                 buffer.push_str(line);
                 buffer.push_str("\x1b[0m\r\n");
             }
-            self.hardware_cursor_row = lines.len();
+            self.viewport_top = 0;
+            self.place_cursor(lines.len(), height);
         } else if width != self.previous_width {
             // Width changed: full re-render with screen clear
             buffer.push_str("\x1b[3J\x1b[2J\x1b[H");
@@ -365,7 +711,8 @@ impl<E: Send + 'static> TUI<E> {
                 buffer.push_str(line);
                 buffer.push_str("\x1b[0m\r\n");
             }
-            self.hardware_cursor_row = lines.len();
+            self.viewport_top = 0;
+            self.place_cursor(lines.len(), height);
         } else {
             // Differential render: compare previous vs new
             let old = &self.previous_lines;
@@ -386,12 +733,17 @@ impl<E: Send + 'static> TUI<E> {
                 }
             }
 
+            // Rows scrolled off the top can't be reached; skip their changes.
+            let last_changed = last_changed.filter(|&last| last >= self.viewport_top);
+            let first_changed = first_changed.map(|first| first.max(self.viewport_top));
+
             if let (Some(first), Some(last)) = (first_changed, last_changed) {
                 // Move cursor from hardware_cursor_row to first_changed
-                if self.hardware_cursor_row > first {
-                    write!(buffer, "\x1b[{}A", self.hardware_cursor_row - first).unwrap();
-                } else if self.hardware_cursor_row < first {
-                    write!(buffer, "\x1b[{}B", first - self.hardware_cursor_row).unwrap();
+                let cursor = self.viewport_top + self.hardware_cursor_row;
+                if cursor > first {
+                    write!(buffer, "\x1b[{}A", cursor - first).unwrap();
+                } else if cursor < first {
+                    write!(buffer, "\x1b[{}B", first - cursor).unwrap();
                 }
                 buffer.push('\r'); // Ensure column 0
 
@@ -407,15 +759,15 @@ impl<E: Send + 'static> TUI<E> {
                     }
                 }
 
-                // Cursor is now at last + 1
+                // Cursor is now at last + 1, which may have scrolled the screen
                 let cursor_pos = last + 1;
+                self.place_cursor(cursor_pos, height);
 
                 // If we went past the new content end, move cursor back
-                if cursor_pos > lines.len() {
-                    write!(buffer, "\x1b[{}A", cursor_pos - lines.len()).unwrap();
-                    self.hardware_cursor_row = lines.len();
-                } else {
-                    self.hardware_cursor_row = cursor_pos;
+                let end = lines.len().max(self.viewport_top);
+                if cursor_pos > end {
+                    write!(buffer, "\x1b[{}A", cursor_pos - end).unwrap();
+                    self.place_cursor(end, height);
                 }
             }
             // else: no changes, buffer stays empty → no write
@@ -437,6 +789,17 @@ impl<E: Send + 'static> TUI<E> {
         self.previous_width = width;
     }
 
+    /// Record that the terminal cursor sits on content row `row`, scrolling
+    /// the viewport if that row lies below the bottom of a `height`-row
+    /// screen (writing a newline on the last row scrolls the terminal).
+    fn place_cursor(&mut self, row: usize, height: u16) {
+        let bottom = (height as usize).max(1) - 1;
+        if row > self.viewport_top + bottom {
+            self.viewport_top = row - bottom;
+        }
+        self.hardware_cursor_row = row - self.viewport_top;
+    }
+
     /// Access stored lines from the previous render.
     pub fn previous_lines(&self) -> &[String] {
         &self.previous_lines
@@ -470,16 +833,48 @@ fn calculate_overlay_position(
         ),
     };
 
-    let row = (base_row as i32 + options.offset_y as i32).max(0) as usize;
-    let col = (base_col as i32 + options.offset_x as i32).max(0) as usize;
+    // Keep the whole overlay inside the content area when it fits; one
+    // larger than the content is pinned to the top/left edge.
+    let max_row = content_height.saturating_sub(overlay_height) as i32;
+    let max_col = content_width.saturating_sub(overlay_width) as i32;
+    let row = (base_row as i32 + options.offset_y as i32).clamp(0, max_row) as usize;
+    let col = (base_col as i32 + options.offset_x as i32).clamp(0, max_col) as usize;
     (row, col)
 }
 
+/// Draw `line` in inverse video across the full `width`, re-applying the
+/// inverse after every SGR reset inside it.
+fn invert_line(line: &str, width: usize) -> String {
+    let pad = width.saturating_sub(visible_width(line));
+    format!(
+        "\x1b[7m{}{}\x1b[27m",
+        line.replace("\x1b[0m", "\x1b[0m\x1b[7m"),
+        " ".repeat(pad)
+    )
+}
+
+/// Render a toast as a three-line bordered box no wider than `max_width`.
+/// Returns no lines if even an empty box wouldn't fit.
+fn render_toast(message: &str, max_width: usize) -> Vec<String> {
+    if max_width < 4 {
+        return vec![];
+    }
+    let text = truncate_to_width(message, max_width - 4, "…");
+    let inner = visible_width(&text) + 2;
+    vec![
+        format!("┌{}┐", "─".repeat(inner)),
+        format!("│ {} │", text),
+        format!("└{}┘", "─".repeat(inner)),
+    ]
+}
+
 /// Splice overlay content into a base line at the given column position.
 ///
 /// Cuts the base line at `col`, inserts the overlay content, then resumes
-/// the base line after `col + overlay_width`. Resets ANSI state between
-/// sections and re-applies the base's SGR state for the "after" portion.
+/// the base line after `col + overlay_width`. The SGR state open where the
+/// base is cut is reset before any padding and the overlay, the overlay's
+/// own state is reset after it, and the "after" portion re-opens exactly
+/// the base's SGR state at its column — so no color crosses either edge.
 fn splice_overlay_into_line(
     base: &str,
     col: usize,
@@ -494,28 +889,42 @@ fn splice_overlay_into_line(
     let after_col = col + overlay_width;
     let after = if after_col < base_width {
         let (sgr, remaining) = slice_from_column(base, after_col);
-        format!("{}{}", sgr, remaining)
+        // A wide character straddling `after_col` is skipped whole; blank
+        // the column of it the overlay didn't cover.
+        let resumed_at = base_width.saturating_sub(visible_width(&remaining));
+        let blank = " ".repeat(resumed_at.saturating_sub(after_col));
+        format!("{}{}{}", sgr, blank, remaining)
     } else {
         String::new()
     };
 
-    format!("{}{}\x1b[0m{}\x1b[0m{}", before, pad, overlay, after)
+    format!("{}\x1b[0m{}{}\x1b[0m{}", before, pad, overlay, after)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::component::Component;
+    use crate::components::Input;
     use crate::terminal::MockTerminal;
+    use crate::utils::strip_ansi;
 
     /// Helper: get a reference to the MockTerminal inside a TUI.
-    fn mock_terminal(tui: &TUI<()>) -> &MockTerminal {
+    fn mock_terminal<E: Send + 'static>(tui: &TUI<E>) -> &MockTerminal {
         tui.terminal
             .as_any()
             .downcast_ref::<MockTerminal>()
             .expect("terminal should be MockTerminal")
     }
 
+    /// Helper: get a mutable reference to the MockTerminal inside a TUI.
+    fn mock_terminal_mut<E: Send + 'static>(tui: &mut TUI<E>) -> &mut MockTerminal {
+        tui.terminal
+            .as_any_mut()
+            .downcast_mut::<MockTerminal>()
+            .expect("terminal should be MockTerminal")
+    }
+
     /// A simple test component that returns fixed lines.
     struct StubComponent {
         lines: Vec<String>,
@@ -623,6 +1032,37 @@ mod tests {
         assert!(output.contains("\x1b[31mred\x1b[0m\x1b[0m\r\n"));
     }
 
+    #[test]
+    fn render_downsamples_truecolor_when_configured() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        tui.root()
+            .add_child(Box::new(StubComponent::new(&["\x1b[38;2;255;0;0mred"])));
+        tui.set_color_depth(ColorDepth::Ansi256);
+        tui.render();
+        let output = mock_terminal(&tui).output();
+        assert!(output.contains("\x1b[38;5;196mred"));
+        assert!(!output.contains("38;2;"));
+    }
+
+    #[test]
+    fn theme_reaches_nested_components() {
+        use crate::components::{BoxComponent, SelectItem, SelectList};
+        use crate::theme::Style;
+
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(40, 24)));
+        let mut boxed = BoxComponent::new(1, 0);
+        boxed.add_child(Box::new(SelectList::new(vec![SelectItem::new("a", "a")], 5)));
+        tui.root().add_child(Box::new(boxed));
+        tui.set_theme(Theme {
+            selection: Style::new("\x1b[4m", "\x1b[24m"),
+            ..Theme::default()
+        });
+        tui.render();
+        let output = mock_terminal(&tui).output();
+        assert!(output.contains("\x1b[4m→ a"));
+        assert!(!output.contains("\x1b[1;7m"));
+    }
+
     #[test]
     fn render_stores_previous_lines() {
         let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
@@ -685,6 +1125,29 @@ mod tests {
         assert!(output.ends_with("\x1b[?2026l"));
     }
 
+    #[test]
+    fn zero_sized_terminal_skips_render() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(0, 24)));
+        tui.root()
+            .add_child(Box::new(StubComponent::new(&["hello"])));
+        tui.render();
+        assert!(mock_terminal(&tui).writes.is_empty());
+
+        mock_terminal_mut(&mut tui).set_size(80, 24);
+        tui.render();
+        assert_eq!(mock_terminal(&tui).writes.len(), 1);
+
+        // A transient zero size mid-session draws nothing and loses nothing.
+        mock_terminal_mut(&mut tui).set_size(80, 0);
+        tui.render();
+        mock_terminal_mut(&mut tui).set_size(0, 0);
+        tui.render();
+        assert_eq!(mock_terminal(&tui).writes.len(), 1);
+        mock_terminal_mut(&mut tui).set_size(80, 24);
+        tui.render();
+        assert_eq!(mock_terminal(&tui).writes.len(), 1, "same frame, nothing to redraw");
+    }
+
     #[test]
     fn same_content_second_render_no_output() {
         let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
@@ -774,7 +1237,7 @@ mod tests {
             .add_child(Box::new(StubComponent::new(&["A", "X", "C"])));
         tui.render(); // differential render
 
-        let last_write = mock_terminal(&tui).writes.last().unwrap();
+        let last_write = mock_terminal(&tui).last_frame().unwrap();
         // Should contain exactly one \x1b[2K (for the single changed line)
         assert_eq!(
             last_write.matches("\x1b[2K").count(),
@@ -802,14 +1265,10 @@ mod tests {
         tui.render(); // first render at width 80
 
         // Change terminal width
-        tui.terminal
-            .as_any_mut()
-            .downcast_mut::<MockTerminal>()
-            .unwrap()
-            .set_size(120, 24);
+        mock_terminal_mut(&mut tui).set_size(120, 24);
         tui.render(); // should trigger full redraw
 
-        let last_write = mock_terminal(&tui).writes.last().unwrap();
+        let last_write = mock_terminal(&tui).last_frame().unwrap();
         // Full redraw: contains clear-screen sequence
         assert!(
             last_write.contains("\x1b[3J\x1b[2J\x1b[H"),
@@ -831,7 +1290,7 @@ mod tests {
             .add_child(Box::new(StubComponent::new(&["A", "B", "C"])));
         tui.render(); // differential: grew from 1 to 3
 
-        let last_write = mock_terminal(&tui).writes.last().unwrap();
+        let last_write = mock_terminal(&tui).last_frame().unwrap();
         // Should contain the new lines
         assert!(last_write.contains("B"), "contains new line B");
         assert!(last_write.contains("C"), "contains new line C");
@@ -854,7 +1313,7 @@ mod tests {
             .add_child(Box::new(StubComponent::new(&["A", "B"])));
         tui.render(); // differential: shrunk from 4 to 2
 
-        let last_write = mock_terminal(&tui).writes.last().unwrap();
+        let last_write = mock_terminal(&tui).last_frame().unwrap();
         // Should contain clear sequences for the removed lines (C and D)
         // Lines at indices 2 and 3 are cleared with \x1b[2K
         assert_eq!(
@@ -879,7 +1338,7 @@ mod tests {
         tui.root().clear();
         tui.render(); // differential: shrunk to empty
 
-        let last_write = mock_terminal(&tui).writes.last().unwrap();
+        let last_write = mock_terminal(&tui).last_frame().unwrap();
         // Two lines cleared
         assert_eq!(last_write.matches("\x1b[2K").count(), 2);
         // Cursor returns to row 0
@@ -895,7 +1354,7 @@ mod tests {
             .add_child(Box::new(StubComponent::new(&["A", "B"])));
         tui.render(); // differential: grew from empty to 2 lines
 
-        let last_write = mock_terminal(&tui).writes.last().unwrap();
+        let last_write = mock_terminal(&tui).last_frame().unwrap();
         assert!(last_write.contains("A"));
         assert!(last_write.contains("B"));
     }
@@ -946,7 +1405,7 @@ mod tests {
             .add_child(Box::new(StubComponent::new(&["A", "X", "C", "D", "Z"])));
         tui.render();
 
-        let last_write = mock_terminal(&tui).writes.last().unwrap();
+        let last_write = mock_terminal(&tui).last_frame().unwrap();
         // Move down from row 2 to row 4 = 2 down
         assert!(
             last_write.contains("\x1b[2B"),
@@ -985,7 +1444,7 @@ mod tests {
             .add_child(Box::new(StubComponent::new(&["A", "X"])));
         tui.render();
 
-        let last_write = mock_terminal(&tui).writes.last().unwrap();
+        let last_write = mock_terminal(&tui).last_frame().unwrap();
         assert!(
             last_write.starts_with("\x1b[?2026h"),
             "differential render starts with sync start"
@@ -1015,6 +1474,54 @@ mod tests {
         );
     }
 
+    /// Largest `\x1b[<n>A` cursor-up distance in `frame`.
+    fn max_cursor_up(frame: &str) -> usize {
+        frame
+            .split("\x1b[")
+            .skip(1)
+            .filter_map(|seq| seq.split_once('A'))
+            .filter_map(|(n, _)| n.parse().ok())
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn overflowing_first_render_keeps_cursor_on_screen() {
+        let owned: Vec<String> = (0..40).map(|i| format!("line {}", i)).collect();
+        fn lines(owned: &[String]) -> Vec<&str> {
+            owned.iter().map(String::as_str).collect()
+        }
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        tui.root()
+            .add_child(Box::new(StubComponent::new(&lines(&owned))));
+        tui.render();
+        assert!(tui.hardware_cursor_row < 24);
+        assert_eq!(tui.viewport_top, 17);
+
+        // One change on screen, one scrolled into the scrollback.
+        let mut changed = owned.clone();
+        changed[2] = "off screen".to_string();
+        changed[30] = "on screen".to_string();
+        tui.root().clear();
+        tui.root()
+            .add_child(Box::new(StubComponent::new(&lines(&changed))));
+        tui.render();
+        let frame = mock_terminal(&tui).last_frame().unwrap();
+        assert!(frame.contains("on screen"));
+        assert!(!frame.contains("off screen"), "scrolled-off rows aren't redrawn");
+        assert!(max_cursor_up(frame) < 24, "cursor moves stay on screen: {:?}", frame);
+        assert!(tui.hardware_cursor_row < 24);
+
+        // Growing further scrolls the viewport along.
+        changed.extend((40..50).map(|i| format!("line {}", i)));
+        tui.root().clear();
+        tui.root()
+            .add_child(Box::new(StubComponent::new(&lines(&changed))));
+        tui.render();
+        assert!(tui.hardware_cursor_row < 24);
+        assert_eq!(tui.viewport_top, 27);
+    }
+
     #[test]
     fn diff_cursor_row_tracks_logical_end() {
         let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
@@ -1051,12 +1558,112 @@ mod tests {
     #[test]
     fn set_focus_and_read_back() {
         let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        tui.root().add_child(Box::new(StubComponent::new(&["child0"])));
         tui.set_focus(Some(0));
         assert_eq!(tui.focused(), Some(0));
         tui.set_focus(None);
         assert_eq!(tui.focused(), None);
     }
 
+    #[test]
+    fn set_focus_skips_hidden_children() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        tui.root().add_child(Box::new(Input::new()));
+        tui.root().add_child(Box::new(StubComponent::new(&["label"])));
+        tui.root().add_child(Box::new(Input::new()));
+        tui.root().set_child_visible(0, false);
+
+        tui.set_focus(Some(0));
+        assert_eq!(tui.focused(), Some(2));
+
+        // With nothing else to take it, the hidden child keeps focus.
+        tui.root().set_child_visible(2, false);
+        tui.set_focus(Some(2));
+        assert_eq!(tui.focused(), Some(2));
+    }
+
+    #[tokio::test]
+    async fn run_moves_focus_off_a_hidden_child_before_forwarding_keys() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        tui.root().add_child(Box::new(Input::new()));
+        tui.root().add_child(Box::new(Input::new()));
+        tui.set_focus(Some(0));
+        tui.root().set_child_visible(0, false);
+        let key = crossterm::event::KeyEvent::new(
+            crossterm::event::KeyCode::Char('x'),
+            crossterm::event::KeyModifiers::NONE,
+        );
+        tui.crossterm_event_tx()
+            .send(crossterm::event::Event::Key(key))
+            .unwrap();
+
+        tui.run(|_event, tui| tui.quit()).await;
+
+        assert_eq!(tui.focused(), Some(1));
+        assert_eq!(tui.root().child_mut(1).unwrap().form_value().as_deref(), Some("x"));
+    }
+
+    #[test]
+    fn set_focus_moves_input_cursor() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        tui.root().add_child(Box::new(Input::new()));
+        tui.root().add_child(Box::new(Input::new()));
+        let has_cursor = |tui: &mut TUI<()>, idx: usize| {
+            tui.root().child_mut(idx).unwrap().render(20)[0].contains("\x1b[7m")
+        };
+
+        tui.set_focus(Some(0));
+        tui.set_focus(Some(1));
+        assert!(!has_cursor(&mut tui, 0));
+        assert!(has_cursor(&mut tui, 1));
+
+        tui.set_focus(Some(0));
+        assert!(has_cursor(&mut tui, 0));
+        assert!(!has_cursor(&mut tui, 1));
+
+        tui.set_focus(None);
+        assert!(!has_cursor(&mut tui, 0));
+    }
+
+    #[test]
+    fn removing_focused_root_child_moves_focus() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        tui.root().add_child(Box::new(Input::new()));
+        tui.root().add_child(Box::new(Input::new()));
+        tui.set_focus(Some(0));
+        tui.root().remove_child(0);
+        assert_eq!(tui.focused(), Some(0));
+        assert!(tui.root().child_mut(0).unwrap().render(20)[0].contains("\x1b[7m"));
+        tui.root().remove_child(0);
+        assert_eq!(tui.focused(), None);
+    }
+
+    #[test]
+    fn overlay_takes_focus_from_base_child_until_hidden() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        tui.root().add_child(Box::new(Input::new()));
+        tui.set_focus(Some(0));
+        let has_cursor = |tui: &mut TUI<()>| {
+            tui.root().child_mut(0).unwrap().render(20)[0].contains("\x1b[7m")
+        };
+
+        tui.show_overlay(
+            Box::new(Input::new()),
+            OverlayOptions {
+                width: 20,
+                max_height: None,
+                anchor: Anchor::Center,
+                offset_x: 0,
+                offset_y: 0,
+            },
+        );
+        assert!(!has_cursor(&mut tui));
+        assert!(tui.overlays[0].component.render(20)[0].contains("\x1b[7m"));
+
+        tui.hide_overlay();
+        assert!(has_cursor(&mut tui));
+    }
+
     // ── Async event loop (run) ──────────────────────────────────────
 
     #[tokio::test]
@@ -1107,6 +1714,55 @@ mod tests {
         assert!(received_key, "handler should receive key event");
     }
 
+    #[tokio::test]
+    async fn run_survives_closed_user_channel() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        // Close the user channel outright, as if every sender were dropped.
+        let (closed_tx, closed_rx) = mpsc::unbounded_channel();
+        drop(closed_tx);
+        tui.event_rx = Some(closed_rx);
+        tui.event_tx = mpsc::unbounded_channel().0;
+
+        let ct_tx = tui.crossterm_event_tx();
+        tokio::spawn(async move {
+            for c in ['a', 'b', 'q'] {
+                tokio::task::yield_now().await;
+                let key = crossterm::event::KeyEvent::new(
+                    crossterm::event::KeyCode::Char(c),
+                    crossterm::event::KeyModifiers::NONE,
+                );
+                ct_tx.send(crossterm::event::Event::Key(key)).unwrap();
+            }
+        });
+
+        let mut keys = Vec::new();
+        tui.run(|event, tui| {
+            if let Event::Key(key) = event {
+                keys.push(key.code);
+                if key.code == crossterm::event::KeyCode::Char('q') {
+                    tui.quit();
+                }
+            }
+        })
+        .await;
+
+        use crossterm::event::KeyCode::Char;
+        assert_eq!(keys, [Char('a'), Char('b'), Char('q')]);
+    }
+
+    #[tokio::test]
+    async fn run_ends_when_terminal_input_ends() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        let (closed_tx, closed_rx) = mpsc::unbounded_channel();
+        drop(closed_tx);
+        tui.crossterm_rx = Some(closed_rx);
+        tui.crossterm_tx = mpsc::unbounded_channel().0;
+        // The user channel stays open; run() returns anyway, without quit().
+        let _tx = tui.event_tx();
+        tui.run(|_event, _tui| {}).await;
+        assert!(!tui.should_quit);
+    }
+
     #[tokio::test]
     async fn run_quit_breaks_loop() {
         let mut tui: TUI<String> = TUI::new(Box::new(MockTerminal::new(80, 24)));
@@ -1149,11 +1805,7 @@ mod tests {
         })
         .await;
 
-        let mock = tui
-            .terminal
-            .as_any()
-            .downcast_ref::<MockTerminal>()
-            .unwrap();
+        let mock = mock_terminal(&tui);
         assert!(mock.started, "run() should call start()");
         assert!(mock.stopped, "run() should call stop()");
     }
@@ -1174,11 +1826,7 @@ mod tests {
         })
         .await;
 
-        let mock = tui
-            .terminal
-            .as_any()
-            .downcast_ref::<MockTerminal>()
-            .unwrap();
+        let mock = mock_terminal(&tui);
         // At least the initial render + one render after handler
         assert!(mock.writes.len() >= 1, "should render at least once");
     }
@@ -1273,6 +1921,49 @@ mod tests {
         assert_eq!(received.len(), 0, "without focus, no key forwarding");
     }
 
+    #[tokio::test]
+    async fn run_hidden_focused_child_gets_no_keys() {
+        use std::sync::{Arc, Mutex};
+
+        struct KeyTracker {
+            keys: Arc<Mutex<Vec<crossterm::event::KeyEvent>>>,
+        }
+
+        impl Component for KeyTracker {
+            fn render(&self, _width: u16) -> Vec<String> {
+                vec![]
+            }
+            fn handle_input(&mut self, event: &crossterm::event::KeyEvent) {
+                self.keys.lock().unwrap().push(*event);
+            }
+        }
+
+        let keys = Arc::new(Mutex::new(Vec::new()));
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        tui.root().add_child(Box::new(KeyTracker {
+            keys: keys.clone(),
+        }));
+        tui.set_focus(Some(0));
+        tui.root().set_child_visible(0, false);
+
+        let ct_tx = tui.crossterm_event_tx();
+        tokio::spawn(async move {
+            let key = crossterm::event::KeyEvent::new(
+                crossterm::event::KeyCode::Char('x'),
+                crossterm::event::KeyModifiers::NONE,
+            );
+            ct_tx.send(crossterm::event::Event::Key(key)).unwrap();
+        });
+
+        tui.run(|_event, tui| {
+            tui.quit();
+        })
+        .await;
+
+        let received = keys.lock().unwrap();
+        assert_eq!(received.len(), 0, "hidden child should not receive keys");
+    }
+
     #[tokio::test]
     async fn run_resize_event_arrives() {
         let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
@@ -1298,6 +1989,31 @@ mod tests {
         assert!(received_resize, "handler should receive resize event");
     }
 
+    #[tokio::test]
+    async fn run_renders_at_new_size_after_mock_resize() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        tui.root()
+            .add_child(Box::new(StubComponent::new(&["hello"])));
+        let ct_tx = tui.crossterm_event_tx();
+        let mock = mock_terminal_mut(&mut tui);
+        mock.set_event_tx(ct_tx);
+        mock.resize(40, 10);
+
+        let mut sizes = Vec::new();
+        tui.run(|event, tui| {
+            if let Event::Resize(w, h) = event {
+                sizes.push((w, h));
+            }
+            tui.quit();
+        })
+        .await;
+
+        assert_eq!(sizes, vec![(40, 10)]);
+        assert_eq!(tui.previous_width(), 40);
+        let frames = mock_terminal(&tui).frames();
+        assert!(frames.iter().any(|frame| frame.contains("hello")));
+    }
+
     // ── Stop cursor repositioning (US-007a) ─────────────────────────
 
     #[test]
@@ -1342,19 +2058,72 @@ mod tests {
         tui.render();
         // After first render: hardware_cursor_row = 3, cursor_row = 3
 
-        let writes_before_stop = mock_terminal(&tui).writes.len();
         tui.stop();
 
         let mock = mock_terminal(&tui);
         assert!(mock.stopped, "terminal.stop() was called");
-        // No additional writes for cursor movement
+        // Only the SGR reset, no cursor movement
         assert_eq!(
-            mock.writes.len(),
-            writes_before_stop,
+            mock.last_frame(),
+            Some("\x1b[0m"),
             "stop() should not write cursor movement when already at end"
         );
     }
 
+    #[test]
+    fn stop_disables_modes_enabled_by_start() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        tui.set_mouse_capture(true);
+        tui.set_bracketed_paste(true);
+        tui.start();
+        let enabled = mock_terminal(&tui).last_frame().unwrap().to_string();
+        assert!(enabled.contains("\x1b[?1002h"));
+        assert!(enabled.contains("\x1b[?1006h"));
+        assert!(enabled.contains("\x1b[?2004h"));
+        assert!(!enabled.contains("\x1b[?1049h"), "alt screen was not requested");
+
+        tui.stop();
+        let disabled = mock_terminal(&tui).last_frame().unwrap();
+        assert!(disabled.contains("\x1b[0m"));
+        assert!(disabled.contains("\x1b[?1002l"));
+        assert!(disabled.contains("\x1b[?1006l"));
+        assert!(disabled.contains("\x1b[?2004l"));
+        assert!(!disabled.contains("\x1b[?1049l"));
+    }
+
+    #[test]
+    fn stop_disables_alt_screen() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        tui.set_alt_screen(true);
+        tui.start();
+        assert_eq!(mock_terminal(&tui).last_frame(), Some("\x1b[?1049h"));
+        tui.stop();
+        assert_eq!(mock_terminal(&tui).last_frame(), Some("\x1b[0m\x1b[?1049l"));
+    }
+
+    #[test]
+    fn stop_erases_visible_overlay() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(20, 10)));
+        tui.root()
+            .add_child(Box::new(StubComponent::new(&["base line"])));
+        tui.show_overlay(
+            Box::new(StubComponent::new(&["OVERLAY"])),
+            OverlayOptions {
+                width: 10,
+                max_height: None,
+                anchor: Anchor::TopLeft,
+                offset_x: 0,
+                offset_y: 0,
+            },
+        );
+        tui.render();
+        assert!(strip_ansi(&tui.previous_lines()[0]).starts_with("OVERLAY"));
+
+        tui.stop();
+        assert!(!tui.has_overlay());
+        assert_eq!(tui.previous_lines(), ["base line"]);
+    }
+
     // ── Overlay: splice_overlay_into_line ───────────────────────────
 
     #[test]
@@ -1393,7 +2162,7 @@ mod tests {
         // before="hi", pad="   " (3 spaces to reach col 5), overlay="XX", after=""
         assert_eq!(
             result,
-            "hi   \x1b[0mXX\x1b[0m"
+            "hi\x1b[0m   XX\x1b[0m"
         );
     }
 
@@ -1403,8 +2172,58 @@ mod tests {
         // before="", pad="   ", overlay, after=""
         assert_eq!(
             result,
-            "   \x1b[0moverlay\x1b[0m"
+            "\x1b[0m   overlay\x1b[0m"
+        );
+    }
+
+    /// The SGR state in effect at each visible column of `line`.
+    fn sgr_by_column(line: &str) -> Vec<String> {
+        (0..visible_width(line))
+            .map(|col| slice_from_column(line, col).0)
+            .collect()
+    }
+
+    #[test]
+    fn splice_overlay_keeps_base_color_out_of_overlay() {
+        let base = "\x1b[41mred background line\x1b[0m";
+        let result = super::splice_overlay_into_line(base, 4, "OVL", 3);
+        assert_eq!(
+            result,
+            "\x1b[41mred \x1b[0mOVL\x1b[0m\x1b[41mkground line\x1b[0m"
         );
+        let states = sgr_by_column(&result);
+        assert!(states[..4].iter().all(|s| s == "\x1b[41m"));
+        assert!(states[4..7].iter().all(|s| s.is_empty()));
+        assert!(states[7..].iter().all(|s| s == "\x1b[41m"));
+    }
+
+    #[test]
+    fn splice_overlay_keeps_overlay_color_out_of_base() {
+        // Neither side closes its own color.
+        let base = "\x1b[1m\x1b[34mbold blue text";
+        let result = super::splice_overlay_into_line(base, 2, "\x1b[32mgreen", 5);
+        let states = sgr_by_column(&result);
+        assert!(states[..2].iter().all(|s| s == "\x1b[1m\x1b[34m"));
+        assert!(states[2..7].iter().all(|s| s == "\x1b[32m"));
+        assert!(states[7..].iter().all(|s| s == "\x1b[1m\x1b[34m"));
+        assert_eq!(strip_ansi(&result), "bogreenue text");
+    }
+
+    #[test]
+    fn splice_overlay_padding_is_uncolored() {
+        // A base that ends with its background still open.
+        let result = super::splice_overlay_into_line("\x1b[44mhi", 5, "XX", 2);
+        let states = sgr_by_column(&result);
+        assert_eq!(states[..2], ["\x1b[44m", "\x1b[44m"]);
+        assert!(states[2..].iter().all(|s| s.is_empty()));
+    }
+
+    #[test]
+    fn splice_overlay_blanks_half_covered_wide_char() {
+        // "你" spans columns 2-3; the overlay covers columns 0-2.
+        let result = super::splice_overlay_into_line("ab你好", 0, "XYZ", 3);
+        assert_eq!(result, "\x1b[0mXYZ\x1b[0m 好");
+        assert_eq!(visible_width(&result), 6);
     }
 
     // ── Overlay: calculate_overlay_position ─────────────────────────
@@ -1476,6 +2295,52 @@ mod tests {
         assert_eq!(col, 0); // clamped to 0
     }
 
+    #[test]
+    fn overlay_position_positive_offset_stays_on_screen() {
+        let (row, col) = super::calculate_overlay_position(
+            &OverlayOptions {
+                width: 10,
+                max_height: None,
+                anchor: Anchor::BottomRight,
+                offset_x: 5,
+                offset_y: 3,
+            },
+            80, 20, 10, 4,
+        );
+        assert_eq!(row, 16); // clamped to 20 - 4
+        assert_eq!(col, 70); // clamped to 80 - 10
+    }
+
+    #[test]
+    fn overlay_position_large_offset_clamped_to_far_edge() {
+        let (row, col) = super::calculate_overlay_position(
+            &OverlayOptions {
+                width: 10,
+                max_height: None,
+                anchor: Anchor::Center,
+                offset_x: 100,
+                offset_y: 100,
+            },
+            80, 20, 10, 4,
+        );
+        assert_eq!((row, col), (16, 70));
+    }
+
+    #[test]
+    fn overlay_position_oversized_pinned_top_left() {
+        let (row, col) = super::calculate_overlay_position(
+            &OverlayOptions {
+                width: 100,
+                max_height: None,
+                anchor: Anchor::BottomRight,
+                offset_x: 4,
+                offset_y: 2,
+            },
+            80, 20, 100, 30,
+        );
+        assert_eq!((row, col), (0, 0));
+    }
+
     // ── Overlay: TUI methods ────────────────────────────────────────
 
     #[test]
@@ -1628,11 +2493,11 @@ mod tests {
         let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(20, 24)));
         // Base: 1 line
         tui.root().add_child(Box::new(StubComponent::new(&["base"])));
-        // Overlay at row 2 (beyond base)
+        // Overlay taller than the base: pinned to the top, offset ignored
         tui.show_overlay(
-            Box::new(StubComponent::new(&["overlay"])),
+            Box::new(StubComponent::new(&["over", "lay", "!"])),
             OverlayOptions {
-                width: 7,
+                width: 4,
                 max_height: None,
                 anchor: Anchor::TopLeft,
                 offset_x: 0,
@@ -1643,9 +2508,31 @@ mod tests {
 
         let lines = tui.previous_lines();
         assert_eq!(lines.len(), 3); // extended from 1 to 3
-        assert_eq!(lines[0], "base");
-        assert_eq!(lines[1], ""); // empty padding line
-        assert_eq!(lines[2], "\x1b[0moverlay\x1b[0m");
+        assert_eq!(lines[0], "\x1b[0mover\x1b[0m");
+        assert_eq!(lines[1], "\x1b[0mlay\x1b[0m");
+        assert_eq!(lines[2], "\x1b[0m!\x1b[0m");
+    }
+
+    #[test]
+    fn overlay_offset_past_content_is_clamped() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(20, 24)));
+        tui.root()
+            .add_child(Box::new(StubComponent::new(&["line one", "line two"])));
+        tui.show_overlay(
+            Box::new(StubComponent::new(&["OV"])),
+            OverlayOptions {
+                width: 2,
+                max_height: None,
+                anchor: Anchor::BottomRight,
+                offset_x: 3,
+                offset_y: 5,
+            },
+        );
+        tui.render();
+
+        let lines = tui.previous_lines();
+        assert_eq!(lines.len(), 2, "no lines added below the content");
+        assert_eq!(strip_ansi(&lines[1]), format!("line two{}OV", " ".repeat(10)));
     }
 
     #[test]
@@ -1731,6 +2618,8 @@ mod tests {
     #[test]
     fn nested_overlays_restore_focus_correctly() {
         let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        tui.root().add_child(Box::new(StubComponent::new(&["child0"])));
+        tui.root().add_child(Box::new(StubComponent::new(&["child1"])));
         tui.set_focus(Some(0));
 
         // Show first overlay — saves focus=Some(0)
@@ -1955,4 +2844,299 @@ mod tests {
         assert_eq!(overlay2_keys.lock().unwrap().len(), 1);
         assert_eq!(overlay1_keys.lock().unwrap().len(), 0);
     }
+
+    #[tokio::test]
+    async fn overlay_container_tab_cycles_its_own_focus() {
+        use crate::components::Input;
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        use std::cell::RefCell;
+
+        let submitted = Rc::new(RefCell::new(Vec::new()));
+        let mut form = Container::new();
+        form.set_tab_cycles_focus(true);
+        for field in ["first", "second"] {
+            let submitted = submitted.clone();
+            let mut input = Input::new();
+            input.on_submit = Some(Box::new(move |value: &str| {
+                submitted.borrow_mut().push(format!("{}={}", field, value));
+            }));
+            form.add_child(Box::new(input));
+        }
+
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(80, 24)));
+        tui.root().add_child(Box::new(Input::new()));
+        tui.set_focus(Some(0));
+        tui.show_overlay(
+            Box::new(form),
+            OverlayOptions {
+                width: 20,
+                max_height: None,
+                anchor: Anchor::Center,
+                offset_x: 0,
+                offset_y: 0,
+            },
+        );
+        tui.overlay_set_focus(Some(0));
+
+        let keys = [
+            KeyCode::Char('a'),
+            KeyCode::Tab,
+            KeyCode::Char('b'),
+            KeyCode::Enter,
+            KeyCode::BackTab,
+            KeyCode::Enter,
+        ];
+        let ct_tx = tui.crossterm_event_tx();
+        for code in keys {
+            let key = KeyEvent::new(code, KeyModifiers::NONE);
+            ct_tx.send(crossterm::event::Event::Key(key)).unwrap();
+        }
+
+        let mut remaining = keys.len();
+        tui.run(|_event, tui| {
+            remaining -= 1;
+            if remaining == 0 {
+                tui.quit();
+            }
+        })
+        .await;
+
+        assert_eq!(*submitted.borrow(), vec!["second=b", "first=a"]);
+        let base = tui.root().child_mut(0).unwrap().form_value();
+        assert_eq!(base.as_deref(), Some(""));
+    }
+
+    // ── Bell / flash ────────────────────────────────────────────────
+
+    #[test]
+    fn bell_writes_bel_immediately() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(20, 24)));
+        tui.bell();
+        assert_eq!(mock_terminal(&tui).last_frame(), Some("\x07"));
+    }
+
+    #[test]
+    fn flash_inverts_one_frame_then_restores() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(10, 24)));
+        tui.root()
+            .add_child(Box::new(StubComponent::new(&["hi", "\x1b[1mbold\x1b[0m"])));
+        tui.render();
+
+        tui.flash();
+        let flashed = mock_terminal(&tui).last_frame().unwrap().to_string();
+        assert!(flashed.contains("\x1b[7mhi        \x1b[27m"));
+        assert!(flashed.contains("\x1b[7m\x1b[1mbold\x1b[0m\x1b[7m      \x1b[27m"));
+        assert!(tui.flash_shown.is_some());
+
+        tui.render();
+        let restored = mock_terminal(&tui).last_frame().unwrap();
+        assert!(!restored.contains("\x1b[7m"));
+        assert!(restored.contains("hi\x1b[0m"));
+        assert_eq!(tui.previous_lines(), ["hi", "\x1b[1mbold\x1b[0m"]);
+        assert!(tui.flash_shown.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn run_restores_after_flash() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(10, 24)));
+        tui.root().add_child(Box::new(StubComponent::new(&["hi"])));
+        let tx = tui.event_tx();
+        tx.send(()).unwrap();
+
+        let mut flashed = false;
+        tui.run(|_event, tui| {
+            if !flashed {
+                flashed = true;
+                tui.flash();
+                // Arrives after the restore is due.
+                let tx = tui.event_tx();
+                tokio::spawn(async move {
+                    tokio::time::sleep(FLASH_DURATION * 2).await;
+                    tx.send(()).unwrap();
+                });
+            } else {
+                tui.quit();
+            }
+        })
+        .await;
+
+        let frames = mock_terminal(&tui).frames();
+        let flash_at = frames.iter().position(|f| f.contains("\x1b[7m")).unwrap();
+        assert!(frames[flash_at + 1..].iter().any(|f| f.contains("hi\x1b[0m")));
+        assert!(tui.flash_shown.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn run_keeps_flash_on_screen_until_its_timer() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(10, 24)));
+        tui.root().add_child(Box::new(StubComponent::new(&["hi"])));
+        let tx = tui.event_tx();
+        tx.send(()).unwrap();
+
+        let mut events = 0;
+        let mut mid_flash_frame = None;
+        tui.run(|_event, tui| {
+            events += 1;
+            match events {
+                1 => {
+                    tui.flash();
+                    let tx = tui.event_tx();
+                    tokio::spawn(async move {
+                        tokio::time::sleep(FLASH_DURATION / 2).await;
+                        tx.send(()).unwrap();
+                        tokio::time::sleep(FLASH_DURATION * 2).await;
+                        tx.send(()).unwrap();
+                    });
+                }
+                2 => {
+                    let frame = mock_terminal(tui).last_frame().map(str::to_string);
+                    mid_flash_frame = frame;
+                }
+                _ => tui.quit(),
+            }
+        })
+        .await;
+
+        // The renders after events 1 and 2 waited for the flash to end.
+        assert!(mid_flash_frame.unwrap().contains("\x1b[7m"));
+        let last = mock_terminal(&tui).last_frame().unwrap();
+        assert!(!last.contains("\x1b[7m"));
+        assert!(tui.flash_shown.is_none());
+    }
+
+    // ── Toasts ──────────────────────────────────────────────────────
+
+    #[test]
+    fn render_toast_draws_bordered_box() {
+        assert_eq!(
+            super::render_toast("Saved", 80),
+            vec!["┌───────┐", "│ Saved │", "└───────┘"]
+        );
+    }
+
+    #[test]
+    fn render_toast_truncates_to_width() {
+        let lines = super::render_toast("Connection lost", 10);
+        assert_eq!(lines[1], "│ Conne… │");
+        assert!(lines.iter().all(|l| visible_width(l) == 10));
+        assert!(super::render_toast("x", 3).is_empty());
+    }
+
+    #[tokio::test]
+    async fn toasts_stack_in_top_right() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(20, 24)));
+        tui.root()
+            .add_child(Box::new(StubComponent::new(&["base content"])));
+        tui.toast("one", Duration::from_secs(5));
+        tui.toast("two", Duration::from_secs(5));
+        tui.render();
+
+        let lines: Vec<String> = tui.previous_lines().iter().map(|l| strip_ansi(l)).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "base content ┌─────┐",
+                "             │ one │",
+                "             └─────┘",
+                "             ┌─────┐",
+                "             │ two │",
+                "             └─────┘",
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn toast_dismissed_after_duration() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(40, 24)));
+        tui.toast("Copied!", Duration::from_secs(1));
+        tui.render();
+        assert!(tui.previous_lines().iter().any(|l| l.contains("Copied!")));
+
+        // Quit well after the toast's timer; the paused clock auto-advances.
+        let tx = tui.event_tx();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(2)).await;
+            tx.send(()).unwrap();
+        });
+
+        let mut shown_before_quit = true;
+        tui.run(|_event, tui| {
+            shown_before_quit = tui.previous_lines().iter().any(|l| l.contains("Copied!"));
+            tui.quit();
+        })
+        .await;
+
+        assert!(!shown_before_quit, "toast should be gone before the later event");
+        assert!(tui.previous_lines().is_empty());
+    }
+
+    // ── Frame rate limiting ─────────────────────────────────────────
+
+    /// Renders a shared counter, so every increment changes the output.
+    struct Counter(Rc<Cell<usize>>);
+
+    impl Component for Counter {
+        fn render(&self, _width: u16) -> Vec<String> {
+            vec![format!("count {}", self.0.get())]
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn max_fps_coalesces_event_bursts() {
+        let mut tui: TUI<()> = TUI::new(Box::new(MockTerminal::new(40, 24)));
+        let count = Rc::new(Cell::new(0));
+        tui.root().add_child(Box::new(Counter(count.clone())));
+        tui.set_max_fps(30);
+
+        let tx = tui.event_tx();
+        tokio::spawn(async move {
+            for _ in 0..100 {
+                tx.send(()).unwrap();
+            }
+        });
+
+        tui.run(|_event, tui| {
+            count.set(count.get() + 1);
+            if count.get() == 100 {
+                tui.quit();
+            }
+        })
+        .await;
+
+        // Initial frame plus the owed frame flushed on quit — not ~100.
+        assert!(
+            mock_terminal(&tui).writes.len() <= 3,
+            "expected coalesced renders, got {} writes",
+            mock_terminal(&tui).writes.len()
+        );
+        assert_eq!(tui.previous_lines(), &["count 100"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn deferred_render_fires_when_frame_is_due() {
+        let mut tui: TUI<u8> = TUI::new(Box::new(MockTerminal::new(40, 24)));
+        let count = Rc::new(Cell::new(0));
+        tui.root().add_child(Box::new(Counter(count.clone())));
+        tui.set_max_fps(10);
+
+        let tx = tui.event_tx();
+        tokio::spawn(async move {
+            tx.send(1).unwrap();
+            // Well past the 100ms frame, so the owed render has happened.
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            tx.send(2).unwrap();
+        });
+
+        let mut seen_before_second = Vec::new();
+        tui.run(|event, tui| match event {
+            Event::User(1) => count.set(1),
+            _ => {
+                seen_before_second = tui.previous_lines().to_vec();
+                tui.quit();
+            }
+        })
+        .await;
+
+        assert_eq!(seen_before_second, vec!["count 1"]);
+    }
 }
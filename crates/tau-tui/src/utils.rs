@@ -16,6 +16,7 @@ const BEL: u8 = 0x07;
 /// - CSI sequences: `\x1b[...{final}` where final byte is 0x40–0x7E
 /// - OSC sequences: `\x1b]...(\x07|\x1b\\)`
 /// - APC sequences: `\x1b_...(\x07|\x1b\\)`
+/// - DCS sequences: `\x1bP...(\x07|\x1b\\)` (Sixel, terminal responses)
 pub fn extract_ansi_code(s: &str, pos: usize) -> Option<(String, usize)> {
     let bytes = s.as_bytes();
     if pos >= bytes.len() || bytes[pos] != ESC {
@@ -31,6 +32,7 @@ pub fn extract_ansi_code(s: &str, pos: usize) -> Option<(String, usize)> {
         b'[' => extract_csi(bytes, pos),
         b']' => extract_string_sequence(bytes, pos),
         b'_' => extract_string_sequence(bytes, pos),
+        b'P' => extract_string_sequence(bytes, pos),
         _ => None,
     }
 }
@@ -61,11 +63,11 @@ fn extract_csi(bytes: &[u8], pos: usize) -> Option<(String, usize)> {
     }
 }
 
-/// Extract an OSC (`\x1b]`) or APC (`\x1b_`) sequence, terminated by BEL
-/// (`\x07`) or ST (`\x1b\\`).
+/// Extract an OSC (`\x1b]`), APC (`\x1b_`), or DCS (`\x1bP`) sequence,
+/// terminated by BEL (`\x07`) or ST (`\x1b\\`).
 fn extract_string_sequence(bytes: &[u8], pos: usize) -> Option<(String, usize)> {
     let start = pos;
-    let mut i = pos + 2; // skip ESC and ] or _
+    let mut i = pos + 2; // skip ESC and ], _ or P
 
     while i < bytes.len() {
         if bytes[i] == BEL {
@@ -86,8 +88,8 @@ fn extract_string_sequence(bytes: &[u8], pos: usize) -> Option<(String, usize)>
 
 /// Remove all ANSI escape sequences from a string.
 ///
-/// Strips CSI (`\x1b[...`), OSC (`\x1b]...\x07`), and APC (`\x1b_...\x07`)
-/// sequences, returning only the visible text.
+/// Strips CSI (`\x1b[...`), OSC (`\x1b]...\x07`), APC (`\x1b_...\x07`),
+/// and DCS (`\x1bP...\x1b\\`) sequences, returning only the visible text.
 pub fn strip_ansi(s: &str) -> String {
     let bytes = s.as_bytes();
     let mut result = String::with_capacity(s.len());
@@ -143,9 +145,17 @@ pub fn visible_width(s: &str) -> usize {
 /// If `max_width` is smaller than the ellipsis width, returns a truncation
 /// to exactly `max_width` columns with no ellipsis.
 pub fn truncate_to_width(s: &str, max_width: usize, ellipsis: &str) -> String {
+    truncate_to_width_info(s, max_width, ellipsis).0
+}
+
+/// Like [`truncate_to_width`], but also reports whether the string was cut.
+///
+/// Returns `(result, was_truncated)`; `was_truncated` is `false` exactly when
+/// `s` already fit and is returned unchanged.
+pub fn truncate_to_width_info(s: &str, max_width: usize, ellipsis: &str) -> (String, bool) {
     let full_visible = visible_width(s);
     if full_visible <= max_width {
-        return s.to_string();
+        return (s.to_string(), false);
     }
 
     let ellipsis_width = visible_width(ellipsis);
@@ -191,7 +201,7 @@ pub fn truncate_to_width(s: &str, max_width: usize, ellipsis: &str) -> String {
         result.push_str(ellipsis);
     }
 
-    result
+    (result, true)
 }
 
 /// Check if a CSI code is an SGR (Select Graphic Rendition) code.
@@ -218,6 +228,176 @@ pub(crate) fn sgr_prefix(state: &[String]) -> String {
     state.concat()
 }
 
+// ── Color depth ─────────────────────────────────────────────────────
+
+/// How many colors a terminal can display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    /// 24-bit RGB (`38;2;r;g;b`).
+    TrueColor,
+    /// The xterm 256-color palette (`38;5;n`).
+    Ansi256,
+    /// The 16 basic colors (`30`–`37`, `90`–`97`).
+    Ansi16,
+}
+
+/// Guess the terminal's color depth from `$COLORTERM` and `$TERM`.
+pub fn detect_color_depth() -> ColorDepth {
+    color_depth_from(
+        std::env::var("COLORTERM").ok().as_deref(),
+        std::env::var("TERM").ok().as_deref(),
+    )
+}
+
+fn color_depth_from(colorterm: Option<&str>, term: Option<&str>) -> ColorDepth {
+    if matches!(colorterm, Some("truecolor") | Some("24bit")) {
+        return ColorDepth::TrueColor;
+    }
+    match term {
+        Some(t) if t.ends_with("-direct") => ColorDepth::TrueColor,
+        Some(t) if t.contains("256color") => ColorDepth::Ansi256,
+        _ => ColorDepth::Ansi16,
+    }
+}
+
+/// Rewrite truecolor SGR colors (`38;2;r;g;b` / `48;2;r;g;b`) into the
+/// nearest color available at `target` depth. All other codes and text are
+/// left intact; with `ColorDepth::TrueColor` the string is returned unchanged.
+pub fn downsample_truecolor(s: &str, target: ColorDepth) -> String {
+    if target == ColorDepth::TrueColor || !s.contains("2;") {
+        return s.to_string();
+    }
+
+    let bytes = s.as_bytes();
+    let mut result = String::with_capacity(s.len());
+    let mut i = 0;
+    let mut copied = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == ESC {
+            if let Some((code, len)) = extract_ansi_code(s, i) {
+                if is_sgr(&code) {
+                    result.push_str(&s[copied..i]);
+                    result.push_str(&downsample_sgr(&code, target));
+                    copied = i + len;
+                }
+                i += len;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    result.push_str(&s[copied..]);
+    result
+}
+
+/// Downsample the truecolor parameters of one SGR code.
+fn downsample_sgr(code: &str, target: ColorDepth) -> String {
+    let params: Vec<&str> = code[2..code.len() - 1].split(';').collect();
+    let mut out: Vec<String> = Vec::with_capacity(params.len());
+    let mut i = 0;
+
+    while i < params.len() {
+        let rgb = match (params[i], params.get(i + 1)) {
+            ("38", Some(&"2")) | ("48", Some(&"2")) if i + 4 < params.len() => {
+                let channel = |k: usize| params[i + k].parse::<u8>().ok();
+                match (channel(2), channel(3), channel(4)) {
+                    (Some(r), Some(g), Some(b)) => Some((r, g, b)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        match rgb {
+            Some((r, g, b)) => {
+                let background = params[i] == "48";
+                match target {
+                    ColorDepth::Ansi256 => {
+                        out.push(params[i].to_string());
+                        out.push("5".to_string());
+                        out.push(rgb_to_ansi256(r, g, b).to_string());
+                    }
+                    ColorDepth::Ansi16 => {
+                        let idx = rgb_to_ansi16(r, g, b);
+                        let base = if idx < 8 { 30 + idx } else { 90 + idx - 8 };
+                        out.push((base + if background { 10 } else { 0 }).to_string());
+                    }
+                    ColorDepth::TrueColor => unreachable!(),
+                }
+                i += 5;
+            }
+            None => {
+                out.push(params[i].to_string());
+                i += 1;
+            }
+        }
+    }
+
+    format!("\x1b[{}m", out.join(";"))
+}
+
+/// Channel levels of the 6×6×6 color cube in the xterm 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Default xterm RGB values of the 16 basic colors.
+const ANSI16_PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+fn color_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// Nearest xterm 256-color index: the closer of the color-cube entry
+/// (16–231) and the grayscale ramp entry (232–255).
+pub fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let cube_index = |v: u8| -> usize {
+        match v {
+            0..=47 => 0,
+            48..=114 => 1,
+            _ => (v as usize - 35) / 40,
+        }
+    };
+    let (ri, gi, bi) = (cube_index(r), cube_index(g), cube_index(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    let cube_code = 16 + 36 * ri + 6 * gi + bi;
+
+    let avg = (r as usize + g as usize + b as usize) / 3;
+    let gray_index = if avg > 238 { 23 } else { avg.saturating_sub(3) / 10 };
+    let gray_level = (8 + 10 * gray_index) as u8;
+    let gray = (gray_level, gray_level, gray_level);
+
+    if color_distance((r, g, b), gray) < color_distance((r, g, b), cube) {
+        (232 + gray_index) as u8
+    } else {
+        cube_code as u8
+    }
+}
+
+/// Nearest of the 16 basic colors, as an index 0–15.
+pub fn rgb_to_ansi16(r: u8, g: u8, b: u8) -> u8 {
+    (0..16u8)
+        .min_by_key(|&i| color_distance((r, g, b), ANSI16_PALETTE[i as usize]))
+        .unwrap()
+}
+
 /// Skip the first `skip` visible columns of a string, returning the remainder
 /// along with the active SGR state at that point.
 ///
@@ -261,6 +441,77 @@ pub fn slice_from_column(s: &str, skip: usize) -> (String, String) {
     (sgr_prefix(&sgr_state), s[pos..].to_string())
 }
 
+/// Cut the visible columns `[start, end)` out of a string.
+///
+/// SGR codes before `start` are collapsed into a prefix that re-applies the
+/// style in effect there; codes inside the range are kept in place, and
+/// codes after `end` dropped. A wide character straddling either edge
+/// renders its visible half as a space, so the result is never wider than
+/// `end - start` (it is narrower only if the string ends first).
+pub fn slice_columns(s: &str, start: usize, end: usize) -> String {
+    let bytes = s.as_bytes();
+    let mut sgr_state: Vec<String> = Vec::new();
+    // Filled once the range is entered, starting with the carried style.
+    let mut result: Option<String> = None;
+    let mut col = 0;
+    let mut pos = 0;
+
+    while pos < bytes.len() && col < end {
+        if bytes[pos] == ESC {
+            if let Some((code, len)) = extract_ansi_code(s, pos) {
+                match result.as_mut() {
+                    Some(result) => result.push_str(&code),
+                    None => update_sgr_state(&mut sgr_state, &code),
+                }
+                pos += len;
+                continue;
+            }
+        }
+
+        let Some(grapheme) = s[pos..].graphemes(true).next() else {
+            break;
+        };
+        let w = if grapheme == "\t" { 3 } else { UnicodeWidthStr::width(grapheme) };
+        let next = col + w;
+        if next > start {
+            let result = result.get_or_insert_with(|| sgr_prefix(&sgr_state));
+            if col >= start && next <= end {
+                result.push_str(grapheme);
+            } else {
+                // Straddles an edge: blank the part that shows.
+                result.push_str(&" ".repeat(next.min(end) - col.max(start)));
+            }
+        }
+        col = next;
+        pos += grapheme.len();
+    }
+
+    result.unwrap_or_default()
+}
+
+/// Options for [`wrap_text_with_options`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrapOptions {
+    /// Hard-split a word longer than the width at a column boundary
+    /// (grapheme-aware). When false, such a word gets a line of its own and
+    /// overflows it.
+    pub break_long_words: bool,
+    /// Keep the space a line was broken at as the last character of that
+    /// line, instead of dropping it.
+    pub preserve_trailing_whitespace: bool,
+}
+
+impl Default for WrapOptions {
+    /// The behavior of [`wrap_text_with_ansi`]: break long words, drop the
+    /// space at each break.
+    fn default() -> Self {
+        WrapOptions {
+            break_long_words: true,
+            preserve_trailing_whitespace: false,
+        }
+    }
+}
+
 /// Word-wrap text to fit within `width` visible columns, preserving ANSI codes.
 ///
 /// - Splits on word boundaries (spaces)
@@ -268,8 +519,15 @@ pub fn slice_from_column(s: &str, skip: usize) -> (String, String) {
 /// - Tracks ANSI SGR state and re-applies at the start of each wrapped line
 /// - Hard line breaks (`\n`) are preserved
 ///
-/// Returns empty `Vec` for empty input or zero width.
+/// Returns empty `Vec` for empty input or zero width. Equivalent to
+/// [`wrap_text_with_options`] with `WrapOptions::default()`.
 pub fn wrap_text_with_ansi(text: &str, width: usize) -> Vec<String> {
+    wrap_text_with_options(text, width, WrapOptions::default())
+}
+
+/// Word-wrap text like [`wrap_text_with_ansi`], with control over long-word
+/// breaking and whitespace at line breaks.
+pub fn wrap_text_with_options(text: &str, width: usize, options: WrapOptions) -> Vec<String> {
     if text.is_empty() || width == 0 {
         return vec![];
     }
@@ -278,16 +536,94 @@ pub fn wrap_text_with_ansi(text: &str, width: usize) -> Vec<String> {
     let mut sgr_state: Vec<String> = Vec::new();
 
     for hard_line in text.split('\n') {
-        wrap_single_line(hard_line, width, &mut sgr_state, &mut result);
+        wrap_single_line(hard_line, width, options, &mut sgr_state, &mut result);
     }
 
     result
 }
 
+/// Number of lines [`wrap_text_with_ansi`] would produce for `text` at `width`,
+/// without building them.
+///
+/// Respects hard line breaks and ignores ANSI codes. Returns 0 for empty
+/// input or zero width.
+pub fn wrapped_height(text: &str, width: usize) -> usize {
+    if text.is_empty() || width == 0 {
+        return 0;
+    }
+    text.split('\n')
+        .map(|hard_line| wrapped_line_count(hard_line, width))
+        .sum()
+}
+
+/// Width-only mirror of [`wrap_single_line`] with default options: counts the
+/// output lines for one hard line.
+fn wrapped_line_count(text: &str, width: usize) -> usize {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    let mut lines = 0;
+    let mut current_width: usize = 0;
+    let mut break_width: Option<usize> = None;
+
+    while i < bytes.len() {
+        if bytes[i] == ESC {
+            if let Some((_, len)) = extract_ansi_code(text, i) {
+                i += len;
+                continue;
+            }
+        }
+
+        let grapheme = match text[i..].graphemes(true).next() {
+            Some(g) => g,
+            None => {
+                i += 1;
+                continue;
+            }
+        };
+        i += grapheme.len();
+
+        if grapheme == " " {
+            if current_width + 1 > width && current_width > 0 {
+                lines += 1;
+                current_width = 0;
+                break_width = None;
+            } else {
+                break_width = Some(current_width);
+                current_width += 1;
+            }
+            continue;
+        }
+
+        let gw = if grapheme == "\t" {
+            3
+        } else {
+            UnicodeWidthStr::width(grapheme)
+        };
+        if current_width + gw > width {
+            lines += 1;
+            if let Some(bw) = break_width.take() {
+                current_width -= bw + 1;
+            } else if current_width == 0 {
+                // Over-wide grapheme on a line of its own
+                continue;
+            } else {
+                current_width = 0;
+            }
+        }
+        current_width += gw;
+    }
+
+    if current_width > 0 || lines == 0 {
+        lines += 1;
+    }
+    lines
+}
+
 /// Wrap a single line (no newlines) into one or more output lines.
 fn wrap_single_line(
     text: &str,
     width: usize,
+    options: WrapOptions,
     sgr_state: &mut Vec<String>,
     out: &mut Vec<String>,
 ) {
@@ -350,12 +686,17 @@ fn wrap_single_line(
             continue;
         }
 
-        // Non-space visible character
-        if current_width + gw > width {
+        // Non-space visible character. Without a break point and with long-word
+        // breaking off, the word overflows until the next space.
+        if current_width + gw > width && (break_pos.is_some() || options.break_long_words) {
             if let Some(bp) = break_pos {
                 // Break at last space
                 let after_break = current_line[bp + 1..].to_string();
-                current_line.truncate(bp);
+                if options.preserve_trailing_whitespace {
+                    current_line.truncate(bp + 1);
+                } else {
+                    current_line.truncate(bp);
+                }
                 out.push(current_line);
 
                 let prefix = sgr_prefix(&break_sgr);
@@ -441,6 +782,13 @@ mod tests {
         assert_eq!(strip_ansi(input), "visible");
     }
 
+    #[test]
+    fn strip_ansi_strips_dcs() {
+        // Sixel image payload, ST-terminated
+        let input = "before\x1bPq#0;2;0;0;0#0~~@@vv@@~~\x1b\\after";
+        assert_eq!(strip_ansi(input), "beforeafter");
+    }
+
     #[test]
     fn strip_ansi_preserves_unicode() {
         assert_eq!(strip_ansi("\x1b[31m你好\x1b[0m"), "你好");
@@ -594,6 +942,12 @@ mod tests {
         assert_eq!(visible_width(input), 5);
     }
 
+    #[test]
+    fn visible_width_ignores_dcs() {
+        let input = "ok\x1bP1$r0m\x1b\\";
+        assert_eq!(visible_width(input), 2);
+    }
+
     // ── truncate_to_width ───────────────────────────────────────────
 
     #[test]
@@ -658,6 +1012,22 @@ mod tests {
         assert_eq!(truncate_to_width("", 5, "..."), "");
     }
 
+    #[test]
+    fn truncate_info_reports_truncation() {
+        assert_eq!(
+            truncate_to_width_info("hello world", 8, "..."),
+            ("hello...".to_string(), true)
+        );
+    }
+
+    #[test]
+    fn truncate_info_reports_no_truncation() {
+        assert_eq!(
+            truncate_to_width_info("\x1b[31mhello\x1b[0m", 5, "..."),
+            ("\x1b[31mhello\x1b[0m".to_string(), false)
+        );
+    }
+
     // ── wrap_text_with_ansi ─────────────────────────────────────────
 
     #[test]
@@ -792,6 +1162,173 @@ mod tests {
         assert_eq!(result, vec!["你"]);
     }
 
+    #[test]
+    fn wrap_options_default_matches_wrap_text_with_ansi() {
+        let text = "\x1b[31mthe quick brown\x1b[0m fox jumps over the lazy dog";
+        assert_eq!(
+            wrap_text_with_options(text, 10, WrapOptions::default()),
+            wrap_text_with_ansi(text, 10)
+        );
+    }
+
+    #[test]
+    fn wrap_long_token_hard_split() {
+        let token = "abcdefghijklmnopqrstuvwxyz0123"; // 30 columns
+        let options = WrapOptions {
+            break_long_words: true,
+            ..WrapOptions::default()
+        };
+        assert_eq!(
+            wrap_text_with_options(token, 10, options),
+            vec!["abcdefghij", "klmnopqrst", "uvwxyz0123"]
+        );
+    }
+
+    #[test]
+    fn wrap_long_token_overflows_without_break() {
+        let options = WrapOptions {
+            break_long_words: false,
+            ..WrapOptions::default()
+        };
+        assert_eq!(
+            wrap_text_with_options("go abcdefghijklmnopqrstuvwxyz0123 end", 10, options),
+            vec!["go", "abcdefghijklmnopqrstuvwxyz0123", "end"]
+        );
+    }
+
+    #[test]
+    fn wrap_long_token_hard_split_wide_chars() {
+        // 15 wide chars = 30 columns; width 10 fits 5 per line
+        let token = "你好世界测试编程代码你好世界测";
+        let result = wrap_text_with_options(token, 10, WrapOptions::default());
+        assert_eq!(result.len(), 3);
+        assert!(result.iter().all(|line| visible_width(line) == 10));
+    }
+
+    #[test]
+    fn wrap_preserve_trailing_whitespace() {
+        let options = WrapOptions {
+            preserve_trailing_whitespace: true,
+            ..WrapOptions::default()
+        };
+        assert_eq!(
+            wrap_text_with_options("hello world", 8, options),
+            vec!["hello ", "world"]
+        );
+        assert_eq!(wrap_text_with_ansi("hello world", 8), vec!["hello", "world"]);
+    }
+
+    // ── wrapped_height ──────────────────────────────────────────────
+
+    #[test]
+    fn wrapped_height_phrase_at_width_10() {
+        assert_eq!(wrapped_height("the quick brown fox jumps", 10), 3);
+    }
+
+    #[test]
+    fn wrapped_height_empty() {
+        assert_eq!(wrapped_height("", 10), 0);
+        assert_eq!(wrapped_height("hello", 0), 0);
+    }
+
+    #[test]
+    fn wrapped_height_explicit_newlines() {
+        assert!(wrapped_height("one\ntwo\nthree", 80) >= 3);
+        assert_eq!(wrapped_height("a\n\nb", 80), 3);
+    }
+
+    #[test]
+    fn wrapped_height_matches_wrap_text_with_ansi() {
+        let cases = [
+            "hello world",
+            "\x1b[31mthe quick brown\x1b[0m fox jumps over the lazy dog",
+            "abcdefghijklmnopqrstuvwxyz0123",
+            "你好世界测试编程代码",
+            "trailing space ",
+            "a\tb\tc d e f g",
+            "line one\n\nline three is a bit longer",
+            "你",
+        ];
+        for text in cases {
+            for width in 1..=12 {
+                assert_eq!(
+                    wrapped_height(text, width),
+                    wrap_text_with_ansi(text, width).len(),
+                    "{:?} at width {}",
+                    text,
+                    width
+                );
+            }
+        }
+    }
+
+    // ── downsample_truecolor ────────────────────────────────────────
+
+    #[test]
+    fn rgb_to_ansi256_known_values() {
+        assert_eq!(rgb_to_ansi256(255, 0, 0), 196);
+        assert_eq!(rgb_to_ansi256(0, 0, 0), 16);
+        assert_eq!(rgb_to_ansi256(255, 255, 255), 231);
+        assert_eq!(rgb_to_ansi256(95, 135, 175), 67);
+        // Mid gray lands on the grayscale ramp, not the cube
+        assert_eq!(rgb_to_ansi256(128, 128, 128), 244);
+    }
+
+    #[test]
+    fn rgb_to_ansi16_known_values() {
+        assert_eq!(rgb_to_ansi16(250, 10, 10), 9);
+        assert_eq!(rgb_to_ansi16(0, 0, 0), 0);
+        assert_eq!(rgb_to_ansi16(200, 200, 0), 3);
+    }
+
+    #[test]
+    fn downsample_to_256_rewrites_foreground_and_background() {
+        let input = "\x1b[38;2;255;0;0mred\x1b[48;2;0;0;0m on black\x1b[0m";
+        assert_eq!(
+            downsample_truecolor(input, ColorDepth::Ansi256),
+            "\x1b[38;5;196mred\x1b[48;5;16m on black\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn downsample_to_16_uses_basic_codes() {
+        let input = "\x1b[38;2;255;0;0;48;2;0;0;238mx";
+        assert_eq!(downsample_truecolor(input, ColorDepth::Ansi16), "\x1b[91;44mx");
+    }
+
+    #[test]
+    fn downsample_keeps_other_params_and_codes() {
+        let input = "\x1b[1;38;2;255;0;0;4mbold\x1b[2K\x1b[38;5;33mkept";
+        assert_eq!(
+            downsample_truecolor(input, ColorDepth::Ansi256),
+            "\x1b[1;38;5;196;4mbold\x1b[2K\x1b[38;5;33mkept"
+        );
+    }
+
+    #[test]
+    fn downsample_truecolor_target_is_identity() {
+        let input = "\x1b[38;2;1;2;3mtext";
+        assert_eq!(downsample_truecolor(input, ColorDepth::TrueColor), input);
+    }
+
+    #[test]
+    fn downsample_leaves_malformed_rgb_alone() {
+        let input = "\x1b[38;2;300;0;0mtext\x1b[38;2;1mshort";
+        assert_eq!(downsample_truecolor(input, ColorDepth::Ansi256), input);
+    }
+
+    #[test]
+    fn color_depth_detection() {
+        assert_eq!(
+            color_depth_from(Some("truecolor"), Some("xterm")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(color_depth_from(None, Some("xterm-direct")), ColorDepth::TrueColor);
+        assert_eq!(color_depth_from(None, Some("xterm-256color")), ColorDepth::Ansi256);
+        assert_eq!(color_depth_from(None, Some("xterm")), ColorDepth::Ansi16);
+        assert_eq!(color_depth_from(None, None), ColorDepth::Ansi16);
+    }
+
     // ── slice_from_column ───────────────────────────────────────────
 
     #[test]
@@ -848,6 +1385,29 @@ mod tests {
         assert_eq!(rest, "世界");
     }
 
+    #[test]
+    fn slice_columns_plain_window() {
+        assert_eq!(slice_columns("hello world", 3, 8), "lo wo");
+        assert_eq!(slice_columns("hello", 3, 10), "lo");
+        assert_eq!(slice_columns("hello", 7, 10), "");
+    }
+
+    #[test]
+    fn slice_columns_carries_style_into_window() {
+        let s = "\x1b[31mhello\x1b[0m world";
+        assert_eq!(slice_columns(s, 2, 7), "\x1b[31mllo\x1b[0m w");
+        assert_eq!(slice_columns(s, 6, 9), "wor");
+    }
+
+    #[test]
+    fn slice_columns_blanks_straddling_wide_chars() {
+        // 你 covers columns 1-2, 好 3-4, 世 5-6.
+        let s = "a你好世";
+        assert_eq!(slice_columns(s, 2, 5), " 好");
+        assert_eq!(slice_columns(s, 2, 6), " 好 ");
+        assert_eq!(slice_columns(s, 1, 5), "你好");
+    }
+
     #[test]
     fn slice_from_column_empty_string() {
         let (sgr, rest) = slice_from_column("", 5);